@@ -49,7 +49,7 @@ impl defmt::Format for SendSgi {
 exception_handlers!(Exceptions);
 struct Exceptions;
 impl ExceptionHandlers for Exceptions {
-    extern "C" fn irq_current(_context: &StackedRegisters) {
+    extern "C" fn irq_current(_context: &mut StackedRegisters) {
         let _guard = Nesting::increase();
         println!("{Nesting}> irq_current()");
         while let Some(int_id) = Gic::get_and_acknowledge_interrupt() {
@@ -50,7 +50,7 @@ fn main() -> ! {
 exception_handlers!(Exceptions);
 struct Exceptions;
 impl ExceptionHandlers for Exceptions {
-    extern "C" fn irq_current(_context: &StackedRegisters) {
+    extern "C" fn irq_current(_context: &mut StackedRegisters) {
         let _guard = Nesting::increase();
         println!("{Nesting}> irq_current()");
 
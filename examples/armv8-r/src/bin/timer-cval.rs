@@ -66,7 +66,7 @@ fn main() -> ! {
 exception_handlers!(Exceptions);
 struct Exceptions;
 impl ExceptionHandlers for Exceptions {
-    extern "C" fn irq_current(_context: &StackedRegisters) {
+    extern "C" fn irq_current(_context: &mut StackedRegisters) {
         println!("IRQ!");
         while let Some(int_id) = Gic::get_and_acknowledge_interrupt() {
             if int_id == armv8_r::PHYS_INT_ID {
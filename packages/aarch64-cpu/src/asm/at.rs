@@ -0,0 +1,217 @@
+//! Address translation instructions.
+//!
+//! [`translate`] issues an `AT` instruction and decodes the result it leaves in `PAR_EL1`.
+//!
+//! Note that `PAR_EL1` is shared by every `AT` instruction executed on the PE, so an exception
+//! handler that also translates addresses can overwrite the result. Mask interrupts around
+//! [`translate`] if that is a concern.
+//!
+//! See section C5.4 "A64 System instructions for address translation" in the [ARM Architecture
+//! Reference Manual v8][armv8].
+//!
+//! [armv8]: https://developer.arm.com/documentation/ddi0487/latest/
+
+use tock_registers::{interfaces::Readable, LocalRegisterCopy};
+
+use crate::{asm::barrier, registers::PAR_EL1, syndrome::FaultStatus};
+
+/// The kind of address translation to perform
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Translation {
+    /// Stage 1, EL1&0 regime, as a read from EL1 (`AT S1E1R`)
+    S1E1R,
+    /// Stage 1, EL1&0 regime, as a write from EL1 (`AT S1E1W`)
+    S1E1W,
+    /// Stage 1, EL1&0 regime, as a read from EL0 (`AT S1E0R`)
+    S1E0R,
+    /// Stage 1, EL1&0 regime, as a write from EL0 (`AT S1E0W`)
+    S1E0W,
+    /// Stage 1, EL1&0 regime, as a read from EL1 taking `PSTATE.PAN` into account
+    /// (`AT S1E1RP`, FEAT_PAN2)
+    S1E1RP,
+    /// Stage 1, EL1&0 regime, as a write from EL1 taking `PSTATE.PAN` into account
+    /// (`AT S1E1WP`, FEAT_PAN2)
+    S1E1WP,
+    /// Stage 1, EL2 regime, as a read (`AT S1E2R`)
+    S1E2R,
+    /// Stage 1, EL2 regime, as a write (`AT S1E2W`)
+    S1E2W,
+    /// Stages 1 and 2, EL1&0 regime, as a read from EL1 (`AT S12E1R`)
+    S12E1R,
+    /// Stages 1 and 2, EL1&0 regime, as a write from EL1 (`AT S12E1W`)
+    S12E1W,
+    /// Stages 1 and 2, EL1&0 regime, as a read from EL0 (`AT S12E0R`)
+    S12E0R,
+    /// Stages 1 and 2, EL1&0 regime, as a write from EL0 (`AT S12E0W`)
+    S12E0W,
+    /// Stage 1, EL3 regime, as a read (`AT S1E3R`)
+    S1E3R,
+    /// Stage 1, EL3 regime, as a write (`AT S1E3W`)
+    S1E3W,
+}
+
+/// Shareability of a translated address
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Shareability {
+    /// Non-shareable
+    NonShareable,
+    /// Outer Shareable
+    OuterShareable,
+    /// Inner Shareable
+    InnerShareable,
+}
+
+/// The result of a successful address translation
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Translated {
+    /// The output (physical or intermediate physical) address, including the page offset of the
+    /// input address
+    pub pa: u64,
+    /// Memory attributes of the output address, in `MAIR_ELx` encoding
+    pub attr: u8,
+    /// Shareability of the output address
+    pub shareability: Shareability,
+    /// `true` if the output address is in the Non-secure address space
+    pub non_secure: bool,
+}
+
+/// The translation stage that reported a fault
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Stage {
+    /// Stage 1
+    Stage1,
+    /// Stage 2
+    Stage2,
+}
+
+/// Why an address translation aborted
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FaultInfo {
+    /// The fault status code
+    pub status: FaultStatus,
+    /// The stage at which the translation aborted
+    pub stage: Stage,
+    /// `true` if a stage 2 fault occurred during a stage 1 translation table walk
+    pub ptw: bool,
+}
+
+/// Translate `va` as described by `kind`
+///
+/// Issues the `AT` instruction followed by an `ISB`, so that the result is visible in `PAR_EL1`,
+/// then decodes `PAR_EL1` with [`decode`].
+///
+/// The instruction must be permitted at the current Exception level; e.g. the `S12*` and `S1E2*`
+/// forms are only available at EL2 and above and the `S1E3*` forms only at EL3.
+pub fn translate(kind: Translation, va: u64) -> Result<Translated, FaultInfo> {
+    at(kind, va);
+    barrier::isb(barrier::SY);
+
+    decode(PAR_EL1.get(), va)
+}
+
+/// Issues the `AT` instruction selected by `kind`
+#[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+#[inline(always)]
+fn at(kind: Translation, va: u64) {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => unsafe {
+            use core::arch::asm;
+
+            match kind {
+                Translation::S1E1R => asm!("AT S1E1R, {}", in(reg) va, options(nostack)),
+                Translation::S1E1W => asm!("AT S1E1W, {}", in(reg) va, options(nostack)),
+                Translation::S1E0R => asm!("AT S1E0R, {}", in(reg) va, options(nostack)),
+                Translation::S1E0W => asm!("AT S1E0W, {}", in(reg) va, options(nostack)),
+                Translation::S1E1RP => asm!(
+                    ".arch_extension pan-rwv",
+                    "AT S1E1RP, {}",
+                    in(reg) va,
+                    options(nostack)
+                ),
+                Translation::S1E1WP => asm!(
+                    ".arch_extension pan-rwv",
+                    "AT S1E1WP, {}",
+                    in(reg) va,
+                    options(nostack)
+                ),
+                Translation::S1E2R => asm!("AT S1E2R, {}", in(reg) va, options(nostack)),
+                Translation::S1E2W => asm!("AT S1E2W, {}", in(reg) va, options(nostack)),
+                Translation::S12E1R => asm!("AT S12E1R, {}", in(reg) va, options(nostack)),
+                Translation::S12E1W => asm!("AT S12E1W, {}", in(reg) va, options(nostack)),
+                Translation::S12E0R => asm!("AT S12E0R, {}", in(reg) va, options(nostack)),
+                Translation::S12E0W => asm!("AT S12E0W, {}", in(reg) va, options(nostack)),
+                Translation::S1E3R => asm!("AT S1E3R, {}", in(reg) va, options(nostack)),
+                Translation::S1E3W => asm!("AT S1E3W, {}", in(reg) va, options(nostack)),
+            }
+        },
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+/// Decode a `PAR_EL1` value produced by translating `va`
+///
+/// `va` only provides the page offset that is added to the output address.
+pub fn decode(par: u64, va: u64) -> Result<Translated, FaultInfo> {
+    let par = LocalRegisterCopy::<u64, PAR_EL1::Register>::new(par);
+
+    if par.is_set(PAR_EL1::F) {
+        return Err(FaultInfo {
+            status: FaultStatus::from_bits(par.read(PAR_EL1::FST) as u8),
+            stage: match par.read_as_enum(PAR_EL1::S) {
+                Some(PAR_EL1::S::Value::Stage2) => Stage::Stage2,
+                _ => Stage::Stage1,
+            },
+            ptw: par.is_set(PAR_EL1::PTW),
+        });
+    }
+
+    // PA[51:12]; bits [51:48] are RES0 unless 52-bit output addresses are in use
+    let pa = (par.get() & 0x000f_ffff_ffff_f000) | (va & 0xfff);
+    Ok(Translated {
+        pa,
+        attr: par.read(PAR_EL1::ATTR) as u8,
+        shareability: match par.read_as_enum(PAR_EL1::SH) {
+            Some(PAR_EL1::SH::Value::InnerShareable) => Shareability::InnerShareable,
+            Some(PAR_EL1::SH::Value::OuterShareable) => Shareability::OuterShareable,
+            _ => Shareability::NonShareable,
+        },
+        non_secure: par.is_set(PAR_EL1::NS),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_success() {
+        // Normal WB memory, Inner Shareable, PA 0x4008_1000
+        let par = 0xff00_0000_4008_1000 | 0b11 << 7;
+        assert_eq!(
+            decode(par, 0xffff_0000_0000_0123),
+            Ok(Translated {
+                pa: 0x4008_1123,
+                attr: 0xff,
+                shareability: Shareability::InnerShareable,
+                non_secure: false,
+            })
+        );
+    }
+
+    #[test]
+    fn decode_fault() {
+        // stage 2 translation fault at level 2, on a stage 1 walk
+        let par = 1 << 9 | 1 << 8 | 0b00_0110 << 1 | 1;
+        assert_eq!(
+            decode(par, 0),
+            Err(FaultInfo {
+                status: FaultStatus::Translation { level: 2 },
+                stage: Stage::Stage2,
+                ptw: true,
+            })
+        );
+    }
+}
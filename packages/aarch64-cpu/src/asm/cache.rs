@@ -0,0 +1,285 @@
+//! Cache maintenance instructions.
+//!
+//! The single-line wrappers (`dc_*`, `ic_*`) issue exactly one instruction and no barrier. The
+//! range and whole-cache operations complete with a `DSB SY` so that the maintenance has finished
+//! when they return.
+//!
+//! See section D7.5 "Cache support" in the [ARM Architecture Reference Manual v8][armv8].
+//!
+//! [armv8]: https://developer.arm.com/documentation/ddi0487/latest/
+
+use crate::{
+    asm::barrier,
+    registers::{Readable, Writeable, CCSIDR_EL1, CLIDR_EL1, CSSELR_EL1, CTR_EL0, DCZID_EL0},
+};
+
+macro_rules! cache_op {
+    ($(#[$attr:meta])* $vis:vis fn $name:ident($arg:ident) => $instr:literal) => {
+        $(#[$attr])*
+        #[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+        #[inline(always)]
+        $vis fn $name($arg: usize) {
+            match () {
+                #[cfg(target_arch = "aarch64")]
+                () => unsafe {
+                    core::arch::asm!(concat!($instr, ", {}"), in(reg) $arg, options(nostack))
+                },
+
+                #[cfg(not(target_arch = "aarch64"))]
+                () => unimplemented!(),
+            }
+        }
+    };
+
+    ($(#[$attr:meta])* $vis:vis unsafe fn $name:ident($arg:ident) => $instr:literal) => {
+        $(#[$attr])*
+        #[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+        #[inline(always)]
+        $vis unsafe fn $name($arg: usize) {
+            match () {
+                #[cfg(target_arch = "aarch64")]
+                () => unsafe {
+                    core::arch::asm!(concat!($instr, ", {}"), in(reg) $arg, options(nostack))
+                },
+
+                #[cfg(not(target_arch = "aarch64"))]
+                () => unimplemented!(),
+            }
+        }
+    };
+}
+
+cache_op! {
+    /// Data or unified cache line Clean and Invalidate by VA to PoC (`DC CIVAC`).
+    pub fn dc_civac(va) => "DC CIVAC"
+}
+
+cache_op! {
+    /// Data or unified cache line Clean by VA to PoC (`DC CVAC`).
+    pub fn dc_cvac(va) => "DC CVAC"
+}
+
+cache_op! {
+    /// Data or unified cache line Clean by VA to PoU (`DC CVAU`).
+    pub fn dc_cvau(va) => "DC CVAU"
+}
+
+cache_op! {
+    /// Data or unified cache line Invalidate by VA to PoC (`DC IVAC`).
+    ///
+    /// # Safety
+    ///
+    /// Any dirty data in the line is discarded. The caller must make sure that the line does not
+    /// hold data that still needs to be written back, and that no other data shares the line.
+    pub unsafe fn dc_ivac(va) => "DC IVAC"
+}
+
+cache_op! {
+    /// Data Cache Zero by VA (`DC ZVA`).
+    ///
+    /// Zeroes a naturally aligned block of [`zero_block_size`] bytes that contains `va`.
+    ///
+    /// # Safety
+    ///
+    /// The whole block is overwritten with zeroes, regardless of where in the block `va` points.
+    pub unsafe fn dc_zva(va) => "DC ZVA"
+}
+
+cache_op! {
+    /// Data or unified cache line Clean and Invalidate by Set/Way (`DC CISW`).
+    pub fn dc_cisw(set_way) => "DC CISW"
+}
+
+cache_op! {
+    /// Data or unified cache line Clean by Set/Way (`DC CSW`).
+    pub fn dc_csw(set_way) => "DC CSW"
+}
+
+cache_op! {
+    /// Data or unified cache line Invalidate by Set/Way (`DC ISW`).
+    ///
+    /// # Safety
+    ///
+    /// Any dirty data in the line is discarded.
+    pub unsafe fn dc_isw(set_way) => "DC ISW"
+}
+
+cache_op! {
+    /// Instruction cache line Invalidate by VA to PoU (`IC IVAU`).
+    pub fn ic_ivau(va) => "IC IVAU"
+}
+
+/// Instruction cache Invalidate All to PoU (`IC IALLU`).
+#[inline(always)]
+pub fn ic_iallu() {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => unsafe { core::arch::asm!("IC IALLU", options(nostack)) },
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+/// Instruction cache Invalidate All to PoU, Inner Shareable (`IC IALLUIS`).
+#[inline(always)]
+pub fn ic_ialluis() {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => unsafe { core::arch::asm!("IC IALLUIS", options(nostack)) },
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+/// Size, in bytes, of the smallest data cache line, as reported by `CTR_EL0.DminLine`.
+#[inline]
+pub fn dcache_line_size() -> usize {
+    4 << CTR_EL0.read(CTR_EL0::DminLine)
+}
+
+/// Size, in bytes, of the smallest instruction cache line, as reported by `CTR_EL0.IminLine`.
+#[inline]
+pub fn icache_line_size() -> usize {
+    4 << CTR_EL0.read(CTR_EL0::IminLine)
+}
+
+/// Size, in bytes, of the block zeroed by [`dc_zva`].
+///
+/// Returns `None` if the use of `DC ZVA` is prohibited.
+#[inline]
+pub fn zero_block_size() -> Option<usize> {
+    let dczid = DCZID_EL0.extract();
+    if dczid.is_set(DCZID_EL0::DZP) {
+        None
+    } else {
+        Some(4 << dczid.read(DCZID_EL0::BS))
+    }
+}
+
+/// Calls `op` once for every line of `line_size` bytes that overlaps `[start, start + size)`
+#[inline(always)]
+fn for_each_line(start: usize, size: usize, line_size: usize, op: impl Fn(usize)) {
+    if size == 0 {
+        return;
+    }
+    let end = start.saturating_add(size);
+    let mut line = start & !(line_size - 1);
+    while line < end {
+        op(line);
+        line += line_size;
+    }
+}
+
+/// Clean and invalidate the data cache lines covering `[start, start + size)` to the Point of
+/// Coherency.
+///
+/// Use this before handing a buffer to a non-coherent device that may also write it.
+pub fn clean_invalidate_dcache_range(start: usize, size: usize) {
+    for_each_line(start, size, dcache_line_size(), dc_civac);
+    barrier::dsb(barrier::SY);
+}
+
+/// Clean the data cache lines covering `[start, start + size)` to the Point of Coherency.
+///
+/// Use this before a non-coherent device reads a buffer written by the CPU.
+pub fn clean_dcache_range(start: usize, size: usize) {
+    for_each_line(start, size, dcache_line_size(), dc_cvac);
+    barrier::dsb(barrier::SY);
+}
+
+/// Invalidate the data cache lines covering `[start, start + size)` to the Point of Coherency.
+///
+/// Use this after a non-coherent device has written a buffer, before the CPU reads it.
+///
+/// # Safety
+///
+/// Lines are invalidated in whole, so if `start` or `start + size` are not aligned to
+/// [`dcache_line_size`] any dirty data sharing the first or last line is lost.
+pub unsafe fn invalidate_dcache_range(start: usize, size: usize) {
+    for_each_line(start, size, dcache_line_size(), |va| unsafe { dc_ivac(va) });
+    barrier::dsb(barrier::SY);
+}
+
+/// Make instructions written to `[start, start + size)` visible to instruction fetches.
+///
+/// Cleans the data cache to the Point of Unification and invalidates the instruction cache, as
+/// required by `CTR_EL0.IDC` and `CTR_EL0.DIC`.
+pub fn sync_icache_range(start: usize, size: usize) {
+    let ctr = CTR_EL0.extract();
+    if !ctr.is_set(CTR_EL0::IDC) {
+        for_each_line(start, size, 4 << ctr.read(CTR_EL0::DminLine), dc_cvau);
+    }
+    barrier::dsb(barrier::ISH);
+    if !ctr.is_set(CTR_EL0::DIC) {
+        for_each_line(start, size, 4 << ctr.read(CTR_EL0::IminLine), ic_ivau);
+        barrier::dsb(barrier::ISH);
+    }
+    barrier::isb(barrier::SY);
+}
+
+/// Calls `op` with the Set/Way operand of every line of every data or unified cache, from L1 up
+/// to the Level of Coherency.
+fn for_each_set_way(op: impl Fn(usize)) {
+    let clidr = CLIDR_EL1.extract();
+    let loc = clidr.read(CLIDR_EL1::LoC);
+
+    for level in 0..loc {
+        // Ctype<n>: 0b010 data only, 0b011 separate I and D, 0b100 unified
+        let ctype = (clidr.get() >> (3 * level)) & 0b111;
+        if ctype < 0b010 {
+            continue;
+        }
+
+        CSSELR_EL1.write(CSSELR_EL1::Level.val(level) + CSSELR_EL1::InD::Data);
+        barrier::isb(barrier::SY);
+
+        let line_shift = CCSIDR_EL1.read(CCSIDR_EL1::LineSize) + 4;
+        let ways = CCSIDR_EL1.get_associativity() + 1;
+        let sets = CCSIDR_EL1.get_num_sets() + 1;
+        // the way index is left aligned in the 32-bit operand
+        let way_shift = (ways as u32 - 1).leading_zeros();
+
+        for way in 0..ways {
+            for set in 0..sets {
+                let set_way = (way << way_shift) | (set << line_shift) | (level << 1);
+                op(set_way as usize);
+            }
+        }
+    }
+
+    CSSELR_EL1.set(0);
+    barrier::dsb(barrier::SY);
+    barrier::isb(barrier::SY);
+}
+
+/// Clean and invalidate all data and unified caches, by Set/Way, to the Point of Coherency.
+///
+/// This is meant for use on a single core before enabling or after disabling the data cache (e.g.
+/// `SCTLR_ELx.C`). Set/Way operations are local to the PE and are not a replacement for the
+/// range operations when sharing memory with other observers.
+pub fn clean_invalidate_dcache_all() {
+    barrier::dsb(barrier::SY);
+    for_each_set_way(dc_cisw);
+}
+
+/// Clean all data and unified caches, by Set/Way, to the Point of Coherency.
+///
+/// See [`clean_invalidate_dcache_all`] for the caveats of Set/Way maintenance.
+pub fn clean_dcache_all() {
+    barrier::dsb(barrier::SY);
+    for_each_set_way(dc_csw);
+}
+
+/// Invalidate all data and unified caches, by Set/Way, to the Point of Coherency.
+///
+/// Typically used at boot, before the data cache is enabled for the first time, to discard the
+/// reset contents of the caches.
+///
+/// # Safety
+///
+/// Any dirty data held in the caches is discarded.
+pub unsafe fn invalidate_dcache_all() {
+    for_each_set_way(|set_way| unsafe { dc_isw(set_way) });
+}
@@ -0,0 +1,652 @@
+//! TLB maintenance instructions.
+//!
+//! Each wrapper issues a single `TLBI` instruction in the requested [`Shareability`] domain and
+//! does not add any barrier. A typical sequence after changing a translation table entry is:
+//!
+//! ```no_run
+//! use aarch64_cpu::asm::{
+//!     barrier,
+//!     tlbi::{self, Asid, Shareability, VaOperand},
+//! };
+//!
+//! // make the table update visible to the table walker
+//! barrier::dsb(barrier::ISHST);
+//! tlbi::vae1(
+//!     Shareability::InnerShareable,
+//!     VaOperand::new(0x4000_0000).asid(Asid::new(1)),
+//! );
+//! // wait for the invalidation to complete, then resynchronize the instruction stream
+//! barrier::dsb(barrier::ISH);
+//! barrier::isb(barrier::SY);
+//! ```
+//!
+//! The Outer Shareable forms require FEAT_TLBIOS and the `r*` range forms require
+//! FEAT_TLBIRANGE; see `ID_AA64ISAR0_EL1.TLB`.
+//!
+//! See section D8.13 "TLB maintenance instructions" in the [ARM Architecture Reference Manual
+//! v8][armv8].
+//!
+//! [armv8]: https://developer.arm.com/documentation/ddi0487/latest/
+
+use crate::registers::{Readable, ID_AA64ISAR0_EL1};
+
+#[cfg(not(arm_architecture = "v8-r"))]
+use crate::{
+    asm::barrier,
+    registers::{Writeable, VTTBR_EL2},
+};
+
+/// Shareability domain a TLB maintenance instruction is broadcast to
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Shareability {
+    /// Only affects the PE executing the instruction
+    NonShareable,
+    /// Affects all PEs in the same Inner Shareable domain (`*IS` forms)
+    InnerShareable,
+    /// Affects all PEs in the same Outer Shareable domain (`*OS` forms, FEAT_TLBIOS)
+    OuterShareable,
+}
+
+/// Address Space Identifier
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Asid(u16);
+
+impl Asid {
+    /// Create an ASID. Only the low 8 bits are used unless 16-bit ASIDs are enabled in
+    /// `TCR_ELx.AS`.
+    pub const fn new(asid: u16) -> Asid {
+        Asid(asid)
+    }
+
+    /// The raw ASID value
+    pub const fn get(self) -> u16 {
+        self.0
+    }
+
+    /// The encoded register value for the by-ASID instructions (e.g. `TLBI ASIDE1`)
+    pub const fn bits(self) -> u64 {
+        (self.0 as u64) << ASID_SHIFT
+    }
+}
+
+/// Virtual Machine Identifier
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Vmid(u16);
+
+impl Vmid {
+    /// Create a VMID. Only the low 8 bits are used unless 16-bit VMIDs are enabled in
+    /// `VTCR_EL2.VS`.
+    pub const fn new(vmid: u16) -> Vmid {
+        Vmid(vmid)
+    }
+
+    /// The raw VMID value
+    pub const fn get(self) -> u16 {
+        self.0
+    }
+}
+
+/// Translation granule size
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Granule {
+    /// 4KiB pages
+    Size4KiB,
+    /// 16KiB pages
+    Size16KiB,
+    /// 64KiB pages
+    Size64KiB,
+}
+
+impl Granule {
+    /// Log2 of the page size
+    pub const fn shift(self) -> u32 {
+        match self {
+            Granule::Size4KiB => 12,
+            Granule::Size16KiB => 14,
+            Granule::Size64KiB => 16,
+        }
+    }
+
+    /// The page size in bytes
+    pub const fn size(self) -> usize {
+        1 << self.shift()
+    }
+
+    /// Encoding used by the TTL and TG fields of the TLBI operands
+    const fn tg_bits(self) -> u64 {
+        match self {
+            Granule::Size4KiB => 0b01,
+            Granule::Size16KiB => 0b10,
+            Granule::Size64KiB => 0b11,
+        }
+    }
+}
+
+/// Translation Table Level hint (FEAT_TTL)
+///
+/// When the level that holds the leaf entry is known, passing it lets the PE skip entries cached
+/// from other levels. It is ignored by PEs that do not implement FEAT_TTL.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Ttl {
+    /// No information about the level holding the leaf entry
+    Unknown,
+    /// The leaf entry is held at `level` of a table using `granule`
+    Level {
+        /// Granule size of the translation table
+        granule: Granule,
+        /// Lookup level, `0..=3`
+        level: u8,
+    },
+}
+
+impl Ttl {
+    const fn bits(self) -> u64 {
+        match self {
+            Ttl::Unknown => 0,
+            Ttl::Level { granule, level } => (granule.tg_bits() << 2) | (level as u64 & 0b11),
+        }
+    }
+}
+
+const ASID_SHIFT: u32 = 48;
+const TTL_SHIFT: u32 = 44;
+
+/// Operand of the by-VA instructions (e.g. `TLBI VAE1`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VaOperand(u64);
+
+impl VaOperand {
+    /// Operand for the page that contains `va`, with ASID 0 and no TTL hint
+    pub const fn new(va: u64) -> VaOperand {
+        VaOperand((va >> 12) & ((1 << 44) - 1))
+    }
+
+    /// Set the ASID to match. Ignored by the "all ASID" forms (e.g. `TLBI VAAE1`) and at EL2
+    /// and EL3 when the regime has no ASID.
+    pub const fn asid(self, asid: Asid) -> VaOperand {
+        VaOperand((self.0 & !(0xffff << ASID_SHIFT)) | ((asid.0 as u64) << ASID_SHIFT))
+    }
+
+    /// Set the Translation Table Level hint
+    pub const fn ttl(self, ttl: Ttl) -> VaOperand {
+        VaOperand((self.0 & !(0b1111 << TTL_SHIFT)) | (ttl.bits() << TTL_SHIFT))
+    }
+
+    /// The encoded register value
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+}
+
+/// Operand of the by-IPA instructions (e.g. `TLBI IPAS2E1`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct IpaOperand(u64);
+
+impl IpaOperand {
+    /// Operand for the page that contains `ipa`, with no TTL hint
+    pub const fn new(ipa: u64) -> IpaOperand {
+        IpaOperand((ipa >> 12) & ((1 << 40) - 1))
+    }
+
+    /// Set the Translation Table Level hint
+    pub const fn ttl(self, ttl: Ttl) -> IpaOperand {
+        IpaOperand((self.0 & !(0b1111 << TTL_SHIFT)) | (ttl.bits() << TTL_SHIFT))
+    }
+
+    /// Select the Non-secure IPA space when executed in Secure state
+    pub const fn non_secure(self, ns: bool) -> IpaOperand {
+        IpaOperand((self.0 & !(1 << 63)) | ((ns as u64) << 63))
+    }
+
+    /// The encoded register value
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+}
+
+/// Operand of the range instructions (e.g. `TLBI RVAE1`, FEAT_TLBIRANGE)
+///
+/// A range covers `(num + 1) << (5 * scale + 1)` pages starting at the base address. Use
+/// [`RangeOperand::split`] to cover an arbitrary number of pages.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RangeOperand(u64);
+
+impl RangeOperand {
+    /// Operand covering `(num + 1) << (5 * scale + 1)` pages of `granule` size starting at the
+    /// page containing `base`. `scale` must be in `0..=3` and `num` in `0..=31`.
+    pub const fn new(granule: Granule, base: u64, scale: u8, num: u8) -> RangeOperand {
+        RangeOperand(
+            (granule.tg_bits() << 46)
+                | ((scale as u64 & 0b11) << 44)
+                | ((num as u64 & 0b1_1111) << 39)
+                | ((base >> granule.shift()) & ((1 << 37) - 1)),
+        )
+    }
+
+    /// Set the ASID to match. Ignored by the "all ASID" forms and by the stage 2 forms.
+    pub const fn asid(self, asid: Asid) -> RangeOperand {
+        RangeOperand((self.0 & !(0xffff << ASID_SHIFT)) | ((asid.0 as u64) << ASID_SHIFT))
+    }
+
+    /// Set the level hint of the leaf entries, `1..=3`. `0` means any level.
+    pub const fn level(self, level: u8) -> RangeOperand {
+        RangeOperand((self.0 & !(0b11 << 37)) | ((level as u64 & 0b11) << 37))
+    }
+
+    /// Number of pages covered by this operand
+    pub const fn pages(self) -> u64 {
+        let scale = (self.0 >> 44) & 0b11;
+        let num = (self.0 >> 39) & 0b1_1111;
+        (num + 1) << (5 * scale + 1)
+    }
+
+    /// The encoded register value
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Split `pages` pages starting at `base` into range operands
+    ///
+    /// A range covers an even number of pages, so when `pages` is odd the last operand also
+    /// covers the page that follows the requested range. A count too large to round up is
+    /// rounded down instead.
+    pub fn split(granule: Granule, base: u64, pages: u64) -> RangeSplit {
+        RangeSplit {
+            granule,
+            base,
+            // round up to the two-page granularity of the smallest range
+            remaining: pages.saturating_add(pages & 1) & !1,
+        }
+    }
+}
+
+/// Iterator returned by [`RangeOperand::split`]
+#[derive(Debug, Clone)]
+pub struct RangeSplit {
+    granule: Granule,
+    base: u64,
+    remaining: u64,
+}
+
+impl Iterator for RangeSplit {
+    type Item = RangeOperand;
+
+    fn next(&mut self) -> Option<RangeOperand> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        // largest scale whose smallest range still fits in what remains
+        let mut scale = 3;
+        while (1 << (5 * scale + 1)) > self.remaining {
+            scale -= 1;
+        }
+        let unit = 1u64 << (5 * scale + 1);
+        let num = core::cmp::min(self.remaining / unit, 32);
+
+        let operand = RangeOperand::new(self.granule, self.base, scale as u8, (num - 1) as u8);
+        let pages = num * unit;
+        self.base = self.base.wrapping_add(pages << self.granule.shift());
+        self.remaining -= pages;
+        Some(operand)
+    }
+}
+
+macro_rules! tlbi {
+    ($(#[$attr:meta])* fn $name:ident() => $op:literal) => {
+        $(#[$attr])*
+        #[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+        #[inline(always)]
+        pub fn $name(sh: Shareability) {
+            match () {
+                #[cfg(target_arch = "aarch64")]
+                () => unsafe {
+                    match sh {
+                        Shareability::NonShareable => {
+                            core::arch::asm!(concat!("TLBI ", $op), options(nostack))
+                        }
+                        Shareability::InnerShareable => {
+                            core::arch::asm!(concat!("TLBI ", $op, "IS"), options(nostack))
+                        }
+                        Shareability::OuterShareable => core::arch::asm!(
+                            ".arch_extension tlb-rmi",
+                            concat!("TLBI ", $op, "OS"),
+                            options(nostack)
+                        ),
+                    }
+                },
+
+                #[cfg(not(target_arch = "aarch64"))]
+                () => unimplemented!(),
+            }
+        }
+    };
+
+    ($(#[$attr:meta])* fn $name:ident($arg:ident: $ty:ty) => $op:literal) => {
+        $(#[$attr])*
+        #[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+        #[inline(always)]
+        pub fn $name(sh: Shareability, $arg: $ty) {
+            match () {
+                #[cfg(target_arch = "aarch64")]
+                () => unsafe {
+                    let xt: u64 = $arg.bits();
+                    match sh {
+                        Shareability::NonShareable => core::arch::asm!(
+                            ".arch_extension tlb-rmi",
+                            concat!("TLBI ", $op, ", {}"),
+                            in(reg) xt,
+                            options(nostack)
+                        ),
+                        Shareability::InnerShareable => core::arch::asm!(
+                            ".arch_extension tlb-rmi",
+                            concat!("TLBI ", $op, "IS, {}"),
+                            in(reg) xt,
+                            options(nostack)
+                        ),
+                        Shareability::OuterShareable => core::arch::asm!(
+                            ".arch_extension tlb-rmi",
+                            concat!("TLBI ", $op, "OS, {}"),
+                            in(reg) xt,
+                            options(nostack)
+                        ),
+                    }
+                },
+
+                #[cfg(not(target_arch = "aarch64"))]
+                () => unimplemented!(),
+            }
+        }
+    };
+}
+
+// EL1&0 regime, current VMID
+
+tlbi! {
+    /// Invalidate all stage 1 EL1&0 entries for the current VMID (`TLBI VMALLE1`).
+    fn vmalle1() => "VMALLE1"
+}
+
+tlbi! {
+    /// Invalidate all stage 1 EL1&0 entries for an ASID (`TLBI ASIDE1`).
+    fn aside1(asid: Asid) => "ASIDE1"
+}
+
+tlbi! {
+    /// Invalidate stage 1 EL1&0 entries for a VA and ASID, at any level (`TLBI VAE1`).
+    fn vae1(va: VaOperand) => "VAE1"
+}
+
+tlbi! {
+    /// Invalidate stage 1 EL1&0 last level entries for a VA and ASID (`TLBI VALE1`).
+    fn vale1(va: VaOperand) => "VALE1"
+}
+
+tlbi! {
+    /// Invalidate stage 1 EL1&0 entries for a VA, for all ASIDs (`TLBI VAAE1`).
+    fn vaae1(va: VaOperand) => "VAAE1"
+}
+
+tlbi! {
+    /// Invalidate stage 1 EL1&0 last level entries for a VA, for all ASIDs (`TLBI VAALE1`).
+    fn vaale1(va: VaOperand) => "VAALE1"
+}
+
+tlbi! {
+    /// Invalidate stage 1 EL1&0 entries for a range of VAs and an ASID (`TLBI RVAE1`).
+    fn rvae1(range: RangeOperand) => "RVAE1"
+}
+
+tlbi! {
+    /// Invalidate stage 1 EL1&0 last level entries for a range of VAs and an ASID
+    /// (`TLBI RVALE1`).
+    fn rvale1(range: RangeOperand) => "RVALE1"
+}
+
+tlbi! {
+    /// Invalidate stage 1 EL1&0 entries for a range of VAs, for all ASIDs (`TLBI RVAAE1`).
+    fn rvaae1(range: RangeOperand) => "RVAAE1"
+}
+
+tlbi! {
+    /// Invalidate stage 1 EL1&0 last level entries for a range of VAs, for all ASIDs
+    /// (`TLBI RVAALE1`).
+    fn rvaale1(range: RangeOperand) => "RVAALE1"
+}
+
+// EL1&0 regime, issued from EL2
+
+tlbi! {
+    /// Invalidate all stage 1 and stage 2 EL1&0 entries for all VMIDs (`TLBI ALLE1`).
+    fn alle1() => "ALLE1"
+}
+
+tlbi! {
+    /// Invalidate all stage 1 and stage 2 EL1&0 entries for the current VMID
+    /// (`TLBI VMALLS12E1`).
+    fn vmalls12e1() => "VMALLS12E1"
+}
+
+tlbi! {
+    /// Invalidate stage 2 entries for an IPA, for the current VMID, at any level
+    /// (`TLBI IPAS2E1`).
+    fn ipas2e1(ipa: IpaOperand) => "IPAS2E1"
+}
+
+tlbi! {
+    /// Invalidate stage 2 last level entries for an IPA, for the current VMID
+    /// (`TLBI IPAS2LE1`).
+    fn ipas2le1(ipa: IpaOperand) => "IPAS2LE1"
+}
+
+tlbi! {
+    /// Invalidate stage 2 entries for a range of IPAs, for the current VMID (`TLBI RIPAS2E1`).
+    fn ripas2e1(range: RangeOperand) => "RIPAS2E1"
+}
+
+tlbi! {
+    /// Invalidate stage 2 last level entries for a range of IPAs, for the current VMID
+    /// (`TLBI RIPAS2LE1`).
+    fn ripas2le1(range: RangeOperand) => "RIPAS2LE1"
+}
+
+// EL2 regime
+
+tlbi! {
+    /// Invalidate all EL2 entries (`TLBI ALLE2`).
+    fn alle2() => "ALLE2"
+}
+
+tlbi! {
+    /// Invalidate EL2 entries for a VA, at any level (`TLBI VAE2`).
+    fn vae2(va: VaOperand) => "VAE2"
+}
+
+tlbi! {
+    /// Invalidate EL2 last level entries for a VA (`TLBI VALE2`).
+    fn vale2(va: VaOperand) => "VALE2"
+}
+
+tlbi! {
+    /// Invalidate EL2 entries for a range of VAs (`TLBI RVAE2`).
+    fn rvae2(range: RangeOperand) => "RVAE2"
+}
+
+tlbi! {
+    /// Invalidate EL2 last level entries for a range of VAs (`TLBI RVALE2`).
+    fn rvale2(range: RangeOperand) => "RVALE2"
+}
+
+// EL3 regime
+
+tlbi! {
+    /// Invalidate all EL3 entries (`TLBI ALLE3`).
+    fn alle3() => "ALLE3"
+}
+
+tlbi! {
+    /// Invalidate EL3 entries for a VA, at any level (`TLBI VAE3`).
+    fn vae3(va: VaOperand) => "VAE3"
+}
+
+tlbi! {
+    /// Invalidate EL3 last level entries for a VA (`TLBI VALE3`).
+    fn vale3(va: VaOperand) => "VALE3"
+}
+
+tlbi! {
+    /// Invalidate EL3 entries for a range of VAs (`TLBI RVAE3`).
+    fn rvae3(range: RangeOperand) => "RVAE3"
+}
+
+tlbi! {
+    /// Invalidate EL3 last level entries for a range of VAs (`TLBI RVALE3`).
+    fn rvale3(range: RangeOperand) => "RVALE3"
+}
+
+/// Does the PE implement the range instructions (FEAT_TLBIRANGE)?
+#[inline]
+pub fn has_range() -> bool {
+    ID_AA64ISAR0_EL1.matches_all(ID_AA64ISAR0_EL1::TLB::OuterShareableAndRange)
+}
+
+/// The addresses of `pages` pages starting at `base`, stopping at the top of the address space
+fn page_addresses(granule: Granule, base: u64, pages: u64) -> impl Iterator<Item = u64> {
+    (0..pages).map_while(move |page| {
+        page.checked_mul(1 << granule.shift())
+            .and_then(|offset| base.checked_add(offset))
+    })
+}
+
+/// Invalidate stage 1 EL1&0 entries for `pages` pages starting at `va`, tagged with `asid`.
+///
+/// Uses `TLBI RVAE1` when FEAT_TLBIRANGE is implemented and `TLBI VAE1` on each page otherwise.
+pub fn vae1_range(sh: Shareability, asid: Asid, granule: Granule, va: u64, pages: u64) {
+    if has_range() {
+        for range in RangeOperand::split(granule, va, pages) {
+            rvae1(sh, range.asid(asid));
+        }
+    } else {
+        for va in page_addresses(granule, va, pages) {
+            vae1(sh, VaOperand::new(va).asid(asid));
+        }
+    }
+}
+
+/// Invalidate EL2 entries for `pages` pages starting at `va`.
+///
+/// Uses `TLBI RVAE2` when FEAT_TLBIRANGE is implemented and `TLBI VAE2` on each page otherwise.
+pub fn vae2_range(sh: Shareability, granule: Granule, va: u64, pages: u64) {
+    if has_range() {
+        for range in RangeOperand::split(granule, va, pages) {
+            rvae2(sh, range);
+        }
+    } else {
+        for va in page_addresses(granule, va, pages) {
+            vae2(sh, VaOperand::new(va));
+        }
+    }
+}
+
+/// Invalidate stage 2 entries for `pages` pages starting at `ipa`, for the current VMID.
+///
+/// Uses `TLBI RIPAS2E1` when FEAT_TLBIRANGE is implemented and `TLBI IPAS2E1` on each page
+/// otherwise. Stage 2 invalidation does not remove combined stage 1 and 2 entries; follow up with
+/// [`vmalle1`] as described in the Arm ARM.
+pub fn ipas2e1_range(sh: Shareability, granule: Granule, ipa: u64, pages: u64) {
+    if has_range() {
+        for range in RangeOperand::split(granule, ipa, pages) {
+            ripas2e1(sh, range);
+        }
+    } else {
+        for ipa in page_addresses(granule, ipa, pages) {
+            ipas2e1(sh, IpaOperand::new(ipa));
+        }
+    }
+}
+
+/// Invalidate all stage 1 and stage 2 EL1&0 entries tagged with `vmid`.
+///
+/// The `TLBI` instructions have no VMID operand, so this temporarily installs `vmid` in
+/// `VTTBR_EL2`, issues `TLBI VMALLS12E1` and restores the previous `VTTBR_EL2` value. Unlike the
+/// single-instruction wrappers it includes the barriers needed for the invalidation to complete
+/// before `VTTBR_EL2` is restored. Must be called from EL2.
+///
+/// Not available on Armv8-R, which has no `VTTBR_EL2`.
+#[cfg(not(arm_architecture = "v8-r"))]
+pub fn vmalls12e1_vmid(sh: Shareability, vmid: Vmid) {
+    let vttbr = VTTBR_EL2.get();
+    VTTBR_EL2.set((vttbr & !(0xffff << 48)) | ((vmid.0 as u64) << 48));
+    barrier::isb(barrier::SY);
+
+    vmalls12e1(sh);
+    match sh {
+        Shareability::NonShareable => barrier::dsb(barrier::NSH),
+        Shareability::InnerShareable => barrier::dsb(barrier::ISH),
+        Shareability::OuterShareable => barrier::dsb(barrier::OSH),
+    }
+
+    VTTBR_EL2.set(vttbr);
+    barrier::isb(barrier::SY);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn va_operand() {
+        let op = VaOperand::new(0xffff_0000_1234_5678)
+            .asid(Asid::new(0xab))
+            .ttl(Ttl::Level {
+                granule: Granule::Size4KiB,
+                level: 3,
+            });
+        assert_eq!(op.bits(), 0x00ab_7ff0_0001_2345);
+        assert_eq!(Asid::new(0x12).bits(), 0x0012_0000_0000_0000);
+    }
+
+    #[test]
+    fn ipa_operand() {
+        let op = IpaOperand::new(0x8000_1000).non_secure(true);
+        assert_eq!(op.bits(), (1 << 63) | 0x8_0001);
+    }
+
+    #[test]
+    fn range_operand() {
+        let op = RangeOperand::new(Granule::Size4KiB, 0x4000_0000, 1, 3);
+        assert_eq!(op.pages(), 4 << 6);
+        assert_eq!(op.bits(), (0b01 << 46) | (1 << 44) | (3 << 39) | 0x4_0000);
+    }
+
+    #[test]
+    fn range_split() {
+        let mut total = 0;
+        let mut base = 0x1000_0000;
+        for op in RangeOperand::split(Granule::Size64KiB, base, 1000) {
+            assert_eq!(op.bits() & ((1 << 37) - 1), base >> 16);
+            base += op.pages() << 16;
+            total += op.pages();
+        }
+        assert_eq!(total, 1000);
+
+        // odd counts round up to the next even page
+        let pages: u64 = RangeOperand::split(Granule::Size4KiB, 0, 3)
+            .map(RangeOperand::pages)
+            .sum();
+        assert_eq!(pages, 4);
+
+        // the per-page fallback stops at the top of the address space
+        let top: u64 = !0xfff;
+        assert_eq!(
+            page_addresses(Granule::Size4KiB, top - 0x1000, 4).count(),
+            2
+        );
+
+        // the largest count must not overflow when rounding up
+        let op = RangeOperand::split(Granule::Size4KiB, 0, u64::MAX)
+            .next()
+            .unwrap();
+        assert_eq!(op.pages(), 32 << 16);
+    }
+}
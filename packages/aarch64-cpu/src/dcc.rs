@@ -0,0 +1,109 @@
+//! Code and types for the Debug Communications Channel (DCC)
+//!
+//! The DCC is a pair of 32-bit registers, `DBGDTRTX_EL0` and `DBGDTRRX_EL0`, through which the
+//! PE exchanges data with an external debugger over JTAG or SWD. [`Dcc`] moves one byte per
+//! transfer, which is what debugger DCC consoles expect.
+//!
+//! The blocking functions wait for the debugger to drain or fill the channel. With no debugger
+//! attached they never return, so use the `try_*` functions when one may be missing.
+//!
+//! ```no_run
+//! use aarch64_cpu::dcc::Dcc;
+//! use core::fmt::Write;
+//!
+//! let mut dcc = Dcc::new();
+//! writeln!(dcc, "Hello from core {}", 0).unwrap();
+//! ```
+//!
+//! With the `defmt-dcc` feature enabled, this module also provides a [defmt] global logger that
+//! sends its frames over the DCC.
+//!
+//! [defmt]: https://defmt.ferrous-systems.com/
+
+#[cfg(feature = "defmt-dcc")]
+mod logger;
+
+use crate::registers::*;
+use core::fmt;
+
+/// The channel is full (transmit) or empty (receive)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WouldBlock;
+
+/// A byte channel over the Debug Communications Channel
+///
+/// All instances share the same hardware channel, so bytes written through different instances
+/// can interleave.
+#[derive(Debug, Default)]
+pub struct Dcc {
+    _private: (),
+}
+
+impl Dcc {
+    /// Create a handle to the Debug Communications Channel
+    ///
+    /// EL0 accesses trap when `MDSCR_EL1.TDCC` is set.
+    pub const fn new() -> Dcc {
+        Dcc { _private: () }
+    }
+
+    /// Send a byte, if the debugger has read the previous one
+    pub fn try_write_byte(&mut self, byte: u8) -> Result<(), WouldBlock> {
+        if MDCCSR_EL0.is_set(MDCCSR_EL0::TXfull) {
+            Err(WouldBlock)
+        } else {
+            DBGDTRTX_EL0.set(byte as u64);
+            Ok(())
+        }
+    }
+
+    /// Receive a byte, if the debugger has sent one
+    pub fn try_read_byte(&mut self) -> Result<u8, WouldBlock> {
+        if MDCCSR_EL0.is_set(MDCCSR_EL0::RXfull) {
+            Ok(DBGDTRRX_EL0.get() as u8)
+        } else {
+            Err(WouldBlock)
+        }
+    }
+
+    /// Send a byte, waiting for the debugger to read the previous one
+    pub fn write_byte(&mut self, byte: u8) {
+        while self.try_write_byte(byte).is_err() {
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Receive a byte, waiting for the debugger to send one
+    pub fn read_byte(&mut self) -> u8 {
+        loop {
+            if let Ok(byte) = self.try_read_byte() {
+                return byte;
+            }
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Send all of `bytes`, waiting for the debugger as needed
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.write_byte(*byte);
+        }
+    }
+
+    /// Send as much of `bytes` as the debugger accepts without waiting
+    ///
+    /// Returns the number of bytes sent.
+    pub fn try_write(&mut self, bytes: &[u8]) -> usize {
+        bytes
+            .iter()
+            .take_while(|byte| self.try_write_byte(**byte).is_ok())
+            .count()
+    }
+}
+
+impl fmt::Write for Dcc {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write(s.as_bytes());
+        Ok(())
+    }
+}
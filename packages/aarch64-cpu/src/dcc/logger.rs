@@ -0,0 +1,97 @@
+//! A defmt global logger over the Debug Communications Channel
+//!
+//! Each frame is written inside a critical section, so a `critical-section` implementation must
+//! be linked in, e.g. with the `critical-section-single-core` feature.
+
+use super::Dcc;
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{self, AtomicBool},
+};
+use critical_section::RestoreState;
+
+#[defmt::global_logger]
+struct Logger;
+
+static DEFMT_DCC: DefmtDcc = DefmtDcc::new();
+
+struct DefmtDcc {
+    encoder: UnsafeCell<defmt::Encoder>,
+    taken: AtomicBool,
+    cs_restore: UnsafeCell<RestoreState>,
+}
+
+impl DefmtDcc {
+    const fn new() -> Self {
+        Self {
+            encoder: UnsafeCell::new(defmt::Encoder::new()),
+            taken: AtomicBool::new(false),
+            cs_restore: UnsafeCell::new(RestoreState::invalid()),
+        }
+    }
+
+    fn acquire(&self) {
+        let restore = unsafe { critical_section::acquire() };
+
+        if self.taken.load(atomic::Ordering::Relaxed) {
+            panic!("defmt logger taken reentrantly")
+        }
+
+        self.taken.store(true, atomic::Ordering::Relaxed);
+
+        // SAFETY: inside a critical section
+        unsafe {
+            self.cs_restore.get().write(restore);
+            (*self.encoder.get()).start_frame(|encoded| Dcc::new().write(encoded))
+        }
+    }
+
+    unsafe fn release(&self) {
+        if !self.taken.load(atomic::Ordering::Relaxed) {
+            // release out of context
+            return;
+        }
+
+        // SAFETY: inside a critical section
+        unsafe { (*self.encoder.get()).end_frame(|encoded| Dcc::new().write(encoded)) }
+
+        // this flag needs to be cleared before interrupts are allowed to rerun or they may
+        // hit the 'taken reentrantly' panic branch in `acquire`
+        self.taken.store(false, atomic::Ordering::Relaxed);
+        // end of critical section
+        unsafe {
+            let restore = self.cs_restore.get().read();
+            critical_section::release(restore);
+        }
+    }
+
+    unsafe fn write(&self, bytes: &[u8]) {
+        if !self.taken.load(atomic::Ordering::Relaxed) {
+            // write out of critical section
+            return;
+        }
+
+        // SAFETY: inside a critical section
+        unsafe { (*self.encoder.get()).write(bytes, |encoded| Dcc::new().write(encoded)) }
+    }
+}
+
+unsafe impl defmt::Logger for Logger {
+    fn acquire() {
+        DEFMT_DCC.acquire()
+    }
+
+    unsafe fn flush() {
+        // every byte has been handed to the debugger once `write` returns
+    }
+
+    unsafe fn release() {
+        unsafe { DEFMT_DCC.release() }
+    }
+
+    unsafe fn write(bytes: &[u8]) {
+        unsafe { DEFMT_DCC.write(bytes) }
+    }
+}
+
+unsafe impl Sync for DefmtDcc {}
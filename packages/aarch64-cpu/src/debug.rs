@@ -0,0 +1,493 @@
+//! Code and types for self-hosted debug: hardware breakpoints and watchpoints
+//!
+//! [`SelfHostedDebug`] owns the breakpoint and watchpoint comparators that `ID_AA64DFR0_EL1`
+//! reports, and hands them out as [`Breakpoint`], [`ContextBreakpoint`] and [`Watchpoint`]
+//! slots.
+//!
+//! Breakpoint and Watchpoint exceptions are taken to EL1, or to EL2 when `MDCR_EL2.TDE` or
+//! `HCR_EL2.TGE` is set, and are reported with the `Breakpoint` and `Watchpoint` exception
+//! classes in `ESR_ELx`. They are never taken to EL3, so comparators that only match at EL3 have
+//! no effect.
+//!
+//! ```no_run
+//! use aarch64_cpu::debug::{Access, MatchLevels, SelfHostedDebug, WatchpointConfig};
+//!
+//! static mut COUNTER: u32 = 0;
+//!
+//! let mut debug = unsafe { SelfHostedDebug::new() };
+//! debug.enable();
+//!
+//! // Catch any write to COUNTER from EL1 or EL0
+//! let address = core::ptr::addr_of!(COUNTER) as u64;
+//! let _watchpoint = debug
+//!     .set_watchpoint(
+//!         WatchpointConfig::new(address, 4, Access::Store).levels(MatchLevels::El1El0),
+//!     )
+//!     .unwrap();
+//! ```
+
+use crate::{asm::barrier, registers::*};
+
+/// Ways this API can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// All the comparators of the required kind are in use
+    NoFreeComparator,
+    /// Found an address that is not aligned as required
+    Unaligned(u64),
+    /// Found a watchpoint size that cannot be programmed at the given address
+    ///
+    /// Watchpoints cover up to 8 bytes within an aligned double-word, or a naturally aligned
+    /// power-of-two range of up to 2GB.
+    InvalidSize(u64),
+}
+
+/// The Exception levels at which a comparator matches
+///
+/// These are the `{HMC, SSC, PMC}` combinations that match in both Security states.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MatchLevels {
+    /// EL0 only
+    El0,
+    /// EL1 only
+    El1,
+    /// EL1 and EL0
+    El1El0,
+    /// EL2 only
+    El2,
+    /// EL2 and EL1
+    El2El1,
+    /// EL2, EL1 and EL0
+    El2El1El0,
+}
+
+impl MatchLevels {
+    /// The `(HMC, SSC, PMC)` field values for these levels
+    fn encode(self) -> (u64, u64, u64) {
+        match self {
+            MatchLevels::El0 => (0, 0b00, 0b10),
+            MatchLevels::El1 => (0, 0b00, 0b01),
+            MatchLevels::El1El0 => (0, 0b00, 0b11),
+            MatchLevels::El2 => (1, 0b11, 0b00),
+            MatchLevels::El2El1 => (1, 0b00, 0b01),
+            MatchLevels::El2El1El0 => (1, 0b00, 0b11),
+        }
+    }
+}
+
+/// The accesses that trigger a watchpoint
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Access {
+    /// Loads only
+    Load,
+    /// Stores only
+    Store,
+    /// Loads and stores
+    LoadStore,
+}
+
+/// An allocated address breakpoint
+#[derive(Debug, PartialEq, Eq)]
+pub struct Breakpoint(u8);
+
+impl Breakpoint {
+    /// The comparator number, `n` in `DBGBCR<n>_EL1`
+    pub fn index(&self) -> u8 {
+        self.0
+    }
+}
+
+/// An allocated context-aware breakpoint matching `CONTEXTIDR_EL1`
+///
+/// It never triggers by itself; link address breakpoints and watchpoints to it to make them
+/// only match in a given context.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ContextBreakpoint(u8);
+
+impl ContextBreakpoint {
+    /// The comparator number, `n` in `DBGBCR<n>_EL1`
+    pub fn index(&self) -> u8 {
+        self.0
+    }
+}
+
+/// An allocated watchpoint
+#[derive(Debug, PartialEq, Eq)]
+pub struct Watchpoint(u8);
+
+impl Watchpoint {
+    /// The comparator number, `n` in `DBGWCR<n>_EL1`
+    pub fn index(&self) -> u8 {
+        self.0
+    }
+}
+
+/// The settings of an address breakpoint
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BreakpointConfig {
+    address: u64,
+    levels: MatchLevels,
+    linked: Option<u8>,
+}
+
+impl BreakpointConfig {
+    /// Break on the A64 instruction at `address`, at EL1 and EL0
+    pub fn new(address: u64) -> Self {
+        BreakpointConfig {
+            address,
+            levels: MatchLevels::El1El0,
+            linked: None,
+        }
+    }
+
+    /// Set the Exception levels at which the breakpoint matches
+    pub fn levels(mut self, levels: MatchLevels) -> Self {
+        self.levels = levels;
+        self
+    }
+
+    /// Only match when `context` also matches
+    pub fn linked(mut self, context: &ContextBreakpoint) -> Self {
+        self.linked = Some(context.0);
+        self
+    }
+
+    /// The `(DBGBVR, DBGBCR)` values for this breakpoint
+    fn encode(&self) -> Result<(u64, u64), Error> {
+        if !self.address.is_multiple_of(4) {
+            return Err(Error::Unaligned(self.address));
+        }
+
+        let (hmc, ssc, pmc) = self.levels.encode();
+        let mut control = DBGBCR_EL1::HMC.val(hmc)
+            + DBGBCR_EL1::SSC.val(ssc)
+            + DBGBCR_EL1::PMC.val(pmc)
+            + DBGBCR_EL1::BAS.val(0b1111)
+            + DBGBCR_EL1::E::Enabled;
+        control += match self.linked {
+            Some(lbn) => DBGBCR_EL1::BT::LinkedAddressMatch + DBGBCR_EL1::LBN.val(lbn as u64),
+            None => DBGBCR_EL1::BT::UnlinkedAddressMatch,
+        };
+
+        Ok((self.address, control.value))
+    }
+}
+
+/// The settings of a watchpoint
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WatchpointConfig {
+    address: u64,
+    size: u64,
+    access: Access,
+    levels: MatchLevels,
+    linked: Option<u8>,
+}
+
+impl WatchpointConfig {
+    /// Watch `size` bytes at `address` for `access`, at EL1 and EL0
+    ///
+    /// See [`Error::InvalidSize`] for the supported sizes.
+    pub fn new(address: u64, size: u64, access: Access) -> Self {
+        WatchpointConfig {
+            address,
+            size,
+            access,
+            levels: MatchLevels::El1El0,
+            linked: None,
+        }
+    }
+
+    /// Set the Exception levels at which the watchpoint matches
+    pub fn levels(mut self, levels: MatchLevels) -> Self {
+        self.levels = levels;
+        self
+    }
+
+    /// Only match when `context` also matches
+    pub fn linked(mut self, context: &ContextBreakpoint) -> Self {
+        self.linked = Some(context.0);
+        self
+    }
+
+    /// The `(DBGWVR, DBGWCR)` values for this watchpoint
+    fn encode(&self) -> Result<(u64, u64), Error> {
+        let (address, bas, mask) = if self.size > 0 && (self.address % 8) + self.size <= 8 {
+            // Select the bytes within one double-word
+            let bas = ((1 << self.size) - 1) << (self.address % 8);
+            (self.address & !0b111, bas, 0)
+        } else if self.size.is_power_of_two() && self.size <= 1 << 31 {
+            if !self.address.is_multiple_of(self.size) {
+                return Err(Error::Unaligned(self.address));
+            }
+            (self.address, 0xff, self.size.trailing_zeros() as u64)
+        } else {
+            return Err(Error::InvalidSize(self.size));
+        };
+
+        let (hmc, ssc, pmc) = self.levels.encode();
+        let mut control = DBGWCR_EL1::MASK.val(mask)
+            + DBGWCR_EL1::HMC.val(hmc)
+            + DBGWCR_EL1::SSC.val(ssc)
+            + DBGWCR_EL1::PAC.val(pmc)
+            + DBGWCR_EL1::BAS.val(bas)
+            + DBGWCR_EL1::E::Enabled;
+        control += match self.access {
+            Access::Load => DBGWCR_EL1::LSC::Load,
+            Access::Store => DBGWCR_EL1::LSC::Store,
+            Access::LoadStore => DBGWCR_EL1::LSC::LoadStore,
+        };
+        if let Some(lbn) = self.linked {
+            control += DBGWCR_EL1::WT::Linked + DBGWCR_EL1::LBN.val(lbn as u64);
+        }
+
+        Ok((address, control.value))
+    }
+}
+
+/// Represents the self-hosted debug breakpoint and watchpoint comparators
+pub struct SelfHostedDebug {
+    num_breakpoints: u8,
+    num_watchpoints: u8,
+    num_context: u8,
+    breakpoints_used: u16,
+    watchpoints_used: u16,
+}
+
+impl SelfHostedDebug {
+    /// Create a self-hosted debug driver, with every comparator disabled
+    ///
+    /// # Safety
+    ///
+    /// Only create one of these at any given time, as it owns the comparators and reprograms
+    /// them without checking whether anything else uses them.
+    pub unsafe fn new() -> SelfHostedDebug {
+        let dfr0 = ID_AA64DFR0_EL1.extract();
+        let debug = SelfHostedDebug {
+            num_breakpoints: dfr0.read(ID_AA64DFR0_EL1::BRPs) as u8 + 1,
+            num_watchpoints: dfr0.read(ID_AA64DFR0_EL1::WRPs) as u8 + 1,
+            num_context: dfr0.read(ID_AA64DFR0_EL1::CTX_CMPs) as u8 + 1,
+            breakpoints_used: 0,
+            watchpoints_used: 0,
+        };
+
+        for n in 0..debug.num_breakpoints {
+            write_dbgbcr(n, 0);
+        }
+        for n in 0..debug.num_watchpoints {
+            write_dbgwcr(n, 0);
+        }
+        barrier::isb(barrier::SY);
+
+        debug
+    }
+
+    /// Get the number of breakpoints, including the context-aware ones
+    pub fn num_breakpoints(&self) -> u8 {
+        self.num_breakpoints
+    }
+
+    /// Get the number of context-aware breakpoints
+    pub fn num_context_breakpoints(&self) -> u8 {
+        self.num_context
+    }
+
+    /// Get the number of watchpoints
+    pub fn num_watchpoints(&self) -> u8 {
+        self.num_watchpoints
+    }
+
+    /// Enable Breakpoint and Watchpoint exceptions
+    ///
+    /// Clears the OS Lock and OS Double Lock, sets `MDSCR_EL1.{MDE, KDE}` so that the
+    /// exceptions are generated and can be taken at the current Exception level, and unmasks
+    /// them in `PSTATE.D`.
+    pub fn enable(&mut self) {
+        OSDLR_EL1.write(OSDLR_EL1::DLK::Unlocked);
+        OSLAR_EL1.write(OSLAR_EL1::OSLK::Unlocked);
+        barrier::isb(barrier::SY);
+        MDSCR_EL1.modify(MDSCR_EL1::MDE::SET + MDSCR_EL1::KDE::AllDebugExceptionsEnabled);
+        barrier::isb(barrier::SY);
+        DAIF.modify(DAIF::D::Unmasked);
+    }
+
+    /// Disable Breakpoint and Watchpoint exceptions, leaving the comparators programmed
+    pub fn disable(&mut self) {
+        MDSCR_EL1.modify(MDSCR_EL1::MDE::CLEAR + MDSCR_EL1::KDE::OnlyBreakpointInstructionsEnabled);
+        barrier::isb(barrier::SY);
+    }
+
+    /// Program a free breakpoint comparator
+    ///
+    /// Comparators that are not context-aware are used first, to keep those free for
+    /// [`SelfHostedDebug::set_context_breakpoint`].
+    pub fn set_breakpoint(&mut self, config: BreakpointConfig) -> Result<Breakpoint, Error> {
+        let (value, control) = config.encode()?;
+        let first_context = self.num_breakpoints - self.num_context;
+        let n = self
+            .free_breakpoint(0..first_context)
+            .or_else(|| self.free_breakpoint(first_context..self.num_breakpoints))
+            .ok_or(Error::NoFreeComparator)?;
+
+        self.breakpoints_used |= 1 << n;
+        write_dbgbvr(n, value);
+        write_dbgbcr(n, control);
+        barrier::isb(barrier::SY);
+
+        Ok(Breakpoint(n))
+    }
+
+    /// Program a free context-aware breakpoint comparator to match `CONTEXTIDR_EL1` against
+    /// `context_id`, for linking to other comparators
+    pub fn set_context_breakpoint(&mut self, context_id: u32) -> Result<ContextBreakpoint, Error> {
+        let first_context = self.num_breakpoints - self.num_context;
+        let n = self
+            .free_breakpoint(first_context..self.num_breakpoints)
+            .ok_or(Error::NoFreeComparator)?;
+
+        self.breakpoints_used |= 1 << n;
+        write_dbgbvr(n, context_id as u64);
+        write_dbgbcr(
+            n,
+            (DBGBCR_EL1::BT::LinkedContextIdMatch
+                + DBGBCR_EL1::PMC.val(0b11)
+                + DBGBCR_EL1::BAS.val(0b1111)
+                + DBGBCR_EL1::E::Enabled)
+                .value,
+        );
+        barrier::isb(barrier::SY);
+
+        Ok(ContextBreakpoint(n))
+    }
+
+    /// Program a free watchpoint comparator
+    pub fn set_watchpoint(&mut self, config: WatchpointConfig) -> Result<Watchpoint, Error> {
+        let (value, control) = config.encode()?;
+        let n = (0..self.num_watchpoints)
+            .find(|n| self.watchpoints_used & (1 << n) == 0)
+            .ok_or(Error::NoFreeComparator)?;
+
+        self.watchpoints_used |= 1 << n;
+        write_dbgwvr(n, value);
+        write_dbgwcr(n, control);
+        barrier::isb(barrier::SY);
+
+        Ok(Watchpoint(n))
+    }
+
+    /// Disable and free a breakpoint
+    pub fn clear_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.free(breakpoint.0);
+    }
+
+    /// Disable and free a context-aware breakpoint
+    ///
+    /// Comparators linked to it stop matching.
+    pub fn clear_context_breakpoint(&mut self, context: ContextBreakpoint) {
+        self.free(context.0);
+    }
+
+    /// Disable and free a watchpoint
+    pub fn clear_watchpoint(&mut self, watchpoint: Watchpoint) {
+        write_dbgwcr(watchpoint.0, 0);
+        barrier::isb(barrier::SY);
+        self.watchpoints_used &= !(1 << watchpoint.0);
+    }
+
+    /// Find a free breakpoint comparator in `range`
+    fn free_breakpoint(&self, mut range: core::ops::Range<u8>) -> Option<u8> {
+        range.find(|n| self.breakpoints_used & (1 << n) == 0)
+    }
+
+    /// Disable and free breakpoint comparator `n`
+    fn free(&mut self, n: u8) {
+        write_dbgbcr(n, 0);
+        barrier::isb(barrier::SY);
+        self.breakpoints_used &= !(1 << n);
+    }
+}
+
+/// Generates a function that writes comparator register `n` of a register family
+macro_rules! comparator_write {
+    ($write:ident, [$($n:literal => $reg:ident),*]) => {
+        fn $write(n: u8, value: u64) {
+            match n {
+                $($n => $reg.set(value),)*
+                _ => unreachable!(),
+            }
+        }
+    };
+}
+
+comparator_write!(write_dbgbvr, [
+    0 => DBGBVR0_EL1, 1 => DBGBVR1_EL1, 2 => DBGBVR2_EL1, 3 => DBGBVR3_EL1,
+    4 => DBGBVR4_EL1, 5 => DBGBVR5_EL1, 6 => DBGBVR6_EL1, 7 => DBGBVR7_EL1,
+    8 => DBGBVR8_EL1, 9 => DBGBVR9_EL1, 10 => DBGBVR10_EL1, 11 => DBGBVR11_EL1,
+    12 => DBGBVR12_EL1, 13 => DBGBVR13_EL1, 14 => DBGBVR14_EL1, 15 => DBGBVR15_EL1
+]);
+comparator_write!(write_dbgbcr, [
+    0 => DBGBCR0_EL1, 1 => DBGBCR1_EL1, 2 => DBGBCR2_EL1, 3 => DBGBCR3_EL1,
+    4 => DBGBCR4_EL1, 5 => DBGBCR5_EL1, 6 => DBGBCR6_EL1, 7 => DBGBCR7_EL1,
+    8 => DBGBCR8_EL1, 9 => DBGBCR9_EL1, 10 => DBGBCR10_EL1, 11 => DBGBCR11_EL1,
+    12 => DBGBCR12_EL1, 13 => DBGBCR13_EL1, 14 => DBGBCR14_EL1, 15 => DBGBCR15_EL1
+]);
+comparator_write!(write_dbgwvr, [
+    0 => DBGWVR0_EL1, 1 => DBGWVR1_EL1, 2 => DBGWVR2_EL1, 3 => DBGWVR3_EL1,
+    4 => DBGWVR4_EL1, 5 => DBGWVR5_EL1, 6 => DBGWVR6_EL1, 7 => DBGWVR7_EL1,
+    8 => DBGWVR8_EL1, 9 => DBGWVR9_EL1, 10 => DBGWVR10_EL1, 11 => DBGWVR11_EL1,
+    12 => DBGWVR12_EL1, 13 => DBGWVR13_EL1, 14 => DBGWVR14_EL1, 15 => DBGWVR15_EL1
+]);
+comparator_write!(write_dbgwcr, [
+    0 => DBGWCR0_EL1, 1 => DBGWCR1_EL1, 2 => DBGWCR2_EL1, 3 => DBGWCR3_EL1,
+    4 => DBGWCR4_EL1, 5 => DBGWCR5_EL1, 6 => DBGWCR6_EL1, 7 => DBGWCR7_EL1,
+    8 => DBGWCR8_EL1, 9 => DBGWCR9_EL1, 10 => DBGWCR10_EL1, 11 => DBGWCR11_EL1,
+    12 => DBGWCR12_EL1, 13 => DBGWCR13_EL1, 14 => DBGWCR14_EL1, 15 => DBGWCR15_EL1
+]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn watchpoint_encoding() {
+        // 4 bytes at offset 4 of a double-word, stores from EL1 and EL0
+        assert_eq!(
+            WatchpointConfig::new(0x4000_1004, 4, Access::Store).encode(),
+            Ok((0x4000_1000, 0b1111_0000 << 5 | 0b10 << 3 | 0b11 << 1 | 1))
+        );
+        // 4KB page, masked, linked to breakpoint 5, EL2 only
+        let context = ContextBreakpoint(5);
+        assert_eq!(
+            WatchpointConfig::new(0x8000_0000, 0x1000, Access::LoadStore)
+                .levels(MatchLevels::El2)
+                .linked(&context)
+                .encode(),
+            Ok((
+                0x8000_0000,
+                12 << 24 | 1 << 20 | 5 << 16 | 0b11 << 14 | 1 << 13 | 0xff << 5 | 0b11 << 3 | 1
+            ))
+        );
+        // crosses a double-word boundary
+        assert_eq!(
+            WatchpointConfig::new(0x1006, 3, Access::Load).encode(),
+            Err(Error::InvalidSize(3))
+        );
+        assert_eq!(
+            WatchpointConfig::new(0x1800, 0x1000, Access::Load).encode(),
+            Err(Error::Unaligned(0x1800))
+        );
+    }
+
+    #[test]
+    fn breakpoint_encoding() {
+        assert_eq!(
+            BreakpointConfig::new(0x8_0000)
+                .levels(MatchLevels::El1)
+                .encode(),
+            Ok((0x8_0000, 0b1111 << 5 | 0b01 << 1 | 1))
+        );
+        assert_eq!(
+            BreakpointConfig::new(0x8_0002).encode(),
+            Err(Error::Unaligned(0x8_0002))
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! Exception levels
+
+use crate::registers::{CurrentEL, Readable};
+
+/// An Exception level
+///
+/// The discriminant is the number of the Exception level, as in `CurrentEL.EL`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExceptionLevel {
+    /// EL0, for applications
+    EL0 = 0,
+    /// EL1, for an operating system kernel
+    EL1 = 1,
+    /// EL2, for a hypervisor
+    EL2 = 2,
+    /// EL3, for the Secure monitor
+    EL3 = 3,
+}
+
+impl ExceptionLevel {
+    /// Get the Exception level the PE is running at
+    ///
+    /// `CurrentEL` is not accessible at EL0, so this never returns [`ExceptionLevel::EL0`].
+    pub fn current() -> ExceptionLevel {
+        match CurrentEL.read(CurrentEL::EL) {
+            0 => ExceptionLevel::EL0,
+            1 => ExceptionLevel::EL1,
+            2 => ExceptionLevel::EL2,
+            _ => ExceptionLevel::EL3,
+        }
+    }
+}
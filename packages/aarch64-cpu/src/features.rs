@@ -0,0 +1,330 @@
+//! Discovery of the optional features implemented by the PE
+//!
+//! [`CpuFeatures::detect`] reads the AArch64 ID registers once and decodes them into a plain
+//! struct, so that code can check for features without touching system registers. Values captured
+//! elsewhere can be decoded with [`CpuFeatures::from_raw`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aarch64_cpu::features::CpuFeatures;
+//!
+//! let features = CpuFeatures::detect();
+//! if features.has_pauth && features.has_bti {
+//!     // enable branch protection
+//! }
+//! ```
+
+use tock_registers::{interfaces::Readable, LocalRegisterCopy};
+
+use crate::registers::{
+    ID_AA64DFR0_EL1, ID_AA64ISAR0_EL1, ID_AA64ISAR1_EL1, ID_AA64ISAR2_EL1, ID_AA64MMFR0_EL1,
+    ID_AA64MMFR1_EL1, ID_AA64MMFR2_EL1, ID_AA64PFR0_EL1, ID_AA64PFR1_EL1,
+};
+
+/// Raw values of the ID registers decoded by [`CpuFeatures`]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct IdRegisters {
+    /// `ID_AA64PFR0_EL1`
+    pub pfr0: u64,
+    /// `ID_AA64PFR1_EL1`
+    pub pfr1: u64,
+    /// `ID_AA64ISAR0_EL1`
+    pub isar0: u64,
+    /// `ID_AA64ISAR1_EL1`
+    pub isar1: u64,
+    /// `ID_AA64ISAR2_EL1`
+    pub isar2: u64,
+    /// `ID_AA64MMFR0_EL1`
+    pub mmfr0: u64,
+    /// `ID_AA64MMFR1_EL1`
+    pub mmfr1: u64,
+    /// `ID_AA64MMFR2_EL1`
+    pub mmfr2: u64,
+    /// `ID_AA64DFR0_EL1`
+    pub dfr0: u64,
+}
+
+impl IdRegisters {
+    /// Read the ID registers of the current PE
+    pub fn read() -> IdRegisters {
+        IdRegisters {
+            pfr0: ID_AA64PFR0_EL1.get(),
+            pfr1: ID_AA64PFR1_EL1.get(),
+            isar0: ID_AA64ISAR0_EL1.get(),
+            isar1: ID_AA64ISAR1_EL1.get(),
+            isar2: ID_AA64ISAR2_EL1.get(),
+            mmfr0: ID_AA64MMFR0_EL1.get(),
+            mmfr1: ID_AA64MMFR1_EL1.get(),
+            mmfr2: ID_AA64MMFR2_EL1.get(),
+            dfr0: ID_AA64DFR0_EL1.get(),
+        }
+    }
+}
+
+/// The features implemented by a PE
+///
+/// Levels are the raw values of the corresponding ID register fields, where `0` means not
+/// implemented.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CpuFeatures {
+    /// EL0 can run AArch32 code
+    pub el0_aarch32: bool,
+    /// EL1 can run AArch32 code
+    pub el1_aarch32: bool,
+    /// EL2 is implemented
+    pub has_el2: bool,
+    /// EL3 is implemented
+    pub has_el3: bool,
+    /// Secure EL2 is implemented (FEAT_SEL2)
+    pub has_sel2: bool,
+    /// Floating-point is implemented
+    pub has_fp: bool,
+    /// Advanced SIMD is implemented
+    pub has_advsimd: bool,
+    /// Half-precision floating-point arithmetic is implemented (FEAT_FP16)
+    pub has_fp16: bool,
+    /// The Scalable Vector Extension is implemented (FEAT_SVE)
+    pub has_sve: bool,
+    /// The system register interface to the GICv3 CPU interface is implemented
+    pub has_gic_sysregs: bool,
+    /// RAS Extension level, `ID_AA64PFR0_EL1.RAS`
+    pub ras: u8,
+    /// MPAM major version, `ID_AA64PFR0_EL1.MPAM`
+    pub mpam: u8,
+    /// Activity Monitors level, `ID_AA64PFR0_EL1.AMU`
+    pub amu: u8,
+    /// Data Independent Timing is implemented (FEAT_DIT)
+    pub has_dit: bool,
+    /// The Realm Management Extension is implemented (FEAT_RME)
+    pub has_rme: bool,
+    /// Speculative branch target restrictions level, `ID_AA64PFR0_EL1.CSV2`
+    pub csv2: u8,
+    /// Faulting data is not used under speculation (FEAT_CSV3)
+    pub has_csv3: bool,
+    /// Branch Target Identification is implemented (FEAT_BTI)
+    pub has_bti: bool,
+    /// Memory Tagging Extension level, `ID_AA64PFR1_EL1.MTE`
+    pub mte: u8,
+    /// The Scalable Matrix Extension is implemented (FEAT_SME)
+    pub has_sme: bool,
+    /// Large System Extensions atomics are implemented (FEAT_LSE)
+    pub has_lse: bool,
+    /// `RNDR` and `RNDRRS` are implemented (FEAT_RNG)
+    pub has_rng: bool,
+    /// Outer Shareable TLB maintenance instructions are implemented (FEAT_TLBIOS)
+    pub has_tlbi_os: bool,
+    /// TLB range maintenance instructions are implemented (FEAT_TLBIRANGE)
+    pub has_tlbi_range: bool,
+    /// Address authentication is implemented (FEAT_PAuth), with the QARMA5, QARMA3 or an
+    /// IMPLEMENTATION DEFINED algorithm
+    pub has_pauth: bool,
+    /// Generic authentication (`PACGA`) is implemented
+    pub has_pauth_generic: bool,
+    /// Number of physical address bits
+    pub pa_bits: u8,
+    /// Number of ASID bits
+    pub asid_bits: u8,
+    /// The 4KiB translation granule is supported
+    pub has_granule_4k: bool,
+    /// The 16KiB translation granule is supported
+    pub has_granule_16k: bool,
+    /// The 64KiB translation granule is supported
+    pub has_granule_64k: bool,
+    /// Virtualization Host Extensions are implemented (FEAT_VHE)
+    pub has_vhe: bool,
+    /// Privileged Access Never level, `ID_AA64MMFR1_EL1.PAN`
+    pub pan: u8,
+    /// Number of VMID bits
+    pub vmid_bits: u8,
+    /// Stage 2 execute-never can be set per EL (FEAT_XNX)
+    pub has_xnx: bool,
+    /// Stage 2 forced write-back is implemented (FEAT_S2FWB)
+    pub has_fwb: bool,
+    /// TLB maintenance operands accept a level hint (FEAT_TTL)
+    pub has_ttl: bool,
+    /// PMU version, `ID_AA64DFR0_EL1.PMUVer`
+    pub pmu_version: u8,
+    /// Debug architecture version, `ID_AA64DFR0_EL1.DebugVer`
+    pub debug_version: u8,
+    /// Number of hardware breakpoints
+    pub breakpoints: u8,
+    /// Number of hardware watchpoints
+    pub watchpoints: u8,
+}
+
+impl CpuFeatures {
+    /// Read and decode the ID registers of the current PE
+    #[inline]
+    pub fn detect() -> CpuFeatures {
+        match () {
+            #[cfg(target_arch = "aarch64")]
+            () => CpuFeatures::from_raw(&IdRegisters::read()),
+
+            #[cfg(not(target_arch = "aarch64"))]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Decode raw ID register values
+    pub fn from_raw(regs: &IdRegisters) -> CpuFeatures {
+        let pfr0 = LocalRegisterCopy::<u64, ID_AA64PFR0_EL1::Register>::new(regs.pfr0);
+        let pfr1 = LocalRegisterCopy::<u64, ID_AA64PFR1_EL1::Register>::new(regs.pfr1);
+        let isar0 = LocalRegisterCopy::<u64, ID_AA64ISAR0_EL1::Register>::new(regs.isar0);
+        let isar1 = LocalRegisterCopy::<u64, ID_AA64ISAR1_EL1::Register>::new(regs.isar1);
+        let isar2 = LocalRegisterCopy::<u64, ID_AA64ISAR2_EL1::Register>::new(regs.isar2);
+        let mmfr0 = LocalRegisterCopy::<u64, ID_AA64MMFR0_EL1::Register>::new(regs.mmfr0);
+        let mmfr1 = LocalRegisterCopy::<u64, ID_AA64MMFR1_EL1::Register>::new(regs.mmfr1);
+        let mmfr2 = LocalRegisterCopy::<u64, ID_AA64MMFR2_EL1::Register>::new(regs.mmfr2);
+        let dfr0 = LocalRegisterCopy::<u64, ID_AA64DFR0_EL1::Register>::new(regs.dfr0);
+
+        CpuFeatures {
+            el0_aarch32: pfr0.matches_all(ID_AA64PFR0_EL1::EL0::AArch64AndAArch32),
+            el1_aarch32: pfr0.matches_all(ID_AA64PFR0_EL1::EL1::AArch64AndAArch32),
+            has_el2: pfr0.is_set(ID_AA64PFR0_EL1::EL2),
+            has_el3: pfr0.is_set(ID_AA64PFR0_EL1::EL3),
+            has_sel2: pfr0.is_set(ID_AA64PFR0_EL1::SEL2),
+            has_fp: !pfr0.matches_all(ID_AA64PFR0_EL1::FP::NotImplemented),
+            has_advsimd: !pfr0.matches_all(ID_AA64PFR0_EL1::AdvSIMD::NotImplemented),
+            has_fp16: pfr0.matches_all(ID_AA64PFR0_EL1::FP::ImplementedFP16),
+            has_sve: pfr0.is_set(ID_AA64PFR0_EL1::SVE),
+            has_gic_sysregs: pfr0.is_set(ID_AA64PFR0_EL1::GIC),
+            ras: pfr0.read(ID_AA64PFR0_EL1::RAS) as u8,
+            mpam: pfr0.read(ID_AA64PFR0_EL1::MPAM) as u8,
+            amu: pfr0.read(ID_AA64PFR0_EL1::AMU) as u8,
+            has_dit: pfr0.is_set(ID_AA64PFR0_EL1::DIT),
+            has_rme: pfr0.is_set(ID_AA64PFR0_EL1::RME),
+            csv2: pfr0.read(ID_AA64PFR0_EL1::CSV2) as u8,
+            has_csv3: pfr0.is_set(ID_AA64PFR0_EL1::CSV3),
+            has_bti: pfr1.is_set(ID_AA64PFR1_EL1::BT),
+            mte: pfr1.read(ID_AA64PFR1_EL1::MTE) as u8,
+            has_sme: pfr1.is_set(ID_AA64PFR1_EL1::SME),
+            has_lse: isar0.read(ID_AA64ISAR0_EL1::Atomic) >= 0b0010,
+            has_rng: isar0.is_set(ID_AA64ISAR0_EL1::RNDR),
+            has_tlbi_os: isar0.is_set(ID_AA64ISAR0_EL1::TLB),
+            has_tlbi_range: isar0.read(ID_AA64ISAR0_EL1::TLB) >= 0b0010,
+            has_pauth: isar1.is_set(ID_AA64ISAR1_EL1::APA)
+                || isar1.is_set(ID_AA64ISAR1_EL1::API)
+                || isar2.is_set(ID_AA64ISAR2_EL1::APA3),
+            has_pauth_generic: isar1.is_set(ID_AA64ISAR1_EL1::GPA)
+                || isar1.is_set(ID_AA64ISAR1_EL1::GPI)
+                || isar2.is_set(ID_AA64ISAR2_EL1::GPA3),
+            pa_bits: pa_bits(mmfr0.read(ID_AA64MMFR0_EL1::PARange)),
+            asid_bits: if mmfr0.matches_all(ID_AA64MMFR0_EL1::ASIDBits::Bits_16) {
+                16
+            } else {
+                8
+            },
+            has_granule_4k: !mmfr0.matches_all(ID_AA64MMFR0_EL1::TGran4::NotSupported),
+            has_granule_16k: mmfr0.is_set(ID_AA64MMFR0_EL1::TGran16),
+            has_granule_64k: !mmfr0.matches_all(ID_AA64MMFR0_EL1::TGran64::NotSupported),
+            has_vhe: mmfr1.is_set(ID_AA64MMFR1_EL1::VH),
+            pan: mmfr1.read(ID_AA64MMFR1_EL1::PAN) as u8,
+            vmid_bits: if mmfr1.matches_all(ID_AA64MMFR1_EL1::VMIDBits::Bits16) {
+                16
+            } else {
+                8
+            },
+            has_xnx: mmfr1.is_set(ID_AA64MMFR1_EL1::XNX),
+            has_fwb: mmfr2.is_set(ID_AA64MMFR2_EL1::FWB),
+            has_ttl: mmfr2.is_set(ID_AA64MMFR2_EL1::TTL),
+            pmu_version: dfr0.read(ID_AA64DFR0_EL1::PMUVer) as u8,
+            debug_version: dfr0.read(ID_AA64DFR0_EL1::DebugVer) as u8,
+            breakpoints: dfr0.read(ID_AA64DFR0_EL1::BRPs) as u8 + 1,
+            watchpoints: dfr0.read(ID_AA64DFR0_EL1::WRPs) as u8 + 1,
+        }
+    }
+
+    /// Whether the Memory Tagging Extension is implemented at `level` or above
+    ///
+    /// Level 1 only provides the EL0 tag instructions (FEAT_MTE), level 2 adds the tag storage
+    /// (FEAT_MTE2) and level 3 adds asymmetric tag check faults (FEAT_MTE3).
+    pub fn has_mte(&self, level: u8) -> bool {
+        level != 0 && self.mte >= level
+    }
+}
+
+/// Number of physical address bits for an `ID_AA64MMFR0_EL1.PARange` value
+const fn pa_bits(parange: u64) -> u8 {
+    match parange {
+        0b0000 => 32,
+        0b0001 => 36,
+        0b0010 => 40,
+        0b0011 => 42,
+        0b0100 => 44,
+        0b0101 => 48,
+        0b0110 => 52,
+        _ => 56,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn armv8_0() {
+        // EL0-EL3 with AArch32, FP and AdvSIMD, GICv3, 40-bit PA, 4KiB and 64KiB granules
+        let features = CpuFeatures::from_raw(&IdRegisters {
+            pfr0: 0x0000_0000_0100_2222,
+            isar0: 0x0000_0000_0001_1120,
+            mmfr0: 0x0000_0000_0000_1122,
+            dfr0: 0x0000_0000_1030_5106,
+            ..IdRegisters::default()
+        });
+        assert!(features.el0_aarch32 && features.el1_aarch32);
+        assert!(features.has_el2 && features.has_el3);
+        assert!(features.has_fp && features.has_advsimd && !features.has_fp16);
+        assert!(features.has_gic_sysregs);
+        assert!(!features.has_sve && !features.has_lse && !features.has_rng && !features.has_pauth);
+        assert_eq!(features.pa_bits, 40);
+        assert_eq!(features.asid_bits, 16);
+        assert!(features.has_granule_4k && features.has_granule_64k && !features.has_granule_16k);
+        assert_eq!(features.pmu_version, 1);
+        assert_eq!((features.breakpoints, features.watchpoints), (6, 4));
+        assert!(!features.has_mte(0) && !features.has_mte(1));
+    }
+
+    #[test]
+    fn armv8_5() {
+        let features = CpuFeatures::from_raw(&IdRegisters {
+            // CSV3, CSV2, DIT, AMUv1, SEL2, SVE, RAS, FP16, EL2 and EL3 in AArch64 only
+            pfr0: 0x1101_1011_1011_1111,
+            // MTE2, BTI
+            pfr1: 0x0000_0000_0000_0201,
+            // RNDR, TLBI range, LSE
+            isar0: 0x1200_0000_0020_0000,
+            // GPA, APA
+            isar1: 0x0000_0000_0100_0010,
+            isar2: 0,
+            // 48-bit PA, all granules
+            mmfr0: 0x0000_0000_0010_0025,
+            // XNX, PAN2, VHE, 16-bit VMID
+            mmfr1: 0x0000_0000_1020_0120,
+            // TTL, FWB
+            mmfr2: 0x0001_0100_0000_0000,
+            dfr0: 0,
+        });
+        assert!(!features.el0_aarch32 && !features.el1_aarch32);
+        assert!(features.has_csv3 && features.has_dit && features.has_sve && features.has_fp16);
+        assert_eq!((features.csv2, features.amu, features.ras), (1, 1, 1));
+        assert!(features.has_bti && !features.has_sme);
+        assert!(features.has_mte(1) && features.has_mte(2) && !features.has_mte(3));
+        assert!(features.has_rng && features.has_lse && features.has_tlbi_range);
+        assert!(features.has_pauth && features.has_pauth_generic);
+        assert_eq!(features.pa_bits, 48);
+        assert!(features.has_granule_4k && features.has_granule_16k && features.has_granule_64k);
+        assert!(features.has_vhe && features.has_xnx && features.has_fwb && features.has_ttl);
+        assert_eq!((features.pan, features.vmid_bits), (2, 16));
+    }
+
+    #[test]
+    fn pauth_qarma3() {
+        let features = CpuFeatures::from_raw(&IdRegisters {
+            // GPA3, APA3
+            isar2: 0x0000_0000_0000_3100,
+            ..IdRegisters::default()
+        });
+        assert!(features.has_pauth && features.has_pauth_generic);
+    }
+}
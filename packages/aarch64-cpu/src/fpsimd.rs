@@ -0,0 +1,346 @@
+//! Floating-point and Advanced SIMD control
+//!
+//! ## Floating-point exceptions
+//!
+//! IEEE 754 floating-point exceptions are accumulated in `FPSR` by default. Trapping them, so
+//! that the faulting instruction raises a synchronous exception reported as
+//! [`Syndrome::TrappedFp`], is optional in the architecture: on implementations without support
+//! the trap enable bits of `FPCR` are RAZ/WI. [`enable_traps`] therefore reports which traps were
+//! actually enabled, and callers can fall back to polling the cumulative flags with
+//! [`take_flags`].
+//!
+//! ```no_run
+//! use aarch64_cpu::fpsimd::{self, Exceptions};
+//!
+//! let wanted = Exceptions::INVALID_OPERATION | Exceptions::DIVIDE_BY_ZERO;
+//! let trapped = fpsimd::enable_traps(wanted);
+//! if trapped != wanted {
+//!     // no trap support, check the flags after the computation instead
+//!     fpsimd::take_flags();
+//! }
+//! ```
+//!
+//! ## Context switching
+//!
+//! [`save`] and [`restore`] copy V0-V31, `FPCR` and `FPSR` to and from a [`State`]. [`Lazy`]
+//! defers that work until a context actually uses the registers: a context switch only traps
+//! Advanced SIMD and floating-point instructions, and the state is switched by the handler of the
+//! resulting [`Syndrome::FpSimdAccess`] exception. Contexts that never use the registers never
+//! pay for the switch.
+//!
+//! ```no_run
+//! use aarch64_cpu::{
+//!     fpsimd::{Lazy, State},
+//!     syndrome::Syndrome,
+//!     ExceptionLevel,
+//! };
+//!
+//! static mut LAZY: Lazy = Lazy::new(ExceptionLevel::EL1);
+//! static mut TASK_FP: [State; 2] = [State::new(), State::new()];
+//!
+//! // in the scheduler, when switching to task 1
+//! unsafe { (*&raw mut LAZY).switch_to(&raw mut TASK_FP[1]) };
+//!
+//! // in the Synchronous exception handler
+//! # let esr = 0;
+//! if let Syndrome::FpSimdAccess = Syndrome::decode(esr) {
+//!     unsafe { (*&raw mut LAZY).handle_access_trap() };
+//! }
+//! ```
+//!
+//! [`Syndrome::TrappedFp`]: crate::syndrome::Syndrome::TrappedFp
+//! [`Syndrome::FpSimdAccess`]: crate::syndrome::Syndrome::FpSimdAccess
+
+pub use crate::syndrome::Exceptions;
+use crate::{asm::barrier, registers::*, ExceptionLevel};
+
+/// Offset of the trap enable bits in `FPCR` relative to the cumulative bits in `FPSR`
+const TRAP_ENABLE_SHIFT: u32 = 8;
+
+/// Traps the given floating-point exceptions, and returns the set of exceptions that are trapped
+/// afterwards
+///
+/// Exceptions that the implementation cannot trap are missing from the returned set.
+pub fn enable_traps(exceptions: Exceptions) -> Exceptions {
+    FPCR.set(FPCR.get() | exceptions.bits() << TRAP_ENABLE_SHIFT);
+    enabled_traps()
+}
+
+/// Stops trapping the given floating-point exceptions
+pub fn disable_traps(exceptions: Exceptions) {
+    FPCR.set(FPCR.get() & !(exceptions.bits() << TRAP_ENABLE_SHIFT));
+}
+
+/// Returns the set of floating-point exceptions that are trapped
+pub fn enabled_traps() -> Exceptions {
+    Exceptions::from_bits(FPCR.get() >> TRAP_ENABLE_SHIFT)
+}
+
+/// Returns the floating-point exceptions that occurred without being trapped since the flags were
+/// last cleared
+pub fn flags() -> Exceptions {
+    Exceptions::from_bits(FPSR.get())
+}
+
+/// Clears the cumulative flags of the given floating-point exceptions
+pub fn clear_flags(exceptions: Exceptions) {
+    FPSR.set(FPSR.get() & !exceptions.bits());
+}
+
+/// Returns and clears the cumulative floating-point exception flags
+pub fn take_flags() -> Exceptions {
+    let flags = flags();
+    clear_flags(flags);
+    flags
+}
+
+/// The Advanced SIMD and floating-point register state of a context
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[repr(C, align(16))]
+pub struct State {
+    /// V0-V31
+    pub v: [u128; 32],
+    /// Floating-point Control Register
+    pub fpcr: u64,
+    /// Floating-point Status Register
+    pub fpsr: u64,
+}
+
+impl State {
+    /// All registers zero, which is also the state of a context that has not used them yet
+    pub const fn new() -> State {
+        State {
+            v: [0; 32],
+            fpcr: 0,
+            fpsr: 0,
+        }
+    }
+}
+
+/// Save V0-V31, `FPCR` and `FPSR` to `state`
+///
+/// Advanced SIMD and floating-point instructions must not be trapped at the current Exception
+/// level.
+pub fn save(state: &mut State) {
+    save_registers(state.v.as_mut_ptr());
+    state.fpcr = FPCR.get();
+    state.fpsr = FPSR.get();
+}
+
+/// Restore V0-V31, `FPCR` and `FPSR` from `state`
+///
+/// Advanced SIMD and floating-point instructions must not be trapped at the current Exception
+/// level.
+///
+/// # Safety
+///
+/// This replaces the callee-saved D8-D15 without the compiler knowing. It must only be called
+/// where the compiler does not keep values in those registers, e.g. in code built for a soft-float
+/// target right before returning to the context that owns the state.
+pub unsafe fn restore(state: &State) {
+    FPCR.set(state.fpcr);
+    FPSR.set(state.fpsr);
+    restore_registers(state.v.as_ptr());
+}
+
+/// Trap Advanced SIMD and floating-point instructions executed at `el` and below
+///
+/// EL0 and EL1 are trapped to EL1 by `CPACR_EL1.FPEN`. EL2 is trapped by `CPTR_EL2.TFP`, or
+/// `CPTR_EL2.FPEN` when `HCR_EL2.E2H` is 1, and EL3 by `CPTR_EL3.TFP`, which also trap the
+/// Exception levels below them. Trapped instructions raise a synchronous exception reported as
+/// [`Syndrome::FpSimdAccess`].
+///
+/// [`Syndrome::FpSimdAccess`]: crate::syndrome::Syndrome::FpSimdAccess
+pub fn trap_access(el: ExceptionLevel) {
+    set_access(el, false);
+}
+
+/// Stop trapping Advanced SIMD and floating-point instructions executed at `el` and below
+///
+/// Allowing EL0 also allows EL1, as `CPACR_EL1.FPEN` cannot trap EL1 alone.
+pub fn allow_access(el: ExceptionLevel) {
+    set_access(el, true);
+}
+
+/// Check if the trap control of `el` allows Advanced SIMD and floating-point instructions
+///
+/// The instructions may still be trapped by a higher Exception level.
+pub fn is_access_allowed(el: ExceptionLevel) -> bool {
+    match el {
+        ExceptionLevel::EL0 => CPACR_EL1.matches_all(CPACR_EL1::FPEN::TrapNothing),
+        ExceptionLevel::EL1 => {
+            CPACR_EL1.matches_any(&[CPACR_EL1::FPEN::TrapEl0, CPACR_EL1::FPEN::TrapNothing])
+        }
+        ExceptionLevel::EL2 if HCR_EL2.is_set(HCR_EL2::E2H) => {
+            CPTR_EL2_E2H.matches_all(CPTR_EL2_E2H::FPEN::TrapNothing)
+        }
+        ExceptionLevel::EL2 => CPTR_EL2.matches_all(CPTR_EL2::TFP::NoTrap),
+        ExceptionLevel::EL3 => CPTR_EL3.matches_all(CPTR_EL3::TFP::NoTrap),
+    }
+}
+
+fn set_access(el: ExceptionLevel, allow: bool) {
+    match el {
+        ExceptionLevel::EL0 => CPACR_EL1.modify(if allow {
+            CPACR_EL1::FPEN::TrapNothing
+        } else {
+            CPACR_EL1::FPEN::TrapEl0
+        }),
+        ExceptionLevel::EL1 => CPACR_EL1.modify(if allow {
+            CPACR_EL1::FPEN::TrapNothing
+        } else {
+            CPACR_EL1::FPEN::TrapEl0El1
+        }),
+        ExceptionLevel::EL2 if HCR_EL2.is_set(HCR_EL2::E2H) => CPTR_EL2_E2H.modify(if allow {
+            CPTR_EL2_E2H::FPEN::TrapNothing
+        } else {
+            CPTR_EL2_E2H::FPEN::TrapEl2El0
+        }),
+        ExceptionLevel::EL2 => CPTR_EL2.modify(if allow {
+            CPTR_EL2::TFP::NoTrap
+        } else {
+            CPTR_EL2::TFP::Trap
+        }),
+        ExceptionLevel::EL3 => CPTR_EL3.modify(if allow {
+            CPTR_EL3::TFP::NoTrap
+        } else {
+            CPTR_EL3::TFP::Trap
+        }),
+    }
+    barrier::isb(barrier::SY);
+}
+
+/// Lazy switching of the Advanced SIMD and floating-point state between contexts
+///
+/// `Lazy` remembers which [`State`] the registers currently belong to. [`switch_to`] traps the
+/// registers unless the next context already owns them, and [`handle_access_trap`] moves them to
+/// the running context when it first uses them.
+///
+/// The code that runs while the registers are trapped, including the exception handler up to
+/// [`handle_access_trap`], must not use them, e.g. by being built for a soft-float target.
+///
+/// [`switch_to`]: Lazy::switch_to
+/// [`handle_access_trap`]: Lazy::handle_access_trap
+#[derive(Debug)]
+pub struct Lazy {
+    el: ExceptionLevel,
+    /// The state that the registers belong to
+    owner: *mut State,
+    /// The state of the running context
+    current: *mut State,
+}
+
+// SAFETY: the pointers are only dereferenced by the `unsafe` methods, whose callers guarantee
+// that the states are valid
+unsafe impl Send for Lazy {}
+
+impl Lazy {
+    /// Create a `Lazy` that traps the Advanced SIMD and floating-point instructions of `el` and
+    /// below
+    pub const fn new(el: ExceptionLevel) -> Lazy {
+        Lazy {
+            el,
+            owner: core::ptr::null_mut(),
+            current: core::ptr::null_mut(),
+        }
+    }
+
+    /// Switch to the context whose state is `next`
+    ///
+    /// Access is allowed right away if `next` still owns the registers, and trapped otherwise.
+    ///
+    /// # Safety
+    ///
+    /// `next` must stay valid, and must not be accessed other than through `self`, until it is
+    /// [`release`](Lazy::release)d.
+    pub unsafe fn switch_to(&mut self, next: *mut State) {
+        self.current = next;
+        if self.owner == next {
+            allow_access(self.el);
+        } else {
+            trap_access(self.el);
+        }
+    }
+
+    /// Handle a [`Syndrome::FpSimdAccess`] exception caused by the running context
+    ///
+    /// This allows access, saves the registers to the state of their previous owner, if any, and
+    /// loads the state of the running context.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`restore`].
+    ///
+    /// [`Syndrome::FpSimdAccess`]: crate::syndrome::Syndrome::FpSimdAccess
+    pub unsafe fn handle_access_trap(&mut self) {
+        allow_access(self.el);
+        if self.owner == self.current || self.current.is_null() {
+            return;
+        }
+
+        // SAFETY: the caller of `switch_to` guarantees that the states are valid and not aliased
+        unsafe {
+            if let Some(owner) = self.owner.as_mut() {
+                save(owner);
+            }
+            restore(&*self.current);
+        }
+        self.owner = self.current;
+    }
+
+    /// Forget `state`, e.g. because its context has exited
+    ///
+    /// If `state` owns the registers, their contents are discarded and the next context that uses
+    /// them loads its own state.
+    pub fn release(&mut self, state: *mut State) {
+        if self.owner == state {
+            self.owner = core::ptr::null_mut();
+        }
+        if self.current == state {
+            self.current = core::ptr::null_mut();
+        }
+    }
+}
+
+/// Store V0-V31 at `v`
+#[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+fn save_registers(v: *mut u128) {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => unsafe {
+            core::arch::asm!(
+                ".arch_extension fp",
+                ".irp n, 0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23,24,25,26,27,28,29,30,31",
+                "STR q\\n, [{v}, #(16 * \\n)]",
+                ".endr",
+                v = in(reg) v,
+                options(nostack, preserves_flags)
+            );
+        },
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+/// Load V0-V31 from `v`
+#[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+fn restore_registers(v: *const u128) {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => unsafe {
+            core::arch::asm!(
+                ".arch_extension fp",
+                ".irp n, 0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23,24,25,26,27,28,29,30,31",
+                "LDR q\\n, [{v}, #(16 * \\n)]",
+                ".endr",
+                v = in(reg) v,
+                clobber_abi("C"),
+                options(readonly, nostack, preserves_flags)
+            );
+        },
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
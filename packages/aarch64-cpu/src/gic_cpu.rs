@@ -0,0 +1,363 @@
+//! Code and types for the GICv3 CPU interface System registers
+//!
+//! These functions use the `ICC_*_EL1` System register interface, which must be enabled first,
+//! e.g. with [`enable_system_registers`]. Configuring the Distributor and Redistributors is out of
+//! scope, as they are memory-mapped.
+//!
+//! ```no_run
+//! use aarch64_cpu::gic_cpu::{self, Group, IntId, SgiTarget};
+//!
+//! gic_cpu::enable_system_registers();
+//! gic_cpu::set_priority_mask(0xff);
+//! gic_cpu::enable_group(Group::Group1, true);
+//!
+//! // Interrupt all other PEs
+//! gic_cpu::send_sgi(IntId::sgi(3), SgiTarget::AllOther, Group::Group1);
+//!
+//! // In the IRQ handler
+//! if let Some(intid) = gic_cpu::acknowledge(Group::Group1) {
+//!     // handle `intid`
+//!     gic_cpu::end_of_interrupt(intid, Group::Group1);
+//! }
+//! ```
+//!
+//! See the [Arm Generic Interrupt Controller Architecture Specification][gic].
+//!
+//! [gic]: https://developer.arm.com/documentation/ihi0069/latest/
+
+use crate::{asm::barrier, registers::*};
+use core::fmt;
+
+/// An interrupt ID
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IntId(u32);
+
+impl IntId {
+    /// The number of Software Generated Interrupts
+    pub const SGI_COUNT: u32 = 16;
+
+    /// The number of Private Peripheral Interrupts
+    pub const PPI_COUNT: u32 = 16;
+
+    /// The first Shared Peripheral Interrupt
+    pub const SPI_START: u32 = 32;
+
+    /// The first special INTID
+    pub const SPECIAL_START: u32 = 1020;
+
+    /// The special INTID returned when there is no pending interrupt
+    pub const SPURIOUS: IntId = IntId(1023);
+
+    /// Create a Software Generated Interrupt ID
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sgi` is not below [`IntId::SGI_COUNT`].
+    pub const fn sgi(sgi: u32) -> IntId {
+        assert!(sgi < Self::SGI_COUNT);
+        IntId(sgi)
+    }
+
+    /// Create a Private Peripheral Interrupt ID
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ppi` is not below [`IntId::PPI_COUNT`].
+    pub const fn ppi(ppi: u32) -> IntId {
+        assert!(ppi < Self::PPI_COUNT);
+        IntId(Self::SGI_COUNT + ppi)
+    }
+
+    /// Create a Shared Peripheral Interrupt ID
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting INTID would be a special INTID.
+    pub const fn spi(spi: u32) -> IntId {
+        assert!(spi < Self::SPECIAL_START - Self::SPI_START);
+        IntId(Self::SPI_START + spi)
+    }
+
+    /// Create an interrupt ID from its raw value
+    pub const fn from_raw(raw: u32) -> IntId {
+        IntId(raw)
+    }
+
+    /// Get the raw value of the interrupt ID
+    pub const fn raw(self) -> u32 {
+        self.0
+    }
+
+    /// Is this a Software Generated Interrupt?
+    pub const fn is_sgi(self) -> bool {
+        self.0 < Self::SGI_COUNT
+    }
+
+    /// Is this a Private Peripheral Interrupt?
+    pub const fn is_ppi(self) -> bool {
+        self.0 >= Self::SGI_COUNT && self.0 < Self::SPI_START
+    }
+
+    /// Is this a Shared Peripheral Interrupt?
+    pub const fn is_spi(self) -> bool {
+        self.0 >= Self::SPI_START && self.0 < Self::SPECIAL_START
+    }
+
+    /// Is this one of the special INTIDs 1020-1023?
+    pub const fn is_special(self) -> bool {
+        self.0 >= Self::SPECIAL_START && self.0 <= Self::SPURIOUS.0
+    }
+}
+
+impl fmt::Display for IntId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_sgi() {
+            write!(f, "SGI {}", self.0)
+        } else if self.is_ppi() {
+            write!(f, "PPI {}", self.0 - Self::SGI_COUNT)
+        } else if self.is_spi() {
+            write!(f, "SPI {}", self.0 - Self::SPI_START)
+        } else {
+            write!(f, "INTID {}", self.0)
+        }
+    }
+}
+
+/// An interrupt group
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Group {
+    /// Group 0, normally handled as FIQs at EL3
+    Group0,
+    /// Group 1 for the current Security state
+    Group1,
+}
+
+/// The affinity of a PE, as reported by `MPIDR_EL1`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Affinity {
+    /// Affinity level 3
+    pub aff3: u8,
+    /// Affinity level 2
+    pub aff2: u8,
+    /// Affinity level 1
+    pub aff1: u8,
+    /// Affinity level 0
+    pub aff0: u8,
+}
+
+impl Affinity {
+    /// Get the affinity of the PE this code is running on
+    pub fn current() -> Affinity {
+        Affinity::from_mpidr(MPIDR_EL1.get())
+    }
+
+    /// Extract the affinity fields from an `MPIDR_EL1` value
+    pub const fn from_mpidr(mpidr: u64) -> Affinity {
+        Affinity {
+            aff3: (mpidr >> 32) as u8,
+            aff2: (mpidr >> 16) as u8,
+            aff1: (mpidr >> 8) as u8,
+            aff0: mpidr as u8,
+        }
+    }
+}
+
+/// The PEs that a Software Generated Interrupt is sent to
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SgiTarget {
+    /// Every PE in the system except the one sending the SGI
+    AllOther,
+    /// A single PE
+    Pe(Affinity),
+    /// A set of PEs in the same cluster
+    ///
+    /// Bit `n` of `targets` selects the PE with Aff0 value `range * 16 + n`. Ranges other than
+    /// 0 need `ICC_CTLR_EL1.RSS`.
+    List {
+        /// Affinity level 3 of the cluster
+        aff3: u8,
+        /// Affinity level 2 of the cluster
+        aff2: u8,
+        /// Affinity level 1 of the cluster
+        aff1: u8,
+        /// Which group of 16 Aff0 values `targets` refers to
+        range: u8,
+        /// Bitmap of target PEs within the range
+        targets: u16,
+    },
+}
+
+impl SgiTarget {
+    /// Encode the `ICC_SGI*R_EL1` value that sends `intid` to these targets
+    fn sgi_register_value(self, intid: IntId) -> u64 {
+        let intid = ICC_SGI1R_EL1::INTID.val(intid.raw() as u64);
+        let (aff3, aff2, aff1, range, targets) = match self {
+            SgiTarget::AllOther => return (intid + ICC_SGI1R_EL1::IRM::AllButSelf).value,
+            SgiTarget::Pe(affinity) => (
+                affinity.aff3,
+                affinity.aff2,
+                affinity.aff1,
+                affinity.aff0 / 16,
+                1 << (affinity.aff0 % 16),
+            ),
+            SgiTarget::List {
+                aff3,
+                aff2,
+                aff1,
+                range,
+                targets,
+            } => (aff3, aff2, aff1, range, targets),
+        };
+
+        (intid
+            + ICC_SGI1R_EL1::IRM::TargetList
+            + ICC_SGI1R_EL1::Aff3.val(aff3 as u64)
+            + ICC_SGI1R_EL1::Aff2.val(aff2 as u64)
+            + ICC_SGI1R_EL1::Aff1.val(aff1 as u64)
+            + ICC_SGI1R_EL1::RS.val(range as u64)
+            + ICC_SGI1R_EL1::TargetList.val(targets as u64))
+        .value
+    }
+}
+
+/// Enable the System register interface to the CPU interface for the current Exception level
+///
+/// Must be called from EL1 or higher. At EL1 and EL2 this only takes effect if the higher
+/// Exception levels allow it.
+pub fn enable_system_registers() {
+    match CurrentEL.read(CurrentEL::EL) {
+        3 => ICC_SRE_EL3.modify(ICC_SRE_EL3::SRE::SET),
+        2 => ICC_SRE_EL2.modify(ICC_SRE_EL2::SRE::SET),
+        _ => ICC_SRE_EL1.modify(ICC_SRE_EL1::SRE::SET),
+    }
+    barrier::isb(barrier::SY);
+}
+
+/// Enable or disable an interrupt group on this CPU interface
+pub fn enable_group(group: Group, enabled: bool) {
+    match group {
+        Group::Group0 => ICC_IGRPEN0_EL1.write(ICC_IGRPEN0_EL1::Enable.val(enabled as u64)),
+        Group::Group1 => ICC_IGRPEN1_EL1.write(ICC_IGRPEN1_EL1::Enable.val(enabled as u64)),
+    }
+    barrier::isb(barrier::SY);
+}
+
+/// Only signal interrupts with a higher priority (numerically lower value) than `priority`
+pub fn set_priority_mask(priority: u8) {
+    ICC_PMR_EL1.write(ICC_PMR_EL1::Priority.val(priority as u64));
+}
+
+/// Get the group priority of the interrupt being handled, or 0xFF if there is none
+pub fn running_priority() -> u8 {
+    ICC_RPR_EL1.read(ICC_RPR_EL1::Priority) as u8
+}
+
+/// Acknowledge the highest priority pending interrupt of `group`
+///
+/// Returns `None` if there is no such interrupt.
+pub fn acknowledge(group: Group) -> Option<IntId> {
+    let intid = IntId(match group {
+        Group::Group0 => ICC_IAR0_EL1.read(ICC_IAR0_EL1::INTID),
+        Group::Group1 => ICC_IAR1_EL1.read(ICC_IAR1_EL1::INTID),
+    } as u32);
+
+    if intid.is_special() {
+        None
+    } else {
+        Some(intid)
+    }
+}
+
+/// Get the highest priority pending interrupt of `group`, without acknowledging it
+pub fn highest_pending(group: Group) -> Option<IntId> {
+    let intid = IntId(match group {
+        Group::Group0 => ICC_HPPIR0_EL1.read(ICC_HPPIR0_EL1::INTID),
+        Group::Group1 => ICC_HPPIR1_EL1.read(ICC_HPPIR1_EL1::INTID),
+    } as u32);
+
+    if intid.is_special() {
+        None
+    } else {
+        Some(intid)
+    }
+}
+
+/// Signal the end of an interrupt returned by [`acknowledge`]
+///
+/// This drops the running priority, and also deactivates the interrupt unless
+/// `ICC_CTLR_EL1.EOImode` is set, in which case use [`deactivate`] as well.
+pub fn end_of_interrupt(intid: IntId, group: Group) {
+    match group {
+        Group::Group0 => ICC_EOIR0_EL1.write(ICC_EOIR0_EL1::INTID.val(intid.raw() as u64)),
+        Group::Group1 => ICC_EOIR1_EL1.write(ICC_EOIR1_EL1::INTID.val(intid.raw() as u64)),
+    }
+    barrier::isb(barrier::SY);
+}
+
+/// Deactivate an interrupt, when `ICC_CTLR_EL1.EOImode` separates this from [`end_of_interrupt`]
+pub fn deactivate(intid: IntId) {
+    ICC_DIR_EL1.write(ICC_DIR_EL1::INTID.val(intid.raw() as u64));
+    barrier::isb(barrier::SY);
+}
+
+/// Send the Software Generated Interrupt `intid` to `target`, as an interrupt of `group`
+///
+/// Preceding writes to memory are made visible to the targets before the SGI is sent.
+///
+/// # Panics
+///
+/// Panics if `intid` is not an SGI.
+pub fn send_sgi(intid: IntId, target: SgiTarget, group: Group) {
+    assert!(intid.is_sgi());
+
+    let value = target.sgi_register_value(intid);
+    barrier::dsb(barrier::ISHST);
+    match group {
+        Group::Group0 => ICC_SGI0R_EL1.set(value),
+        Group::Group1 => ICC_SGI1R_EL1.set(value),
+    }
+    barrier::isb(barrier::SY);
+}
+
+/// Send the Software Generated Interrupt `intid` to `target`, as a Group 1 interrupt of the
+/// Security state that is not the current one
+///
+/// # Panics
+///
+/// Panics if `intid` is not an SGI.
+pub fn send_sgi_other_security_state(intid: IntId, target: SgiTarget) {
+    assert!(intid.is_sgi());
+
+    let value = target.sgi_register_value(intid);
+    barrier::dsb(barrier::ISHST);
+    ICC_ASGI1R_EL1.set(value);
+    barrier::isb(barrier::SY);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn intid_ranges() {
+        assert_eq!(IntId::sgi(15).raw(), 15);
+        assert_eq!(IntId::ppi(14).raw(), 30);
+        assert_eq!(IntId::spi(0).raw(), 32);
+        assert!(IntId::ppi(0).is_ppi() && !IntId::ppi(0).is_sgi());
+        assert!(IntId::SPURIOUS.is_special() && !IntId::SPURIOUS.is_spi());
+    }
+
+    #[test]
+    fn sgi_encoding() {
+        assert_eq!(
+            SgiTarget::AllOther.sgi_register_value(IntId::sgi(5)),
+            1 << 40 | 5 << 24
+        );
+        // MPIDR 0x0000_0001_0002_0113: Aff3 1, Aff2 2, Aff1 1, Aff0 0x13
+        let pe = Affinity::from_mpidr(0x0000_0001_0002_0113);
+        assert_eq!(
+            SgiTarget::Pe(pe).sgi_register_value(IntId::sgi(1)),
+            1 << 48 | 1 << 44 | 2 << 32 | 1 << 24 | 1 << 16 | 1 << 3
+        );
+    }
+}
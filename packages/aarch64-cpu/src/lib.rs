@@ -57,8 +57,23 @@
 
 pub mod asm;
 mod critical_section;
+pub mod dcc;
+pub mod debug;
+mod exception_level;
+pub mod features;
+pub mod fpsimd;
 pub mod generic_timer;
+pub mod gic_cpu;
+pub mod mte;
+pub mod paging;
+pub mod pauth;
+pub mod pmu;
 pub mod registers;
+pub mod smccc;
+pub mod sve;
+pub mod syndrome;
 
 #[cfg(arm_architecture = "v8-r")]
 pub mod pmsav8;
+
+pub use exception_level::ExceptionLevel;
@@ -0,0 +1,516 @@
+//! Memory Tagging Extension (FEAT_MTE2) tag checking and tagging primitives
+//!
+//! Every 16-byte granule of memory mapped as Tagged Normal (`0xF0` in `MAIR_ELx`) has a 4-bit
+//! Allocation Tag, and bits \[59:56\] of a pointer hold its logical tag. When tag checking is
+//! [`enable`]d for an Exception level, an access whose logical tag differs from the Allocation Tag
+//! of the accessed granule is a Tag Check Fault, reported as selected by [`TagCheck`].
+//!
+//! Tagged pointers need the top byte to be ignored by address translation (`TCR_ELx.TBI`), and
+//! higher Exception levels must not prevent tag access, i.e. `SCR_EL3.ATA` and `HCR_EL2.ATA` must
+//! be set.
+//!
+//! ```no_run
+//! use aarch64_cpu::{
+//!     mte::{self, TagCheck},
+//!     ExceptionLevel,
+//! };
+//!
+//! // never hand out tag 0, which is the tag of untagged pointers
+//! mte::init_tag_generator(0x5eed, 1 << 0);
+//! mte::enable(ExceptionLevel::EL1, TagCheck::Sync);
+//!
+//! // a 16-byte aligned block of Tagged Normal memory
+//! let block = 0x8000_0000;
+//! let ptr = mte::irg(block, 0);
+//! unsafe { mte::tag_range(ptr, 64) }.unwrap();
+//!
+//! // on free, retag the block so that accesses through `ptr` fault
+//! unsafe { mte::retag_range(ptr, 64) }.unwrap();
+//! ```
+
+use crate::{asm::barrier, registers::*, ExceptionLevel};
+
+/// Size of the memory granule that an Allocation Tag applies to, in bytes
+pub const GRANULE_SIZE: u64 = 16;
+
+/// Position of the logical tag in a pointer
+const TAG_SHIFT: u32 = 56;
+
+/// Ways this API can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The address or the length is not a multiple of [`GRANULE_SIZE`]
+    Unaligned(u64),
+    /// The range starting at the address extends past the top of the address space
+    OutOfRange(u64),
+}
+
+/// How Tag Check Faults are reported
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TagCheck {
+    /// Tag Check Faults have no effect
+    Disabled,
+    /// Tag Check Faults cause a synchronous Data Abort
+    Sync,
+    /// Tag Check Faults are accumulated in `TFSR_ELx`, see [`take_async_faults`]
+    Async,
+    /// Tag Check Faults are synchronous on reads and asynchronous on writes (FEAT_MTE3)
+    Asymmetric,
+}
+
+impl TagCheck {
+    /// The `SCTLR_ELx.TCF` encoding
+    fn tcf(self) -> u64 {
+        match self {
+            TagCheck::Disabled => 0b00,
+            TagCheck::Sync => 0b01,
+            TagCheck::Async => 0b10,
+            TagCheck::Asymmetric => 0b11,
+        }
+    }
+}
+
+/// Allow Allocation Tag access at `el` and report its Tag Check Faults as `check`
+///
+/// This uses `SCTLR_ELx.{ATA, TCF}` of `el`, or `SCTLR_EL1.{ATA0, TCF0}` for EL0, so EL0 must be
+/// configured from EL1 or higher.
+pub fn enable(el: ExceptionLevel, check: TagCheck) {
+    let tcf = check.tcf();
+    match el {
+        ExceptionLevel::EL0 => {
+            SCTLR_EL1.modify(SCTLR_EL1::ATA0::NoPrevent + SCTLR_EL1::TCF0.val(tcf))
+        }
+        ExceptionLevel::EL1 => {
+            SCTLR_EL1.modify(SCTLR_EL1::ATA::NoPrevent + SCTLR_EL1::TCF.val(tcf))
+        }
+        ExceptionLevel::EL2 => {
+            SCTLR_EL2.modify(SCTLR_EL2::ATA::NoPrevent + SCTLR_EL2::TCF.val(tcf))
+        }
+        ExceptionLevel::EL3 => {
+            SCTLR_EL3.modify(SCTLR_EL3::ATA::NoPrevent + SCTLR_EL3::TCF.val(tcf))
+        }
+    }
+    barrier::isb(barrier::SY);
+}
+
+/// Prevent Allocation Tag access and tag checking at `el`
+pub fn disable(el: ExceptionLevel) {
+    match el {
+        ExceptionLevel::EL0 => {
+            SCTLR_EL1.modify(SCTLR_EL1::ATA0::Prevent + SCTLR_EL1::TCF0::NoEffect)
+        }
+        ExceptionLevel::EL1 => SCTLR_EL1.modify(SCTLR_EL1::ATA::Prevent + SCTLR_EL1::TCF::NoEffect),
+        ExceptionLevel::EL2 => SCTLR_EL2.modify(SCTLR_EL2::ATA::Prevent + SCTLR_EL2::TCF::NoEffect),
+        ExceptionLevel::EL3 => SCTLR_EL3.modify(SCTLR_EL3::ATA::Prevent + SCTLR_EL3::TCF::NoEffect),
+    }
+    barrier::isb(barrier::SY);
+}
+
+/// Seed the pseudo-random tag generator used by [`irg`] and exclude tags from all generated tags
+///
+/// Bit n of `exclude` excludes tag n. `seed` should not be zero, as that makes [`irg`] return
+/// only the first non-excluded tag.
+pub fn init_tag_generator(seed: u16, exclude: u16) {
+    GCR_EL1.write(GCR_EL1::RRND::CLEAR + GCR_EL1::Exclude.val(exclude as u64));
+    RGSR_EL1.write(RGSR_EL1::SEED.val(seed as u64));
+    barrier::isb(barrier::SY);
+}
+
+/// Get the logical tag of `ptr`
+pub const fn tag(ptr: u64) -> u8 {
+    ((ptr >> TAG_SHIFT) & 0xf) as u8
+}
+
+/// Replace the logical tag of `ptr` with `tag`
+pub const fn with_tag(ptr: u64, tag: u8) -> u64 {
+    (ptr & !(0xf << TAG_SHIFT)) | (((tag & 0xf) as u64) << TAG_SHIFT)
+}
+
+/// Asynchronous Tag Check Faults accumulated by an Exception level
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct AsyncFaults {
+    /// A fault in the address range translated by `TTBR0_ELx`
+    pub ttbr0: bool,
+    /// A fault in the address range translated by `TTBR1_ELx`
+    pub ttbr1: bool,
+}
+
+impl AsyncFaults {
+    /// Whether any fault was accumulated
+    pub fn any(&self) -> bool {
+        self.ttbr0 || self.ttbr1
+    }
+}
+
+/// Get and clear the asynchronous Tag Check Faults accumulated by `el`
+///
+/// This uses `TFSR_ELx` of `el`, or `TFSRE0_EL1` for EL0.
+pub fn take_async_faults(el: ExceptionLevel) -> AsyncFaults {
+    // make sure the faults of all earlier accesses have been recorded
+    barrier::dsb(barrier::NSH);
+    barrier::isb(barrier::SY);
+
+    let faults = match el {
+        ExceptionLevel::EL0 => {
+            let tfsr = TFSRE0_EL1.extract();
+            TFSRE0_EL1.set(0);
+            AsyncFaults {
+                ttbr0: tfsr.is_set(TFSRE0_EL1::TF0),
+                ttbr1: tfsr.is_set(TFSRE0_EL1::TF1),
+            }
+        }
+        ExceptionLevel::EL1 => {
+            let tfsr = TFSR_EL1.extract();
+            TFSR_EL1.set(0);
+            AsyncFaults {
+                ttbr0: tfsr.is_set(TFSR_EL1::TF0),
+                ttbr1: tfsr.is_set(TFSR_EL1::TF1),
+            }
+        }
+        ExceptionLevel::EL2 => {
+            let tfsr = TFSR_EL2.extract();
+            TFSR_EL2.set(0);
+            AsyncFaults {
+                ttbr0: tfsr.is_set(TFSR_EL2::TF0),
+                ttbr1: tfsr.is_set(TFSR_EL2::TF1),
+            }
+        }
+        ExceptionLevel::EL3 => {
+            let tfsr = TFSR_EL3.extract();
+            TFSR_EL3.set(0);
+            AsyncFaults {
+                ttbr0: tfsr.is_set(TFSR_EL3::TF0),
+                ttbr1: false,
+            }
+        }
+    };
+    barrier::isb(barrier::SY);
+    faults
+}
+
+/// Insert a random logical tag into `ptr` (IRG)
+///
+/// Bit n of `exclude` excludes tag n, in addition to the tags excluded by `GCR_EL1`.
+#[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+#[inline(always)]
+pub fn irg(ptr: u64, exclude: u16) -> u64 {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => {
+            let tagged;
+            unsafe {
+                core::arch::asm!(
+                    ".arch_extension memtag",
+                    "IRG {0}, {1}, {2}",
+                    out(reg) tagged,
+                    in(reg) ptr,
+                    in(reg) exclude as u64,
+                    options(nomem, nostack, preserves_flags)
+                )
+            };
+            tagged
+        }
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+/// Add the logical tag of `ptr` to the exclusion mask `exclude` (GMI)
+#[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+#[inline(always)]
+pub fn gmi(ptr: u64, exclude: u16) -> u16 {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => {
+            let mask: u64;
+            unsafe {
+                core::arch::asm!(
+                    ".arch_extension memtag",
+                    "GMI {0}, {1}, {2}",
+                    out(reg) mask,
+                    in(reg) ptr,
+                    in(reg) exclude as u64,
+                    options(nomem, nostack, preserves_flags)
+                )
+            };
+            mask as u16
+        }
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+/// Add `OFFSET` bytes to `ptr` and advance its logical tag by `TAG_OFFSET` (ADDG)
+///
+/// The tag skips the tags excluded by `GCR_EL1`. `OFFSET` must be a multiple of [`GRANULE_SIZE`]
+/// up to 1008, and `TAG_OFFSET` at most 15.
+#[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+#[inline(always)]
+pub fn addg<const OFFSET: u16, const TAG_OFFSET: u8>(ptr: u64) -> u64 {
+    const { assert!(OFFSET.is_multiple_of(GRANULE_SIZE as u16) && OFFSET <= 1008 && TAG_OFFSET <= 15) };
+
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => {
+            let tagged;
+            unsafe {
+                core::arch::asm!(
+                    ".arch_extension memtag",
+                    "ADDG {0}, {1}, #{offset}, #{tag_offset}",
+                    out(reg) tagged,
+                    in(reg) ptr,
+                    offset = const OFFSET,
+                    tag_offset = const TAG_OFFSET,
+                    options(nomem, nostack, preserves_flags)
+                )
+            };
+            tagged
+        }
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+/// Subtract `OFFSET` bytes from `ptr` and advance its logical tag by `TAG_OFFSET` (SUBG)
+///
+/// The tag skips the tags excluded by `GCR_EL1`. `OFFSET` must be a multiple of [`GRANULE_SIZE`]
+/// up to 1008, and `TAG_OFFSET` at most 15.
+#[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+#[inline(always)]
+pub fn subg<const OFFSET: u16, const TAG_OFFSET: u8>(ptr: u64) -> u64 {
+    const { assert!(OFFSET.is_multiple_of(GRANULE_SIZE as u16) && OFFSET <= 1008 && TAG_OFFSET <= 15) };
+
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => {
+            let tagged;
+            unsafe {
+                core::arch::asm!(
+                    ".arch_extension memtag",
+                    "SUBG {0}, {1}, #{offset}, #{tag_offset}",
+                    out(reg) tagged,
+                    in(reg) ptr,
+                    offset = const OFFSET,
+                    tag_offset = const TAG_OFFSET,
+                    options(nomem, nostack, preserves_flags)
+                )
+            };
+            tagged
+        }
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+/// Replace the logical tag of `ptr` with the Allocation Tag of the granule it points to (LDG)
+///
+/// # Safety
+///
+/// `ptr` must point to mapped memory.
+#[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+#[inline(always)]
+pub unsafe fn ldg(ptr: u64) -> u64 {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => {
+            let mut tagged = ptr;
+            unsafe {
+                core::arch::asm!(
+                    ".arch_extension memtag",
+                    "LDG {0}, [{1}]",
+                    inout(reg) tagged,
+                    in(reg) ptr,
+                    options(readonly, nostack, preserves_flags)
+                )
+            };
+            tagged
+        }
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+/// Set the Allocation Tag of the granule `ptr` points to, to the logical tag of `ptr` (STG)
+///
+/// # Safety
+///
+/// `ptr` must be aligned to [`GRANULE_SIZE`] and point to mapped memory that no other pointer
+/// with a different tag is still used to access.
+#[inline(always)]
+pub unsafe fn stg(ptr: u64) {
+    store_tags(Op::Stg, ptr)
+}
+
+/// Set the Allocation Tags of the two granules `ptr` points to, to the logical tag of `ptr` (ST2G)
+///
+/// # Safety
+///
+/// `ptr` must be aligned to [`GRANULE_SIZE`] and point to mapped memory that no other pointer
+/// with a different tag is still used to access.
+#[inline(always)]
+pub unsafe fn st2g(ptr: u64) {
+    store_tags(Op::St2g, ptr)
+}
+
+/// Zero the granule `ptr` points to and set its Allocation Tag to the logical tag of `ptr` (STZG)
+///
+/// # Safety
+///
+/// `ptr` must be aligned to [`GRANULE_SIZE`] and point to mapped memory that no other pointer
+/// with a different tag is still used to access, and that may be overwritten.
+#[inline(always)]
+pub unsafe fn stzg(ptr: u64) {
+    store_tags(Op::Stzg, ptr)
+}
+
+/// Set the Allocation Tags of `len` bytes at `ptr` to the logical tag of `ptr`
+///
+/// # Safety
+///
+/// The range must be mapped memory that no other pointer with a different tag is still used to
+/// access.
+pub unsafe fn tag_range(ptr: u64, len: u64) -> Result<(), Error> {
+    store_range(Op::Stg, ptr, len)
+}
+
+/// Zero `len` bytes at `ptr` and set their Allocation Tags to the logical tag of `ptr`
+///
+/// # Safety
+///
+/// The range must be mapped memory that no other pointer with a different tag is still used to
+/// access, and that may be overwritten.
+pub unsafe fn zero_tag_range(ptr: u64, len: u64) -> Result<(), Error> {
+    store_range(Op::Stzg, ptr, len)
+}
+
+/// Give `len` bytes at `ptr` a new random tag that differs from the logical tag of `ptr`
+///
+/// Accesses through `ptr`, or any other pointer with its tag, then cause Tag Check Faults. Returns
+/// `ptr` with the new tag.
+///
+/// # Safety
+///
+/// The range must be mapped memory that no other pointer with a different tag is still used to
+/// access.
+pub unsafe fn retag_range(ptr: u64, len: u64) -> Result<u64, Error> {
+    let retagged = irg(ptr, 1 << tag(ptr));
+    unsafe { store_range(Op::Stg, retagged, len) }?;
+    Ok(retagged)
+}
+
+/// The tag store instructions
+#[derive(Copy, Clone)]
+enum Op {
+    Stg,
+    St2g,
+    Stzg,
+    Stz2g,
+}
+
+/// Tag a range, two granules per instruction where possible
+unsafe fn store_range(op: Op, ptr: u64, len: u64) -> Result<(), Error> {
+    if !ptr.is_multiple_of(GRANULE_SIZE) {
+        return Err(Error::Unaligned(ptr));
+    }
+    if !len.is_multiple_of(GRANULE_SIZE) {
+        return Err(Error::Unaligned(len));
+    }
+
+    let (single, pair) = match op {
+        Op::Stg | Op::St2g => (Op::Stg, Op::St2g),
+        Op::Stzg | Op::Stz2g => (Op::Stzg, Op::Stz2g),
+    };
+    // the range must not carry into the top byte, which holds the tag
+    if (ptr & ((1 << TAG_SHIFT) - 1))
+        .checked_add(len)
+        .is_none_or(|end| end > 1 << TAG_SHIFT)
+    {
+        return Err(Error::OutOfRange(ptr));
+    }
+
+    let mut addr = ptr;
+    let mut remaining = len;
+    while remaining >= 2 * GRANULE_SIZE {
+        store_tags(pair, addr);
+        addr = addr.wrapping_add(2 * GRANULE_SIZE);
+        remaining -= 2 * GRANULE_SIZE;
+    }
+    if remaining != 0 {
+        store_tags(single, addr);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+#[inline(always)]
+unsafe fn store_tags(op: Op, ptr: u64) {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => unsafe {
+            use core::arch::asm;
+
+            match op {
+                Op::Stg => asm!(
+                    ".arch_extension memtag",
+                    "STG {0}, [{0}]",
+                    in(reg) ptr,
+                    options(nostack, preserves_flags)
+                ),
+                Op::St2g => asm!(
+                    ".arch_extension memtag",
+                    "ST2G {0}, [{0}]",
+                    in(reg) ptr,
+                    options(nostack, preserves_flags)
+                ),
+                Op::Stzg => asm!(
+                    ".arch_extension memtag",
+                    "STZG {0}, [{0}]",
+                    in(reg) ptr,
+                    options(nostack, preserves_flags)
+                ),
+                Op::Stz2g => asm!(
+                    ".arch_extension memtag",
+                    "STZ2G {0}, [{0}]",
+                    in(reg) ptr,
+                    options(nostack, preserves_flags)
+                ),
+            }
+        },
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tags() {
+        let ptr = 0x0000_0000_8000_1230;
+        assert_eq!(tag(ptr), 0);
+        assert_eq!(with_tag(ptr, 0xa), 0x0a00_0000_8000_1230);
+        assert_eq!(tag(with_tag(ptr, 0xa)), 0xa);
+        assert_eq!(with_tag(0xff00_0000_0000_0000, 0), 0xf000_0000_0000_0000);
+
+        assert_eq!(
+            unsafe { tag_range(ptr + 8, 32) },
+            Err(Error::Unaligned(ptr + 8))
+        );
+        assert_eq!(
+            unsafe { zero_tag_range(ptr, 40) },
+            Err(Error::Unaligned(40))
+        );
+        assert_eq!(
+            unsafe { tag_range(0x0aff_ffff_ffff_fff0, 32) },
+            Err(Error::OutOfRange(0x0aff_ffff_ffff_fff0))
+        );
+    }
+}
@@ -0,0 +1,863 @@
+//! Translation tables for the VMSAv8-64 EL1&0 translation regime
+//!
+//! This module builds stage 1 tables, and [`stage2`] builds stage 2 tables for use at EL2.
+//!
+//! [`Tables`] builds a set of translation tables inside a caller-provided pool of memory. Mappings
+//! use the largest block size allowed by the alignment of the addresses, and set the Contiguous bit
+//! on runs of entries that are eligible for it. [`Layout::tcr_el1`] derives the matching `TCR_EL1`
+//! settings.
+//!
+//! The tables store the addresses of the next-level tables as seen by the code building them, so
+//! the pool must be identity mapped (or the MMU off) while [`Tables`] is in use. Output addresses
+//! are limited to 48 bits.
+//!
+//! Changing tables that are in use by the MMU requires break-before-make sequences and TLB
+//! maintenance (see [`crate::asm::tlbi`]), which is left to the caller.
+//!
+//! ```no_run
+//! use aarch64_cpu::{
+//!     asm::{barrier, tlbi::Granule},
+//!     paging::{AccessPermissions, Attributes, Layout, Tables},
+//!     registers::*,
+//! };
+//!
+//! #[repr(C, align(4096))]
+//! struct Pool([u64; 512 * 4]);
+//!
+//! static mut POOL: Pool = Pool([0; 512 * 4]);
+//!
+//! let layout = Layout::new(Granule::Size4KiB, 39).unwrap();
+//! // SAFETY: the pool is only used here, before any other core runs
+//! let mut tables =
+//!     Tables::new(layout, unsafe { &mut (*core::ptr::addr_of_mut!(POOL)).0 }).unwrap();
+//!
+//! // MAIR index 0: Device-nGnRE, MAIR index 1: Normal Write-Back
+//! let device = Attributes::new(0);
+//! let normal = Attributes::new(1).pxn(false);
+//! tables
+//!     .identity_map(0x0800_0000, 0x0100_0000, device)
+//!     .unwrap();
+//! tables
+//!     .identity_map(0x4000_0000, 0x4000_0000, normal)
+//!     .unwrap();
+//! let ro = normal.access(AccessPermissions::ReadOnlyEl1);
+//! tables
+//!     .map(0x1_0000_0000, 0x4000_0000, 0x20_0000, ro)
+//!     .unwrap();
+//!
+//! MAIR_EL1.write(
+//!     MAIR_EL1::Attr0_Device::nonGathering_nonReordering_EarlyWriteAck
+//!         + MAIR_EL1::Attr1_Normal_Outer::WriteBack_NonTransient_ReadWriteAlloc
+//!         + MAIR_EL1::Attr1_Normal_Inner::WriteBack_NonTransient_ReadWriteAlloc,
+//! );
+//! TTBR0_EL1.set_baddr(tables.root_address());
+//! TCR_EL1.write(layout.tcr_el1());
+//! barrier::isb(barrier::SY);
+//! SCTLR_EL1.modify(SCTLR_EL1::M::Enable + SCTLR_EL1::C::Cacheable + SCTLR_EL1::I::Cacheable);
+//! barrier::isb(barrier::SY);
+//! ```
+//!
+//! See chapter D8 "The AArch64 Virtual Memory System Architecture" in the [ARM Architecture
+//! Reference Manual v8][armv8].
+//!
+//! [armv8]: https://developer.arm.com/documentation/ddi0487/latest/
+
+use tock_registers::{
+    fields::FieldValue, interfaces::Readable, register_bitfields, LocalRegisterCopy,
+};
+
+use crate::{
+    asm::tlbi::Granule,
+    registers::{ID_AA64MMFR0_EL1, TCR_EL1},
+};
+
+pub mod stage2;
+
+register_bitfields! {u64,
+    /// Attributes of a stage 1 block or page descriptor
+    pub STAGE1_DESCRIPTOR [
+        /// Unprivileged execute-never
+        UXN OFFSET(54) NUMBITS(1) [],
+
+        /// Privileged execute-never
+        PXN OFFSET(53) NUMBITS(1) [],
+
+        /// The entry is one of a contiguous set of entries that can be cached in a single TLB
+        /// entry
+        Contiguous OFFSET(52) NUMBITS(1) [],
+
+        /// Not global; the translation is specific to the current ASID
+        NG OFFSET(11) NUMBITS(1) [],
+
+        /// Access flag
+        AF OFFSET(10) NUMBITS(1) [],
+
+        /// Shareability
+        SH OFFSET(8) NUMBITS(2) [
+            NonShareable = 0b00,
+            OuterShareable = 0b10,
+            InnerShareable = 0b11,
+        ],
+
+        /// Data access permissions
+        AP OFFSET(6) NUMBITS(2) [
+            ReadWriteEl1 = 0b00,
+            ReadWriteEl1El0 = 0b01,
+            ReadOnlyEl1 = 0b10,
+            ReadOnlyEl1El0 = 0b11,
+        ],
+
+        /// Non-secure; the output address is in the Non-secure address space. Only used for
+        /// accesses from Secure state.
+        NS OFFSET(5) NUMBITS(1) [],
+
+        /// Index of the memory attributes in `MAIR_ELx`
+        AttrIndx OFFSET(2) NUMBITS(3) []
+    ]
+}
+
+#[doc(inline)]
+pub use STAGE1_DESCRIPTOR::{AP::Value as AccessPermissions, SH::Value as Shareability};
+
+/// Bits holding the upper and lower attributes of a block or page descriptor
+const ATTRIBUTES_MASK: u64 = 0xfffc_0000_0000_0ffc;
+
+/// The Contiguous bit, shared by the stage 1 and stage 2 formats
+const CONTIGUOUS: u64 = 1 << 52;
+
+/// Ways this API can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input address size is not supported with the chosen granule
+    InvalidLayout,
+    /// The pool is not aligned to the granule size, or is not a whole number of tables
+    InvalidPool,
+    /// The pool has no free tables left
+    PoolExhausted,
+    /// Found an address or size that is not a multiple of the granule size
+    Unaligned(u64),
+    /// Found an address outside of the input or output address range
+    OutOfRange(u64),
+    /// The given input address is already mapped
+    AlreadyMapped(u64),
+}
+
+/// Attributes of a block or page mapping
+///
+/// [`Attributes::new`] starts from a global, accessed, Inner Shareable, read/write at EL1 only
+/// and execute-never mapping.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Attributes(u64);
+
+impl Attributes {
+    /// Attributes using the memory type at index `attr_index` (`0..=7`) of `MAIR_EL1`
+    pub fn new(attr_index: u8) -> Attributes {
+        Attributes(
+            (STAGE1_DESCRIPTOR::AttrIndx.val(attr_index as u64 & 0b111)
+                + STAGE1_DESCRIPTOR::AF::SET
+                + STAGE1_DESCRIPTOR::SH::InnerShareable
+                + STAGE1_DESCRIPTOR::AP::ReadWriteEl1
+                + STAGE1_DESCRIPTOR::UXN::SET
+                + STAGE1_DESCRIPTOR::PXN::SET)
+                .value,
+        )
+    }
+
+    /// Attributes from the raw bits of a descriptor. Address and type bits are ignored.
+    pub const fn from_bits(bits: u64) -> Attributes {
+        Attributes(bits & ATTRIBUTES_MASK)
+    }
+
+    /// The raw descriptor bits
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Index of the memory type in `MAIR_EL1`
+    pub fn attr_index(self) -> u8 {
+        self.register().read(STAGE1_DESCRIPTOR::AttrIndx) as u8
+    }
+
+    /// Data access permissions
+    pub fn get_access(self) -> AccessPermissions {
+        match self.register().read_as_enum(STAGE1_DESCRIPTOR::AP) {
+            Some(ap) => ap,
+            None => unreachable!(),
+        }
+    }
+
+    /// Set the data access permissions
+    pub fn access(self, access: AccessPermissions) -> Attributes {
+        self.with(STAGE1_DESCRIPTOR::AP.val(access as u64))
+    }
+
+    /// Set the shareability
+    pub fn shareability(self, shareability: Shareability) -> Attributes {
+        self.with(STAGE1_DESCRIPTOR::SH.val(shareability as u64))
+    }
+
+    /// Set or clear the unprivileged execute-never bit
+    pub fn uxn(self, uxn: bool) -> Attributes {
+        self.with(STAGE1_DESCRIPTOR::UXN.val(uxn as u64))
+    }
+
+    /// Set or clear the privileged execute-never bit
+    pub fn pxn(self, pxn: bool) -> Attributes {
+        self.with(STAGE1_DESCRIPTOR::PXN.val(pxn as u64))
+    }
+
+    /// Set or clear the not-global bit
+    pub fn not_global(self, ng: bool) -> Attributes {
+        self.with(STAGE1_DESCRIPTOR::NG.val(ng as u64))
+    }
+
+    /// Set or clear the access flag. Accessing a mapping with the flag clear faults, unless the
+    /// hardware is set up to manage the flag (`TCR_EL1.HA`).
+    pub fn access_flag(self, af: bool) -> Attributes {
+        self.with(STAGE1_DESCRIPTOR::AF.val(af as u64))
+    }
+
+    /// Set or clear the non-secure bit
+    pub fn non_secure(self, ns: bool) -> Attributes {
+        self.with(STAGE1_DESCRIPTOR::NS.val(ns as u64))
+    }
+
+    /// Set or clear the Contiguous bit
+    ///
+    /// [`Tables::map`] manages this bit itself and ignores it in the attributes it is given.
+    pub fn contiguous(self, contiguous: bool) -> Attributes {
+        self.with(STAGE1_DESCRIPTOR::Contiguous.val(contiguous as u64))
+    }
+
+    /// Whether the Contiguous bit is set
+    pub fn is_contiguous(self) -> bool {
+        self.0 & CONTIGUOUS != 0
+    }
+
+    fn register(self) -> LocalRegisterCopy<u64, STAGE1_DESCRIPTOR::Register> {
+        LocalRegisterCopy::new(self.0)
+    }
+
+    fn with(self, field: FieldValue<u64, STAGE1_DESCRIPTOR::Register>) -> Attributes {
+        let mut reg = self.register();
+        reg.modify(field);
+        Attributes(reg.get())
+    }
+}
+
+/// A decoded translation table entry
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Descriptor {
+    /// The entry is invalid and faults on access
+    Invalid,
+    /// The entry points at a next-level table
+    Table {
+        /// Address of the next-level table
+        address: u64,
+    },
+    /// The entry maps a block larger than the granule (levels 0 to 2)
+    Block {
+        /// The output address of the block
+        address: u64,
+        /// The block attributes
+        attributes: Attributes,
+    },
+    /// The entry maps a single granule (level 3)
+    Page {
+        /// The output address of the page
+        address: u64,
+        /// The page attributes
+        attributes: Attributes,
+    },
+}
+
+impl Descriptor {
+    /// Decode a stage 1 entry found at lookup `level` of tables using `granule`
+    pub fn decode(raw: u64, granule: Granule, level: u8) -> Descriptor {
+        let address = raw & output_address_mask(granule);
+        match (raw & 0b11, level) {
+            (0b11, 3) => Descriptor::Page {
+                address,
+                attributes: Attributes::from_bits(raw),
+            },
+            (0b11, _) => Descriptor::Table { address },
+            (0b01, 0..=2) => Descriptor::Block {
+                address,
+                attributes: Attributes::from_bits(raw),
+            },
+            _ => Descriptor::Invalid,
+        }
+    }
+
+    /// Encode the entry for tables using `granule`
+    pub fn encode(self, granule: Granule) -> u64 {
+        let mask = output_address_mask(granule);
+        match self {
+            Descriptor::Invalid => 0,
+            Descriptor::Table { address } => (address & mask) | 0b11,
+            Descriptor::Block {
+                address,
+                attributes,
+            } => (address & mask) | attributes.bits() | 0b01,
+            Descriptor::Page {
+                address,
+                attributes,
+            } => (address & mask) | attributes.bits() | 0b11,
+        }
+    }
+}
+
+/// Bits [47:shift] of a descriptor, holding the output or next-level table address
+const fn output_address_mask(granule: Granule) -> u64 {
+    ((1 << 48) - 1) & !(granule.size() as u64 - 1)
+}
+
+/// Number of input address bits resolved by a lookup at `level` and all the levels after it
+const fn level_shift(granule: Granule, level: u8) -> u32 {
+    granule.shift() + (3 - level as u32) * (granule.shift() - 3)
+}
+
+/// Number of entries in one table
+const fn entries_per_table(granule: Granule) -> usize {
+    granule.size() / 8
+}
+
+/// Number of entries in a contiguous run at `level`
+const fn contiguous_entries(granule: Granule, level: u8) -> usize {
+    match (granule, level) {
+        (Granule::Size4KiB, _) => 16,
+        (Granule::Size16KiB, 3) => 128,
+        (Granule::Size16KiB, _) => 32,
+        (Granule::Size64KiB, _) => 32,
+    }
+}
+
+/// The first level at which blocks can be used with 48-bit output addresses
+const fn first_block_level(granule: Granule) -> u8 {
+    match granule {
+        Granule::Size4KiB => 1,
+        Granule::Size16KiB | Granule::Size64KiB => 2,
+    }
+}
+
+/// The layout of a stage 1 address space, mapped through `TTBR0_EL1`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Layout {
+    granule: Granule,
+    va_bits: u8,
+}
+
+impl Layout {
+    /// A layout translating `va_bits` (`25..=48`) bits of input address with `granule` pages
+    pub const fn new(granule: Granule, va_bits: u8) -> Result<Layout, Error> {
+        if va_bits < 25 || va_bits > 48 {
+            return Err(Error::InvalidLayout);
+        }
+        Ok(Layout { granule, va_bits })
+    }
+
+    /// The translation granule
+    pub const fn granule(&self) -> Granule {
+        self.granule
+    }
+
+    /// Number of input address bits
+    pub const fn va_bits(&self) -> u8 {
+        self.va_bits
+    }
+
+    /// The lookup level of the root table
+    pub const fn start_level(&self) -> u8 {
+        let resolved = self.va_bits as u32 - self.granule.shift();
+        let per_level = self.granule.shift() - 3;
+        (4 - resolved.div_ceil(per_level)) as u8
+    }
+
+    /// Number of entries in the root table
+    pub const fn root_entries(&self) -> usize {
+        1 << (self.va_bits as u32 - level_shift(self.granule, self.start_level()))
+    }
+
+    /// `TCR_EL1` settings for this layout, with the output address size taken from
+    /// `ID_AA64MMFR0_EL1.PARange`
+    ///
+    /// See [`Layout::tcr_el1_for_parange`].
+    pub fn tcr_el1(&self) -> FieldValue<u64, TCR_EL1::Register> {
+        self.tcr_el1_for_parange(ID_AA64MMFR0_EL1.read(ID_AA64MMFR0_EL1::PARange))
+    }
+
+    /// `TCR_EL1` settings for this layout, for a PE reporting `parange` in
+    /// `ID_AA64MMFR0_EL1.PARange`
+    ///
+    /// Table walks through `TTBR0_EL1` are Inner Shareable and Write-Back cacheable, and walks
+    /// through `TTBR1_EL1` are disabled. The output address size is capped at 48 bits.
+    pub fn tcr_el1_for_parange(&self, parange: u64) -> FieldValue<u64, TCR_EL1::Register> {
+        let (tg0, tg1) = match self.granule {
+            Granule::Size4KiB => (TCR_EL1::TG0::KiB_4, TCR_EL1::TG1::KiB_4),
+            Granule::Size16KiB => (TCR_EL1::TG0::KiB_16, TCR_EL1::TG1::KiB_16),
+            Granule::Size64KiB => (TCR_EL1::TG0::KiB_64, TCR_EL1::TG1::KiB_64),
+        };
+        // PARange and IPS share their encodings up to 0b101, 48 bits
+        let ips = TCR_EL1::IPS.val(parange.min(0b101));
+
+        TCR_EL1::T0SZ.val(64 - self.va_bits as u64)
+            + tg0
+            + TCR_EL1::SH0::Inner
+            + TCR_EL1::IRGN0::WriteBack_ReadAlloc_WriteAlloc_Cacheable
+            + TCR_EL1::ORGN0::WriteBack_ReadAlloc_WriteAlloc_Cacheable
+            + TCR_EL1::EPD0::EnableTTBR0Walks
+            + tg1
+            + TCR_EL1::EPD1::DisableTTBR1Walks
+            + ips
+    }
+}
+
+/// Memory for translation tables, handed out one table at a time
+struct Pool<'a> {
+    memory: &'a mut [u64],
+    granule: Granule,
+    used: usize,
+}
+
+impl<'a> Pool<'a> {
+    fn new(memory: &'a mut [u64], granule: Granule) -> Result<Pool<'a>, Error> {
+        if !(memory.as_ptr() as usize).is_multiple_of(granule.size())
+            || !memory.len().is_multiple_of(entries_per_table(granule))
+        {
+            return Err(Error::InvalidPool);
+        }
+        Ok(Pool {
+            memory,
+            granule,
+            used: 0,
+        })
+    }
+
+    /// Allocate `count` consecutive zeroed tables, returning the index of the first one
+    fn alloc(&mut self, count: usize) -> Result<usize, Error> {
+        let entries = entries_per_table(self.granule);
+        if (self.used + count) * entries > self.memory.len() {
+            return Err(Error::PoolExhausted);
+        }
+        let first = self.used;
+        self.used += count;
+        self.memory[first * entries..self.used * entries].fill(0);
+        Ok(first)
+    }
+
+    /// The address of the table at `index`
+    fn address(&self, index: usize) -> u64 {
+        self.memory.as_ptr() as u64 + (index * self.granule.size()) as u64
+    }
+
+    /// The index of the table at `address`, if it belongs to this pool
+    fn index_of(&self, address: u64) -> Option<usize> {
+        let offset = address.checked_sub(self.memory.as_ptr() as u64)? as usize;
+        let index = offset / self.granule.size();
+        (index < self.used).then_some(index)
+    }
+
+    /// Entry `entry` of the table at `index`. Concatenated tables can be indexed as one.
+    fn entry(&self, index: usize, entry: usize) -> u64 {
+        self.memory[index * entries_per_table(self.granule) + entry]
+    }
+
+    fn set_entry(&mut self, index: usize, entry: usize, value: u64) {
+        self.memory[index * entries_per_table(self.granule) + entry] = value;
+    }
+}
+
+/// Translation tables built in a [`Pool`], shared by the stage 1 and stage 2 formats
+struct Walker<'a> {
+    pool: Pool<'a>,
+    granule: Granule,
+    input_bits: u8,
+    output_bits: u8,
+    start_level: u8,
+    root: usize,
+}
+
+impl<'a> Walker<'a> {
+    fn new(
+        memory: &'a mut [u64],
+        granule: Granule,
+        input_bits: u8,
+        output_bits: u8,
+        start_level: u8,
+        root_tables: usize,
+    ) -> Result<Walker<'a>, Error> {
+        // concatenated root tables are aligned to their combined size
+        if !(memory.as_ptr() as usize).is_multiple_of(root_tables * granule.size()) {
+            return Err(Error::InvalidPool);
+        }
+        let mut pool = Pool::new(memory, granule)?;
+        let root = pool.alloc(root_tables)?;
+        Ok(Walker {
+            pool,
+            granule,
+            input_bits,
+            output_bits,
+            start_level,
+            root,
+        })
+    }
+
+    fn index(&self, ia: u64, level: u8) -> usize {
+        let shift = level_shift(self.granule, level);
+        let bits = if level == self.start_level {
+            self.input_bits as u32 - shift
+        } else {
+            self.granule.shift() - 3
+        };
+        ((ia >> shift) & ((1 << bits) - 1)) as usize
+    }
+
+    /// Map `[ia, ia + size)` to `[oa, oa + size)` with the leaf `attributes` bits
+    fn map(&mut self, ia: u64, oa: u64, size: u64, attributes: u64) -> Result<(), Error> {
+        let granule = self.granule.size() as u64 - 1;
+        for value in [ia, oa, size] {
+            if value & granule != 0 {
+                return Err(Error::Unaligned(value));
+            }
+        }
+        if ia
+            .checked_add(size)
+            .is_none_or(|end| end > 1 << self.input_bits)
+        {
+            return Err(Error::OutOfRange(ia));
+        }
+        if oa
+            .checked_add(size)
+            .is_none_or(|end| end > 1 << self.output_bits)
+        {
+            return Err(Error::OutOfRange(oa));
+        }
+
+        let attributes = attributes & !CONTIGUOUS;
+        let first_level = first_block_level(self.granule).max(self.start_level);
+        let mut offset = 0;
+        while offset < size {
+            let (ia, oa, remaining) = (ia + offset, oa + offset, size - offset);
+            let level = (first_level..3)
+                .find(|&level| {
+                    let block = 1 << level_shift(self.granule, level);
+                    (ia | oa) & (block - 1) == 0 && remaining >= block
+                })
+                .unwrap_or(3);
+            let block = 1u64 << level_shift(self.granule, level);
+
+            let run = contiguous_entries(self.granule, level) as u64;
+            let (count, attributes) =
+                if (ia | oa) & (run * block - 1) == 0 && remaining >= run * block {
+                    (run, attributes | CONTIGUOUS)
+                } else {
+                    (1, attributes)
+                };
+
+            // a partially written contiguous run is CONSTRAINED UNPREDICTABLE, so check the
+            // whole run before writing any of it
+            let table = self.leaf_table(ia, level)?;
+            let first = self.index(ia, level);
+            if let Some(i) =
+                (0..count).find(|&i| self.pool.entry(table, first + i as usize) & 0b1 != 0)
+            {
+                return Err(Error::AlreadyMapped(ia + i * block));
+            }
+
+            let leaf_type = if level == 3 { 0b11 } else { 0b01 };
+            for i in 0..count {
+                let descriptor =
+                    ((oa + i * block) & output_address_mask(self.granule)) | attributes | leaf_type;
+                self.pool.set_entry(table, first + i as usize, descriptor);
+            }
+            offset += count * block;
+        }
+        Ok(())
+    }
+
+    /// Find the table holding the entries for `ia` at `level`, creating intermediate tables as
+    /// needed
+    fn leaf_table(&mut self, ia: u64, level: u8) -> Result<usize, Error> {
+        let mut table = self.root;
+        for current in self.start_level..level {
+            let index = self.index(ia, current);
+            let entry = self.pool.entry(table, index);
+            table = match entry & 0b11 {
+                0b11 => self
+                    .pool
+                    .index_of(entry & output_address_mask(self.granule))
+                    .ok_or(Error::AlreadyMapped(ia))?,
+                0b01 => return Err(Error::AlreadyMapped(ia)),
+                _ => {
+                    let next = self.pool.alloc(1)?;
+                    let address = self.pool.address(next);
+                    let descriptor = Descriptor::Table { address }.encode(self.granule);
+                    self.pool.set_entry(table, index, descriptor);
+                    next
+                }
+            };
+        }
+        Ok(table)
+    }
+
+    /// Find the raw leaf descriptor mapping `ia`, and its level
+    fn lookup(&self, ia: u64) -> Option<(u64, u8)> {
+        if ia >= 1 << self.input_bits {
+            return None;
+        }
+        let mut table = self.root;
+        for level in self.start_level..=3 {
+            let entry = self.pool.entry(table, self.index(ia, level));
+            match (entry & 0b11, level) {
+                (0b11, 0..=2) => {
+                    table = self
+                        .pool
+                        .index_of(entry & output_address_mask(self.granule))?
+                }
+                (0b01, 0..=2) | (0b11, 3) => return Some((entry, level)),
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    /// Translate `ia` through the tables, returning the output address and the leaf descriptor
+    fn translate(&self, ia: u64) -> Option<(u64, u64)> {
+        let (entry, level) = self.lookup(ia)?;
+        let offset = ia & offset_mask(self.granule, level);
+        let base = entry & output_address_mask(self.granule) & !offset_mask(self.granule, level);
+        Some((base | offset, entry))
+    }
+}
+
+/// Address bits below the block size at `level`
+const fn offset_mask(granule: Granule, level: u8) -> u64 {
+    (1 << level_shift(granule, level)) - 1
+}
+
+/// A set of stage 1 translation tables
+pub struct Tables<'a> {
+    layout: Layout,
+    walker: Walker<'a>,
+}
+
+impl<'a> Tables<'a> {
+    /// Create empty tables for `layout`, using `pool` as storage for the tables
+    ///
+    /// The pool must be aligned to the granule size and hold a whole number of tables of
+    /// `granule size / 8` entries. The first table becomes the root table.
+    pub fn new(layout: Layout, pool: &'a mut [u64]) -> Result<Tables<'a>, Error> {
+        Ok(Tables {
+            layout,
+            walker: Walker::new(
+                pool,
+                layout.granule,
+                layout.va_bits,
+                48,
+                layout.start_level(),
+                1,
+            )?,
+        })
+    }
+
+    /// The layout of the tables
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Address of the root table, for `TTBR0_EL1`
+    pub fn root_address(&self) -> u64 {
+        self.walker.pool.address(self.walker.root)
+    }
+
+    /// Number of tables taken from the pool so far
+    pub fn tables_used(&self) -> usize {
+        self.walker.pool.used
+    }
+
+    /// Map `size` bytes at `va` to `pa`
+    ///
+    /// Addresses and size must be multiples of the granule size. Blocks are used wherever `va`
+    /// and `pa` are suitably aligned, and the Contiguous bit is set on aligned runs of entries.
+    ///
+    /// On error, the part of the range before the failing address may already be mapped.
+    pub fn map(
+        &mut self,
+        va: u64,
+        pa: u64,
+        size: u64,
+        attributes: Attributes,
+    ) -> Result<(), Error> {
+        self.walker.map(va, pa, size, attributes.bits())
+    }
+
+    /// Map `size` bytes at `pa` to the same virtual address
+    pub fn identity_map(
+        &mut self,
+        pa: u64,
+        size: u64,
+        attributes: Attributes,
+    ) -> Result<(), Error> {
+        self.map(pa, pa, size, attributes)
+    }
+
+    /// Translate `va` through the tables, returning the physical address and attributes
+    pub fn translate(&self, va: u64) -> Option<(u64, Attributes)> {
+        self.walker
+            .translate(va)
+            .map(|(pa, entry)| (pa, Attributes::from_bits(entry)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[repr(C, align(65536))]
+    struct Memory([u64; 8192 * 4]);
+
+    #[test]
+    fn layout_levels() {
+        let start = |granule, bits| Layout::new(granule, bits).unwrap().start_level();
+        assert_eq!(start(Granule::Size4KiB, 48), 0);
+        assert_eq!(start(Granule::Size4KiB, 39), 1);
+        assert_eq!(start(Granule::Size4KiB, 30), 2);
+        assert_eq!(start(Granule::Size16KiB, 48), 0);
+        assert_eq!(start(Granule::Size16KiB, 47), 1);
+        assert_eq!(start(Granule::Size64KiB, 48), 1);
+        assert_eq!(start(Granule::Size64KiB, 42), 2);
+        assert_eq!(
+            Layout::new(Granule::Size16KiB, 48).unwrap().root_entries(),
+            2
+        );
+        assert_eq!(
+            Layout::new(Granule::Size4KiB, 24),
+            Err(Error::InvalidLayout)
+        );
+    }
+
+    #[test]
+    fn tcr() {
+        let layout = Layout::new(Granule::Size64KiB, 42).unwrap();
+        let tcr = layout.tcr_el1_for_parange(0b0110);
+        let reg = LocalRegisterCopy::<u64, TCR_EL1::Register>::new(tcr.value);
+        assert_eq!(reg.read(TCR_EL1::T0SZ), 22);
+        assert_eq!(reg.read(TCR_EL1::TG0), TCR_EL1::TG0::KiB_64.value >> 14);
+        assert_eq!(reg.read(TCR_EL1::IPS), 0b101);
+        assert!(reg.is_set(TCR_EL1::EPD1));
+        let reg = LocalRegisterCopy::<u64, TCR_EL1::Register>::new(
+            layout.tcr_el1_for_parange(0b0010).value,
+        );
+        assert_eq!(reg.read(TCR_EL1::IPS), 0b010);
+    }
+
+    #[test]
+    fn descriptors() {
+        let attributes = Attributes::new(1)
+            .access(AccessPermissions::ReadOnlyEl1El0)
+            .uxn(false);
+        let block = Descriptor::Block {
+            address: 0x4020_0000,
+            attributes,
+        };
+        let raw = block.encode(Granule::Size4KiB);
+        assert_eq!(raw, 0x0020_0000_4020_07c5);
+        assert_eq!(Descriptor::decode(raw, Granule::Size4KiB, 2), block);
+        assert_eq!(
+            Descriptor::decode(raw, Granule::Size4KiB, 3),
+            Descriptor::Invalid
+        );
+        assert_eq!(attributes.attr_index(), 1);
+        assert_eq!(attributes.get_access(), AccessPermissions::ReadOnlyEl1El0);
+    }
+
+    #[test]
+    fn map_4k() {
+        let mut memory = Memory([0; 8192 * 4]);
+        let layout = Layout::new(Granule::Size4KiB, 39).unwrap();
+        let mut tables = Tables::new(layout, &mut memory.0).unwrap();
+        let attributes = Attributes::new(0);
+
+        // one 1GiB block, then 2MiB blocks, then pages
+        tables
+            .identity_map(0x4000_0000, 0x4000_0000 + 0x20_0000 + 0x3000, attributes)
+            .unwrap();
+        assert_eq!(tables.tables_used(), 3);
+        assert_eq!(
+            tables.translate(0x4123_4567),
+            Some((0x4123_4567, attributes))
+        );
+        assert_eq!(
+            tables.translate(0x8020_2abc),
+            Some((0x8020_2abc, attributes))
+        );
+        assert_eq!(tables.translate(0x8020_3000), None);
+
+        // 16 pages are eligible for a contiguous run
+        tables
+            .map(0x10_0000, 0x9000_0000, 0x11000, attributes)
+            .unwrap();
+        assert_eq!(
+            tables.translate(0x10_f123),
+            Some((0x9000_f123, attributes.contiguous(true)))
+        );
+        assert_eq!(tables.translate(0x11_0000), Some((0x9001_0000, attributes)));
+
+        assert_eq!(
+            tables.map(0x10_2000, 0, 0x1000, attributes),
+            Err(Error::AlreadyMapped(0x10_2000))
+        );
+        assert_eq!(
+            tables.map(0x4020_0000, 0, 0x1000, attributes),
+            Err(Error::AlreadyMapped(0x4020_0000))
+        );
+
+        // a contiguous run over a partially mapped range must leave the table unchanged
+        tables
+            .map(0x20_5000, 0xb000_0000, 0x1000, attributes)
+            .unwrap();
+        assert_eq!(
+            tables.map(0x20_0000, 0xa000_0000, 0x10000, attributes),
+            Err(Error::AlreadyMapped(0x20_5000))
+        );
+        for page in (0x20_0000..0x21_0000).step_by(0x1000) {
+            let expected = (page == 0x20_5000).then_some((0xb000_0000, attributes));
+            assert_eq!(tables.translate(page), expected);
+        }
+        assert_eq!(
+            tables.map(0x80_0000_0000, 0, 0x1000, attributes),
+            Err(Error::OutOfRange(0x80_0000_0000))
+        );
+        assert_eq!(
+            tables.map(0x123, 0, 0x1000, attributes),
+            Err(Error::Unaligned(0x123))
+        );
+    }
+
+    #[test]
+    fn map_64k() {
+        let mut memory = Memory([0; 8192 * 4]);
+        let layout = Layout::new(Granule::Size64KiB, 42).unwrap();
+        let mut tables = Tables::new(layout, &mut memory.0[..8192 * 2]).unwrap();
+        let attributes = Attributes::new(2);
+
+        // a 512MiB block at level 2, then pages from a single new level 3 table
+        tables
+            .identity_map(0x2000_0000, 0x2000_0000 + 0x2_0000, attributes)
+            .unwrap();
+        assert_eq!(tables.tables_used(), 2);
+        assert_eq!(
+            tables.translate(0x3fff_fffc),
+            Some((0x3fff_fffc, attributes))
+        );
+        assert_eq!(
+            tables.map(0x1_0000_0000, 0, 0x1_0000, attributes),
+            Err(Error::PoolExhausted)
+        );
+
+        assert_eq!(
+            Tables::new(layout, &mut memory.0[1..8193]).err(),
+            Some(Error::InvalidPool)
+        );
+    }
+}
@@ -0,0 +1,525 @@
+//! Stage 2 translation tables for the EL1&0 translation regime
+//!
+//! [`Tables`] maps intermediate physical addresses (IPAs) of a guest, tagged with its [`Vmid`], to
+//! physical addresses. [`Layout::new`] picks the initial lookup level and the number of
+//! concatenated root tables for the requested IPA size, and [`Tables::vtcr_el2`] and
+//! [`Tables::vttbr_el2`] produce matching `VTCR_EL2` and `VTTBR_EL2` values.
+//!
+//! As for stage 1, the pool must be identity mapped while the tables are built, and changing
+//! tables in use requires TLB maintenance (see [`crate::asm::tlbi::vmalls12e1_vmid`]).
+//!
+//! ```no_run
+//! use aarch64_cpu::{
+//!     asm::{
+//!         barrier,
+//!         tlbi::{Granule, Vmid},
+//!     },
+//!     paging::stage2::{AccessPermissions, Attributes, Layout, MemoryAttributes, Tables},
+//!     registers::*,
+//! };
+//!
+//! // 40-bit IPAs with 4KiB pages start at level 1 with two concatenated tables
+//! #[repr(C, align(8192))]
+//! struct Pool([u64; 512 * 8]);
+//!
+//! static mut POOL: Pool = Pool([0; 512 * 8]);
+//!
+//! let layout = Layout::new(Granule::Size4KiB, 40).unwrap();
+//! // SAFETY: the pool is only used here, before the guest runs
+//! let pool = unsafe { &mut (*core::ptr::addr_of_mut!(POOL)).0 };
+//! let mut tables = Tables::new(layout, Vmid::new(1), pool).unwrap();
+//!
+//! let ram = Attributes::new(MemoryAttributes::NormalWriteBack).executable(true);
+//! let uart = Attributes::new(MemoryAttributes::DeviceNGnRE);
+//! tables.identity_map(0x4000_0000, 0x1000_0000, ram).unwrap();
+//! tables.map(0x0900_0000, 0x1c09_0000, 0x1000, uart).unwrap();
+//! let rom = ram.access(AccessPermissions::ReadOnly);
+//! tables.map(0x0, 0x8000_0000, 0x20_0000, rom).unwrap();
+//!
+//! VTCR_EL2.write(tables.vtcr_el2());
+//! VTTBR_EL2.write(tables.vttbr_el2());
+//! barrier::isb(barrier::SY);
+//! HCR_EL2.modify(HCR_EL2::VM::Enable);
+//! barrier::isb(barrier::SY);
+//! ```
+//!
+//! See section D8.2 "Translation table walk" and D8.5 "Memory region attributes" in the [ARM
+//! Architecture Reference Manual v8][armv8].
+//!
+//! [armv8]: https://developer.arm.com/documentation/ddi0487/latest/
+
+use tock_registers::{
+    fields::FieldValue, interfaces::Readable, register_bitfields, LocalRegisterCopy,
+};
+
+use super::{entries_per_table, level_shift, Error, Walker, CONTIGUOUS};
+use crate::{
+    asm::tlbi::{Granule, Vmid},
+    registers::{ID_AA64MMFR0_EL1, VTCR_EL2, VTTBR_EL2},
+};
+
+register_bitfields! {u64,
+    /// Attributes of a stage 2 block or page descriptor
+    pub STAGE2_DESCRIPTOR [
+        /// Execute-never controls. Without FEAT_XNX only the upper bit is used, and any non-zero
+        /// value means execute-never at EL1 and EL0.
+        XN OFFSET(53) NUMBITS(2) [
+            ExecuteEl1El0 = 0b00,
+            ExecuteEl0 = 0b01,
+            ExecuteNever = 0b10,
+            ExecuteEl1 = 0b11,
+        ],
+
+        /// The entry is one of a contiguous set of entries that can be cached in a single TLB
+        /// entry
+        Contiguous OFFSET(52) NUMBITS(1) [],
+
+        /// Accesses using the mapping have the XS attribute cleared (FEAT_XS)
+        FnXS OFFSET(11) NUMBITS(1) [],
+
+        /// Access flag
+        AF OFFSET(10) NUMBITS(1) [],
+
+        /// Shareability
+        SH OFFSET(8) NUMBITS(2) [
+            NonShareable = 0b00,
+            OuterShareable = 0b10,
+            InnerShareable = 0b11,
+        ],
+
+        /// Stage 2 data access permissions
+        S2AP OFFSET(6) NUMBITS(2) [
+            NoAccess = 0b00,
+            ReadOnly = 0b01,
+            WriteOnly = 0b10,
+            ReadWrite = 0b11,
+        ],
+
+        /// Stage 2 memory attributes, with `HCR_EL2.FWB` clear
+        ///
+        /// Bits [3:2] hold the outer attributes of Normal memory, or `0b00` for Device memory.
+        /// Bits [1:0] hold the inner attributes of Normal memory, or the Device memory type.
+        MemAttr OFFSET(2) NUMBITS(4) [
+            DeviceNGnRnE = 0b0000,
+            DeviceNGnRE = 0b0001,
+            DeviceNGRE = 0b0010,
+            DeviceGRE = 0b0011,
+            NormalNonCacheable = 0b0101,
+            NormalWriteThrough = 0b1010,
+            NormalWriteBack = 0b1111,
+        ]
+    ]
+}
+
+#[doc(inline)]
+pub use STAGE2_DESCRIPTOR::{
+    MemAttr::Value as MemoryAttributes, S2AP::Value as AccessPermissions,
+    SH::Value as Shareability, XN::Value as ExecutePermissions,
+};
+
+/// Attributes of a stage 2 block or page mapping
+///
+/// [`Attributes::new`] starts from an accessed, Inner Shareable, read/write and execute-never
+/// mapping.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Attributes(u64);
+
+impl Attributes {
+    /// Attributes using the stage 2 memory type `memory`
+    pub fn new(memory: MemoryAttributes) -> Attributes {
+        Attributes(
+            (STAGE2_DESCRIPTOR::MemAttr.val(memory as u64)
+                + STAGE2_DESCRIPTOR::AF::SET
+                + STAGE2_DESCRIPTOR::SH::InnerShareable
+                + STAGE2_DESCRIPTOR::S2AP::ReadWrite
+                + STAGE2_DESCRIPTOR::XN::ExecuteNever)
+                .value,
+        )
+    }
+
+    /// Attributes from the raw bits of a descriptor. Address and type bits are ignored.
+    pub const fn from_bits(bits: u64) -> Attributes {
+        Attributes(bits & super::ATTRIBUTES_MASK)
+    }
+
+    /// The raw descriptor bits
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// The raw `MemAttr` value
+    pub fn memory_attributes(self) -> u8 {
+        self.register().read(STAGE2_DESCRIPTOR::MemAttr) as u8
+    }
+
+    /// Stage 2 data access permissions
+    pub fn get_access(self) -> AccessPermissions {
+        match self.register().read_as_enum(STAGE2_DESCRIPTOR::S2AP) {
+            Some(ap) => ap,
+            None => unreachable!(),
+        }
+    }
+
+    /// Set the stage 2 data access permissions
+    pub fn access(self, access: AccessPermissions) -> Attributes {
+        self.with(STAGE2_DESCRIPTOR::S2AP.val(access as u64))
+    }
+
+    /// Set the shareability
+    pub fn shareability(self, shareability: Shareability) -> Attributes {
+        self.with(STAGE2_DESCRIPTOR::SH.val(shareability as u64))
+    }
+
+    /// Allow or forbid execution at both EL1 and EL0
+    pub fn executable(self, executable: bool) -> Attributes {
+        self.execute(if executable {
+            ExecutePermissions::ExecuteEl1El0
+        } else {
+            ExecutePermissions::ExecuteNever
+        })
+    }
+
+    /// Set the execute-never controls. The EL-specific encodings need FEAT_XNX.
+    pub fn execute(self, xn: ExecutePermissions) -> Attributes {
+        self.with(STAGE2_DESCRIPTOR::XN.val(xn as u64))
+    }
+
+    /// Set or clear the FnXS bit
+    pub fn fnxs(self, fnxs: bool) -> Attributes {
+        self.with(STAGE2_DESCRIPTOR::FnXS.val(fnxs as u64))
+    }
+
+    /// Set or clear the access flag
+    pub fn access_flag(self, af: bool) -> Attributes {
+        self.with(STAGE2_DESCRIPTOR::AF.val(af as u64))
+    }
+
+    /// Whether the Contiguous bit is set. [`Tables::map`] manages this bit itself.
+    pub fn is_contiguous(self) -> bool {
+        self.0 & CONTIGUOUS != 0
+    }
+
+    fn register(self) -> LocalRegisterCopy<u64, STAGE2_DESCRIPTOR::Register> {
+        LocalRegisterCopy::new(self.0)
+    }
+
+    fn with(self, field: FieldValue<u64, STAGE2_DESCRIPTOR::Register>) -> Attributes {
+        let mut reg = self.register();
+        reg.modify(field);
+        Attributes(reg.get())
+    }
+}
+
+/// Number of output address bits for an `ID_AA64MMFR0_EL1.PARange` (or `VTCR_EL2.PS`) value,
+/// capped at 48 bits
+const fn pa_bits(parange: u64) -> u8 {
+    match parange {
+        0b000 => 32,
+        0b001 => 36,
+        0b010 => 40,
+        0b011 => 42,
+        0b100 => 44,
+        _ => 48,
+    }
+}
+
+/// The layout of a stage 2 address space
+///
+/// The initial lookup level is chosen to use as few levels as possible, concatenating up to 16
+/// tables at the initial level.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Layout {
+    granule: Granule,
+    ipa_bits: u8,
+    ps: u8,
+}
+
+impl Layout {
+    /// A layout translating `ipa_bits` bits of IPA with `granule` pages, with the output size
+    /// taken from `ID_AA64MMFR0_EL1.PARange`
+    pub fn new(granule: Granule, ipa_bits: u8) -> Result<Layout, Error> {
+        Layout::for_parange(
+            granule,
+            ipa_bits,
+            ID_AA64MMFR0_EL1.read(ID_AA64MMFR0_EL1::PARange),
+        )
+    }
+
+    /// A layout translating `ipa_bits` bits of IPA with `granule` pages, for a PE reporting
+    /// `parange` in `ID_AA64MMFR0_EL1.PARange`
+    ///
+    /// Fails with [`Error::InvalidLayout`] if `ipa_bits` is below 25 or larger than the supported
+    /// physical address size (itself capped at 48 bits).
+    pub const fn for_parange(
+        granule: Granule,
+        ipa_bits: u8,
+        parange: u64,
+    ) -> Result<Layout, Error> {
+        let ps = if parange > 0b101 {
+            0b101
+        } else {
+            parange as u8
+        };
+        if ipa_bits < 25 || ipa_bits > pa_bits(ps as u64) {
+            return Err(Error::InvalidLayout);
+        }
+        Ok(Layout {
+            granule,
+            ipa_bits,
+            ps,
+        })
+    }
+
+    /// The translation granule
+    pub const fn granule(&self) -> Granule {
+        self.granule
+    }
+
+    /// Number of IPA bits
+    pub const fn ipa_bits(&self) -> u8 {
+        self.ipa_bits
+    }
+
+    /// Number of output address bits
+    pub const fn pa_bits(&self) -> u8 {
+        pa_bits(self.ps as u64)
+    }
+
+    /// The initial lookup level
+    pub const fn start_level(&self) -> u8 {
+        // up to 16 concatenated tables resolve four extra bits at the initial level
+        let resolved = self.ipa_bits as u32 - 4 - self.granule.shift();
+        let per_level = self.granule.shift() - 3;
+        let level = 4 - resolved.div_ceil(per_level) as u8;
+        match self.granule {
+            // starting at level 3 needs FEAT_TTST with 4KiB pages
+            Granule::Size4KiB if level > 2 => 2,
+            _ => level,
+        }
+    }
+
+    /// Number of concatenated tables at the initial lookup level
+    pub const fn root_tables(&self) -> usize {
+        let entries: usize =
+            1 << (self.ipa_bits as u32 - level_shift(self.granule, self.start_level()));
+        entries.div_ceil(entries_per_table(self.granule))
+    }
+
+    /// `VTCR_EL2` settings for this layout
+    ///
+    /// Table walks are Inner Shareable and Write-Back cacheable, and 8-bit VMIDs are used. See
+    /// [`Tables::vtcr_el2`] to also select the VMID size.
+    pub fn vtcr_el2(&self) -> FieldValue<u64, VTCR_EL2::Register> {
+        let (tg0, sl0) = match self.granule {
+            Granule::Size4KiB => (VTCR_EL2::TG0::Granule4KB, 2 - self.start_level()),
+            Granule::Size16KiB => (VTCR_EL2::TG0::Granule16KB, 3 - self.start_level()),
+            Granule::Size64KiB => (VTCR_EL2::TG0::Granule64KB, 3 - self.start_level()),
+        };
+
+        VTCR_EL2::RES1::SET
+            + VTCR_EL2::PS.val(self.ps as u64)
+            + tg0
+            + VTCR_EL2::SH0::Inner
+            + VTCR_EL2::ORGN0::NormalWBRAWA
+            + VTCR_EL2::IRGN0::NormalWBRAWA
+            + VTCR_EL2::SL0.val(sl0 as u64)
+            + VTCR_EL2::T0SZ.val(64 - self.ipa_bits as u64)
+    }
+}
+
+/// A set of stage 2 translation tables for one VMID
+pub struct Tables<'a> {
+    layout: Layout,
+    vmid: Vmid,
+    walker: Walker<'a>,
+}
+
+impl<'a> Tables<'a> {
+    /// Create empty tables for `layout` and `vmid`, using `pool` as storage for the tables
+    ///
+    /// The pool must be aligned to the combined size of the [`Layout::root_tables`] concatenated
+    /// root tables, which come first in the pool.
+    pub fn new(layout: Layout, vmid: Vmid, pool: &'a mut [u64]) -> Result<Tables<'a>, Error> {
+        Ok(Tables {
+            layout,
+            vmid,
+            walker: Walker::new(
+                pool,
+                layout.granule,
+                layout.ipa_bits,
+                layout.pa_bits(),
+                layout.start_level(),
+                layout.root_tables(),
+            )?,
+        })
+    }
+
+    /// The layout of the tables
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// The VMID the tables are tagged with
+    pub fn vmid(&self) -> Vmid {
+        self.vmid
+    }
+
+    /// Address of the (first) root table
+    pub fn root_address(&self) -> u64 {
+        self.walker.pool.address(self.walker.root)
+    }
+
+    /// Number of tables taken from the pool so far, including the root tables
+    pub fn tables_used(&self) -> usize {
+        self.walker.pool.used
+    }
+
+    /// Map `size` bytes at `ipa` to `pa`
+    ///
+    /// Addresses and size must be multiples of the granule size. Blocks are used wherever `ipa`
+    /// and `pa` are suitably aligned, and the Contiguous bit is set on aligned runs of entries.
+    ///
+    /// On error, the part of the range before the failing address may already be mapped.
+    pub fn map(
+        &mut self,
+        ipa: u64,
+        pa: u64,
+        size: u64,
+        attributes: Attributes,
+    ) -> Result<(), Error> {
+        self.walker.map(ipa, pa, size, attributes.bits())
+    }
+
+    /// Map `size` bytes at `pa` to the same IPA
+    pub fn identity_map(
+        &mut self,
+        pa: u64,
+        size: u64,
+        attributes: Attributes,
+    ) -> Result<(), Error> {
+        self.map(pa, pa, size, attributes)
+    }
+
+    /// Translate `ipa` through the tables, returning the physical address and attributes
+    pub fn translate(&self, ipa: u64) -> Option<(u64, Attributes)> {
+        self.walker
+            .translate(ipa)
+            .map(|(pa, entry)| (pa, Attributes::from_bits(entry)))
+    }
+
+    /// `VTTBR_EL2` value selecting these tables and their VMID
+    pub fn vttbr_el2(&self) -> FieldValue<u64, VTTBR_EL2::Register> {
+        VTTBR_EL2::VMID.val(self.vmid.get() as u64) + VTTBR_EL2::BADDR.val(self.root_address() >> 1)
+    }
+
+    /// `VTCR_EL2` settings for these tables
+    ///
+    /// As [`Layout::vtcr_el2`], selecting 16-bit VMIDs if the VMID does not fit in 8 bits.
+    pub fn vtcr_el2(&self) -> FieldValue<u64, VTCR_EL2::Register> {
+        if self.vmid.get() > 0xff {
+            self.layout.vtcr_el2() + VTCR_EL2::VS::Bits16
+        } else {
+            self.layout.vtcr_el2() + VTCR_EL2::VS::Bits8
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[repr(C, align(65536))]
+    struct Memory([u64; 8192 * 4]);
+
+    #[test]
+    fn layout() {
+        let layout = |granule, bits| Layout::for_parange(granule, bits, 0b0101).unwrap();
+        let levels = |granule, bits| {
+            let layout = layout(granule, bits);
+            (layout.start_level(), layout.root_tables())
+        };
+        assert_eq!(levels(Granule::Size4KiB, 48), (0, 1));
+        assert_eq!(levels(Granule::Size4KiB, 44), (0, 1));
+        assert_eq!(levels(Granule::Size4KiB, 43), (1, 16));
+        assert_eq!(levels(Granule::Size4KiB, 40), (1, 2));
+        assert_eq!(levels(Granule::Size4KiB, 32), (2, 4));
+        assert_eq!(levels(Granule::Size4KiB, 25), (2, 1));
+        assert_eq!(levels(Granule::Size16KiB, 48), (1, 2));
+        assert_eq!(levels(Granule::Size16KiB, 40), (2, 16));
+        assert_eq!(levels(Granule::Size64KiB, 48), (1, 1));
+        assert_eq!(levels(Granule::Size64KiB, 40), (2, 1));
+        assert_eq!(levels(Granule::Size64KiB, 32), (3, 8));
+
+        assert_eq!(
+            Layout::for_parange(Granule::Size4KiB, 40, 0b0001),
+            Err(Error::InvalidLayout)
+        );
+        assert_eq!(
+            Layout::for_parange(Granule::Size4KiB, 48, 0b0110)
+                .unwrap()
+                .pa_bits(),
+            48
+        );
+    }
+
+    #[test]
+    fn vtcr() {
+        let vtcr = |granule, bits| {
+            let layout = Layout::for_parange(granule, bits, 0b0010).unwrap();
+            LocalRegisterCopy::<u64, VTCR_EL2::Register>::new(layout.vtcr_el2().value)
+        };
+        let reg = vtcr(Granule::Size4KiB, 40);
+        assert_eq!(reg.read(VTCR_EL2::T0SZ), 24);
+        assert_eq!(reg.read(VTCR_EL2::SL0), 0b01);
+        assert_eq!(reg.read(VTCR_EL2::PS), 0b010);
+        assert!(reg.is_set(VTCR_EL2::RES1));
+        let reg = vtcr(Granule::Size64KiB, 32);
+        assert_eq!(reg.read(VTCR_EL2::T0SZ), 32);
+        assert_eq!(reg.read(VTCR_EL2::SL0), 0b00);
+        assert_eq!(reg.read(VTCR_EL2::TG0), 0b01);
+    }
+
+    #[test]
+    fn map() {
+        let mut memory = Memory([0; 8192 * 4]);
+        let layout = Layout::for_parange(Granule::Size4KiB, 40, 0b0010).unwrap();
+        let vmid = Vmid::new(0x123);
+        let mut tables = Tables::new(layout, vmid, &mut memory.0[..512 * 8]).unwrap();
+        let ram = Attributes::new(MemoryAttributes::NormalWriteBack).executable(true);
+
+        // a 1GiB block in the second concatenated root table, then one page
+        tables
+            .map(0x80_4000_0000, 0x4000_0000, 0x4000_1000, ram)
+            .unwrap();
+        assert_eq!(tables.tables_used(), 4);
+        assert_eq!(tables.translate(0x80_7fff_0010), Some((0x7fff_0010, ram)));
+        assert_eq!(tables.translate(0x80_8000_0010), Some((0x8000_0010, ram)));
+        assert_eq!(tables.translate(0x80_8000_1000), None);
+        assert_eq!(
+            tables.map(0, 0x100_0000_0000, 0x1000, ram),
+            Err(Error::OutOfRange(0x100_0000_0000))
+        );
+
+        let vttbr = LocalRegisterCopy::<u64, VTTBR_EL2::Register>::new(tables.vttbr_el2().value);
+        assert_eq!(vttbr.read(VTTBR_EL2::VMID), 0x123);
+        assert_eq!(vttbr.read(VTTBR_EL2::BADDR) << 1, tables.root_address());
+        let vtcr = LocalRegisterCopy::<u64, VTCR_EL2::Register>::new(tables.vtcr_el2().value);
+        assert!(vtcr.is_set(VTCR_EL2::VS));
+
+        // two concatenated root tables need 8KiB alignment
+        assert_eq!(
+            Tables::new(layout, vmid, &mut memory.0[512..512 * 8]).err(),
+            Some(Error::InvalidPool)
+        );
+    }
+
+    #[test]
+    fn attributes() {
+        let attributes = Attributes::new(MemoryAttributes::DeviceNGnRE)
+            .access(AccessPermissions::ReadOnly)
+            .fnxs(true);
+        assert_eq!(attributes.bits(), 0x0040_0000_0000_0f44);
+        assert_eq!(attributes.memory_attributes(), 0b0001);
+        assert_eq!(attributes.get_access(), AccessPermissions::ReadOnly);
+    }
+}
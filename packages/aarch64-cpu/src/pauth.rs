@@ -0,0 +1,374 @@
+//! Pointer authentication (FEAT_PAuth) key management and instructions
+//!
+//! [`PointerAuth`] is only available when `ID_AA64ISAR1_EL1` reports address authentication,
+//! so holding one proves the key registers and PAC instructions exist. It installs and saves
+//! [`PacKeys`], enables the keys in `SCTLR_EL1`, and wraps the signing and authentication
+//! instructions.
+//!
+//! The keys are shared by everything running in the EL1&0 translation regime. Higher Exception
+//! levels must not trap their use, i.e. `HCR_EL2.{API, APK}` and `SCR_EL3.{API, APK}` must be set.
+//!
+//! ```no_run
+//! use aarch64_cpu::{
+//!     asm::random::ArmRng,
+//!     pauth::{EnabledKeys, PacKeys, PointerAuth},
+//! };
+//!
+//! if let (Some(pauth), Some(rng)) = (PointerAuth::new(), ArmRng::new()) {
+//!     let keys = PacKeys::generate(&rng).unwrap();
+//!     unsafe {
+//!         pauth.install(&keys);
+//!         pauth.set_enabled(EnabledKeys::ALL);
+//!     }
+//!
+//!     let signed = pauth.sign_data(0x4000_1000, 42);
+//!     assert_eq!(pauth.auth_data(signed, 42), Some(0x4000_1000));
+//! }
+//! ```
+
+use crate::{
+    asm::{barrier, random::ArmRng},
+    registers::*,
+};
+
+/// A 128-bit pointer authentication key
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct Key {
+    /// Bits \[127:64\], held in `AP*KeyHi_EL1`
+    pub hi: u64,
+    /// Bits \[63:0\], held in `AP*KeyLo_EL1`
+    pub lo: u64,
+}
+
+impl Key {
+    /// Fill a key with random numbers
+    ///
+    /// Returns `None` if the RNG fails.
+    pub fn generate(rng: &ArmRng) -> Option<Key> {
+        Some(Key {
+            hi: rng.rndr()?,
+            lo: rng.rndr()?,
+        })
+    }
+}
+
+/// A complete set of pointer authentication keys
+///
+/// `Debug` is deliberately not implemented, to keep keys out of logs.
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct PacKeys {
+    /// Instruction key A (`APIAKey_EL1`)
+    pub ia: Key,
+    /// Instruction key B (`APIBKey_EL1`)
+    pub ib: Key,
+    /// Data key A (`APDAKey_EL1`)
+    pub da: Key,
+    /// Data key B (`APDBKey_EL1`)
+    pub db: Key,
+    /// Generic key (`APGAKey_EL1`)
+    pub ga: Key,
+}
+
+impl PacKeys {
+    /// Generate a fresh key set from the Arm v8.5 RNG
+    ///
+    /// Returns `None` if the RNG fails.
+    pub fn generate(rng: &ArmRng) -> Option<PacKeys> {
+        Some(PacKeys {
+            ia: Key::generate(rng)?,
+            ib: Key::generate(rng)?,
+            da: Key::generate(rng)?,
+            db: Key::generate(rng)?,
+            ga: Key::generate(rng)?,
+        })
+    }
+}
+
+/// Which keys are enabled in `SCTLR_EL1`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct EnabledKeys {
+    /// Enable instruction key A (`SCTLR_EL1.EnIA`)
+    pub ia: bool,
+    /// Enable instruction key B (`SCTLR_EL1.EnIB`)
+    pub ib: bool,
+    /// Enable data key A (`SCTLR_EL1.EnDA`)
+    pub da: bool,
+    /// Enable data key B (`SCTLR_EL1.EnDB`)
+    pub db: bool,
+}
+
+impl EnabledKeys {
+    /// Enable every key
+    pub const ALL: EnabledKeys = EnabledKeys {
+        ia: true,
+        ib: true,
+        da: true,
+        db: true,
+    };
+
+    /// Disable every key
+    pub const NONE: EnabledKeys = EnabledKeys {
+        ia: false,
+        ib: false,
+        da: false,
+        db: false,
+    };
+}
+
+/// Proof that address authentication is implemented
+#[derive(Copy, Clone, Debug)]
+pub struct PointerAuth {
+    generic: bool,
+}
+
+impl PointerAuth {
+    /// Check `ID_AA64ISAR1_EL1` and `ID_AA64ISAR2_EL1` for address authentication
+    ///
+    /// Returns `None` if none of the QARMA5 (`APA`), QARMA3 (`APA3`) or IMPLEMENTATION DEFINED
+    /// (`API`) algorithms is implemented.
+    pub fn new() -> Option<Self> {
+        match () {
+            #[cfg(target_arch = "aarch64")]
+            () => {
+                let isar1 = ID_AA64ISAR1_EL1.extract();
+                let isar2 = ID_AA64ISAR2_EL1.extract();
+                if isar1.is_set(ID_AA64ISAR1_EL1::APA)
+                    || isar1.is_set(ID_AA64ISAR1_EL1::API)
+                    || isar2.is_set(ID_AA64ISAR2_EL1::APA3)
+                {
+                    Some(PointerAuth {
+                        generic: isar1.is_set(ID_AA64ISAR1_EL1::GPA)
+                            || isar1.is_set(ID_AA64ISAR1_EL1::GPI)
+                            || isar2.is_set(ID_AA64ISAR2_EL1::GPA3),
+                    })
+                } else {
+                    None
+                }
+            }
+
+            #[cfg(not(target_arch = "aarch64"))]
+            () => None,
+        }
+    }
+
+    /// Whether generic authentication ([`PointerAuth::generic_mac`]) is implemented
+    pub fn has_generic(&self) -> bool {
+        self.generic
+    }
+
+    /// Install a key set, followed by an ISB so that it is used by the next instruction
+    ///
+    /// # Safety
+    ///
+    /// Any pointer signed with the old keys, such as a signed return address of a function that
+    /// is still on the stack, will fail authentication afterwards. The caller must make sure no
+    /// such pointer is authenticated, e.g. by only switching keys on a context switch.
+    pub unsafe fn install(&self, keys: &PacKeys) {
+        APIAKEYHI_EL1.set(keys.ia.hi);
+        APIAKEYLO_EL1.set(keys.ia.lo);
+        APIBKEYHI_EL1.set(keys.ib.hi);
+        APIBKEYLO_EL1.set(keys.ib.lo);
+        APDAKEYHI_EL1.set(keys.da.hi);
+        APDAKEYLO_EL1.set(keys.da.lo);
+        APDBKEYHI_EL1.set(keys.db.hi);
+        APDBKEYLO_EL1.set(keys.db.lo);
+        APGAKEYHI_EL1.set(keys.ga.hi);
+        APGAKEYLO_EL1.set(keys.ga.lo);
+        barrier::isb(barrier::SY);
+    }
+
+    /// Read back the installed key set, e.g. to save it on a context switch
+    pub fn save(&self) -> PacKeys {
+        PacKeys {
+            ia: Key {
+                hi: APIAKEYHI_EL1.get(),
+                lo: APIAKEYLO_EL1.get(),
+            },
+            ib: Key {
+                hi: APIBKEYHI_EL1.get(),
+                lo: APIBKEYLO_EL1.get(),
+            },
+            da: Key {
+                hi: APDAKEYHI_EL1.get(),
+                lo: APDAKEYLO_EL1.get(),
+            },
+            db: Key {
+                hi: APDBKEYHI_EL1.get(),
+                lo: APDBKEYLO_EL1.get(),
+            },
+            ga: Key {
+                hi: APGAKEYHI_EL1.get(),
+                lo: APGAKEYLO_EL1.get(),
+            },
+        }
+    }
+
+    /// Enable or disable the keys for the EL1&0 translation regime in a single `SCTLR_EL1`
+    /// write, followed by an ISB
+    ///
+    /// # Safety
+    ///
+    /// Enabling an instruction key makes previously unsigned return addresses fail
+    /// authentication in functions compiled with `-Z branch-protection=pac-ret`, and disabling
+    /// one leaves signed return addresses unauthenticated. Only change the instruction keys
+    /// when no such function is on the stack, e.g. early in boot.
+    pub unsafe fn set_enabled(&self, enabled: EnabledKeys) {
+        SCTLR_EL1.modify(
+            SCTLR_EL1::EnIA.val(enabled.ia as u64)
+                + SCTLR_EL1::EnIB.val(enabled.ib as u64)
+                + SCTLR_EL1::EnDA.val(enabled.da as u64)
+                + SCTLR_EL1::EnDB.val(enabled.db as u64),
+        );
+        barrier::isb(barrier::SY);
+    }
+
+    /// Get which keys are enabled for the EL1&0 translation regime
+    pub fn enabled(&self) -> EnabledKeys {
+        let sctlr = SCTLR_EL1.extract();
+        EnabledKeys {
+            ia: sctlr.is_set(SCTLR_EL1::EnIA),
+            ib: sctlr.is_set(SCTLR_EL1::EnIB),
+            da: sctlr.is_set(SCTLR_EL1::EnDA),
+            db: sctlr.is_set(SCTLR_EL1::EnDB),
+        }
+    }
+
+    /// Sign an instruction address with key A and `modifier` (`PACIA`)
+    ///
+    /// Returns `ptr` unchanged if `SCTLR_EL1.EnIA` is clear.
+    pub fn sign_instruction(&self, ptr: u64, modifier: u64) -> u64 {
+        pac(Op::Pacia, ptr, modifier)
+    }
+
+    /// Authenticate an instruction address signed with key A and `modifier` (`AUTIA`)
+    ///
+    /// Returns the address without its PAC, or `None` if authentication fails. When FEAT_FPAC
+    /// is implemented a failure raises an exception instead.
+    pub fn auth_instruction(&self, ptr: u64, modifier: u64) -> Option<u64> {
+        checked(pac(Op::Autia, ptr, modifier), self.strip_instruction(ptr))
+    }
+
+    /// Sign a data address with key A and `modifier` (`PACDA`)
+    ///
+    /// Returns `ptr` unchanged if `SCTLR_EL1.EnDA` is clear.
+    pub fn sign_data(&self, ptr: u64, modifier: u64) -> u64 {
+        pac(Op::Pacda, ptr, modifier)
+    }
+
+    /// Authenticate a data address signed with key A and `modifier` (`AUTDA`)
+    ///
+    /// Returns the address without its PAC, or `None` if authentication fails. When FEAT_FPAC
+    /// is implemented a failure raises an exception instead.
+    pub fn auth_data(&self, ptr: u64, modifier: u64) -> Option<u64> {
+        checked(pac(Op::Autda, ptr, modifier), self.strip_data(ptr))
+    }
+
+    /// Remove the PAC from an instruction address without authenticating it (`XPACI`)
+    pub fn strip_instruction(&self, ptr: u64) -> u64 {
+        pac(Op::Xpaci, ptr, 0)
+    }
+
+    /// Remove the PAC from a data address without authenticating it (`XPACD`)
+    pub fn strip_data(&self, ptr: u64) -> u64 {
+        pac(Op::Xpacd, ptr, 0)
+    }
+
+    /// Compute a 32-bit MAC of `value` and `modifier` with the generic key (`PACGA`)
+    ///
+    /// The MAC is returned in the upper 32 bits. Returns `None` if generic authentication is not
+    /// implemented.
+    pub fn generic_mac(&self, value: u64, modifier: u64) -> Option<u64> {
+        if self.generic {
+            Some(pac(Op::Pacga, value, modifier))
+        } else {
+            None
+        }
+    }
+}
+
+/// Authentication succeeded if it produced the same address as stripping the PAC
+fn checked(authenticated: u64, stripped: u64) -> Option<u64> {
+    if authenticated == stripped {
+        Some(authenticated)
+    } else {
+        None
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Op {
+    Pacia,
+    Autia,
+    Pacda,
+    Autda,
+    Xpaci,
+    Xpacd,
+    Pacga,
+}
+
+/// Execute a pointer authentication instruction on `ptr`
+#[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+#[inline(always)]
+fn pac(op: Op, ptr: u64, modifier: u64) -> u64 {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => unsafe {
+            use core::arch::asm;
+
+            let mut value = ptr;
+            match op {
+                Op::Pacia => asm!(
+                    ".arch_extension pauth",
+                    "PACIA {0}, {1}",
+                    inout(reg) value,
+                    in(reg) modifier,
+                    options(nomem, nostack)
+                ),
+                Op::Autia => asm!(
+                    ".arch_extension pauth",
+                    "AUTIA {0}, {1}",
+                    inout(reg) value,
+                    in(reg) modifier,
+                    options(nomem, nostack)
+                ),
+                Op::Pacda => asm!(
+                    ".arch_extension pauth",
+                    "PACDA {0}, {1}",
+                    inout(reg) value,
+                    in(reg) modifier,
+                    options(nomem, nostack)
+                ),
+                Op::Autda => asm!(
+                    ".arch_extension pauth",
+                    "AUTDA {0}, {1}",
+                    inout(reg) value,
+                    in(reg) modifier,
+                    options(nomem, nostack)
+                ),
+                Op::Xpaci => asm!(
+                    ".arch_extension pauth",
+                    "XPACI {0}",
+                    inout(reg) value,
+                    options(nomem, nostack)
+                ),
+                Op::Xpacd => asm!(
+                    ".arch_extension pauth",
+                    "XPACD {0}",
+                    inout(reg) value,
+                    options(nomem, nostack)
+                ),
+                Op::Pacga => asm!(
+                    ".arch_extension pauth",
+                    "PACGA {0}, {0}, {1}",
+                    inout(reg) value,
+                    in(reg) modifier,
+                    options(nomem, nostack)
+                ),
+            }
+            value
+        },
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
@@ -0,0 +1,382 @@
+//! Code and types for the Performance Monitors Extension (PMUv3)
+//!
+//! [`Pmu`] owns the cycle counter and the event counters that are accessible at the current
+//! Exception level. Event counters are programmed with an architectural (or IMPLEMENTATION
+//! DEFINED) [`Event`] number and read back as 64-bit values.
+//!
+//! When EL2 is implemented, `MDCR_EL2.HPMN` splits the event counters into a range owned by
+//! EL1/EL0 (`0..HPMN`) and a range reserved for EL2 (`HPMN..PMCR_EL0.N`). [`Pmu`] only hands
+//! out the first range, which is the only one enabled by `PMCR_EL0.E`.
+//!
+//! Event counters are 64 bits wide when FEAT_PMUv3p5 is implemented. Otherwise they are 32 bits
+//! wide, and [`Pmu::read`] extends them in software using the overflow flags in
+//! `PMOVSCLR_EL0`. In that case each enabled counter must be read at least once every 2^32
+//! events, and nothing else may clear its overflow flag.
+//!
+//! ```no_run
+//! use aarch64_cpu::pmu::{Event, Filter, Pmu};
+//!
+//! let mut pmu = unsafe { Pmu::new() };
+//! pmu.configure(0, Event::INST_RETIRED, Filter::ALL).unwrap();
+//! pmu.enable(0).unwrap();
+//! pmu.enable_cycles();
+//!
+//! // code under test
+//!
+//! let instructions = pmu.read(0).unwrap();
+//! let cycles = pmu.cycles();
+//! ```
+
+use crate::{asm::barrier, registers::*};
+
+/// The cycle counter bit in the counter enable, interrupt enable and overflow registers
+const CYCLE_COUNTER: u64 = 1 << 31;
+
+/// Mask of all the event counter bits in the same registers
+const ALL_COUNTERS: u64 = 0x7fff_ffff;
+
+/// PMUVer value for FEAT_PMUv3p5, which adds 64-bit event counters
+const PMUV3P5: u64 = 0b0110;
+
+/// PMUVer value for an IMPLEMENTATION DEFINED PMU
+const PMU_IMPDEF: u64 = 0b1111;
+
+/// Ways this API can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Found a counter number that is not accessible at this Exception level
+    ///
+    /// Valid counters are `0..pmu.num_counters()`.
+    InvalidCounter(u8),
+}
+
+/// An event number, as programmed into `PMEVTYPER<n>_EL0.evtCount`
+///
+/// The associated constants are the common architectural and microarchitectural events. Use
+/// `Event(n)` for any other event, including IMPLEMENTATION DEFINED ones.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Event(pub u16);
+
+impl Event {
+    /// Instruction architecturally executed, condition code check pass, software increment
+    pub const SW_INCR: Event = Event(0x00);
+    /// Level 1 instruction cache refill
+    pub const L1I_CACHE_REFILL: Event = Event(0x01);
+    /// Level 1 instruction TLB refill
+    pub const L1I_TLB_REFILL: Event = Event(0x02);
+    /// Level 1 data cache refill
+    pub const L1D_CACHE_REFILL: Event = Event(0x03);
+    /// Level 1 data cache access
+    pub const L1D_CACHE: Event = Event(0x04);
+    /// Level 1 data TLB refill
+    pub const L1D_TLB_REFILL: Event = Event(0x05);
+    /// Instruction architecturally executed, condition code check pass, load
+    pub const LD_RETIRED: Event = Event(0x06);
+    /// Instruction architecturally executed, condition code check pass, store
+    pub const ST_RETIRED: Event = Event(0x07);
+    /// Instruction architecturally executed
+    pub const INST_RETIRED: Event = Event(0x08);
+    /// Exception taken
+    pub const EXC_TAKEN: Event = Event(0x09);
+    /// Instruction architecturally executed, condition code check pass, exception return
+    pub const EXC_RETURN: Event = Event(0x0A);
+    /// Instruction architecturally executed, condition code check pass, write to CONTEXTIDR
+    pub const CID_WRITE_RETIRED: Event = Event(0x0B);
+    /// Instruction architecturally executed, condition code check pass, software change of the
+    /// PC
+    pub const PC_WRITE_RETIRED: Event = Event(0x0C);
+    /// Instruction architecturally executed, immediate branch
+    pub const BR_IMMED_RETIRED: Event = Event(0x0D);
+    /// Instruction architecturally executed, condition code check pass, procedure return
+    pub const BR_RETURN_RETIRED: Event = Event(0x0E);
+    /// Instruction architecturally executed, condition code check pass, unaligned load or store
+    pub const UNALIGNED_LDST_RETIRED: Event = Event(0x0F);
+    /// Branch mispredicted or not predicted
+    pub const BR_MIS_PRED: Event = Event(0x10);
+    /// Cycle
+    pub const CPU_CYCLES: Event = Event(0x11);
+    /// Predictable branch speculatively executed
+    pub const BR_PRED: Event = Event(0x12);
+    /// Data memory access
+    pub const MEM_ACCESS: Event = Event(0x13);
+    /// Level 1 instruction cache access
+    pub const L1I_CACHE: Event = Event(0x14);
+    /// Level 1 data cache write-back
+    pub const L1D_CACHE_WB: Event = Event(0x15);
+    /// Level 2 data cache access
+    pub const L2D_CACHE: Event = Event(0x16);
+    /// Level 2 data cache refill
+    pub const L2D_CACHE_REFILL: Event = Event(0x17);
+    /// Level 2 data cache write-back
+    pub const L2D_CACHE_WB: Event = Event(0x18);
+    /// Bus access
+    pub const BUS_ACCESS: Event = Event(0x19);
+    /// Local memory error
+    pub const MEMORY_ERROR: Event = Event(0x1A);
+    /// Operation speculatively executed
+    pub const INST_SPEC: Event = Event(0x1B);
+    /// Instruction architecturally executed, condition code check pass, write to TTBR
+    pub const TTBR_WRITE_RETIRED: Event = Event(0x1C);
+    /// Bus cycle
+    pub const BUS_CYCLES: Event = Event(0x1D);
+    /// For an odd-numbered counter, increment when an overflow occurs on the preceding
+    /// even-numbered counter
+    pub const CHAIN: Event = Event(0x1E);
+    /// No operation sent for execution due to the frontend
+    pub const STALL_FRONTEND: Event = Event(0x23);
+    /// No operation sent for execution due to the backend
+    pub const STALL_BACKEND: Event = Event(0x24);
+    /// Level 1 data TLB access
+    pub const L1D_TLB: Event = Event(0x25);
+    /// Level 1 instruction TLB access
+    pub const L1I_TLB: Event = Event(0x26);
+    /// Exception taken, IRQ
+    pub const EXC_IRQ: Event = Event(0x86);
+    /// Exception taken, FIQ
+    pub const EXC_FIQ: Event = Event(0x87);
+}
+
+/// The Exception levels in which a counter counts
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Filter {
+    /// Count at EL0
+    pub el0: bool,
+    /// Count at EL1
+    pub el1: bool,
+    /// Count at EL2, in both Security states
+    pub el2: bool,
+    /// Count at EL3
+    pub el3: bool,
+}
+
+impl Filter {
+    /// Count at every Exception level
+    pub const ALL: Filter = Filter {
+        el0: true,
+        el1: true,
+        el2: true,
+        el3: true,
+    };
+
+    /// Count at EL0 only
+    pub const EL0: Filter = Filter {
+        el0: true,
+        el1: false,
+        el2: false,
+        el3: false,
+    };
+
+    /// Count at EL1 only
+    pub const EL1: Filter = Filter {
+        el0: false,
+        el1: true,
+        el2: false,
+        el3: false,
+    };
+
+    /// Count at EL2 only
+    pub const EL2: Filter = Filter {
+        el0: false,
+        el1: false,
+        el2: true,
+        el3: false,
+    };
+
+    /// The filter bits of `PMEVTYPER<n>_EL0`, which are laid out the same way in
+    /// `PMCCFILTR_EL0`
+    ///
+    /// `SH` is left clear so that Secure EL2 follows `NSH`.
+    fn bits(self) -> u64 {
+        let mut value = PMEVTYPER_EL0::NSH.val(self.el2 as u64);
+        if !self.el1 {
+            value += PMEVTYPER_EL0::P::SET;
+        }
+        if !self.el0 {
+            value += PMEVTYPER_EL0::U::SET;
+        }
+        // EL3 is counted when `M` equals `P`
+        if !self.el1 ^ !self.el3 {
+            value += PMEVTYPER_EL0::M::SET;
+        }
+        value.value
+    }
+}
+
+/// Represents the Performance Monitors of this PE
+pub struct Pmu {
+    num_counters: u8,
+    long_counters: bool,
+    high: [u32; 31],
+}
+
+impl Pmu {
+    /// Create a PMU driver
+    ///
+    /// Enables the PMU in `PMCR_EL0`, with all counters stopped and their overflow interrupts
+    /// disabled. Must be called from EL1 or higher.
+    ///
+    /// # Safety
+    ///
+    /// Only create one of these at any given time, as it accesses shared mutable state within
+    /// the processor and does read-modify-writes on that state.
+    pub unsafe fn new() -> Pmu {
+        let mut num_counters = PMCR_EL0.read(PMCR_EL0::N) as u8;
+        if CurrentEL.read(CurrentEL::EL) >= 2 && ID_AA64PFR0_EL1.read(ID_AA64PFR0_EL1::EL2) != 0 {
+            // At EL1 `PMCR_EL0.N` already reports HPMN, but at EL2 and EL3 it does not
+            num_counters = num_counters.min(MDCR_EL2.read(MDCR_EL2::HPMN) as u8);
+        }
+
+        let pmu_version = ID_AA64DFR0_EL1.read(ID_AA64DFR0_EL1::PMUVer);
+        let long_counters = (PMUV3P5..PMU_IMPDEF).contains(&pmu_version);
+
+        PMCNTENCLR_EL0.set(CYCLE_COUNTER | ALL_COUNTERS);
+        PMINTENCLR_EL1.set(CYCLE_COUNTER | ALL_COUNTERS);
+        PMOVSCLR_EL0.set(CYCLE_COUNTER | ALL_COUNTERS);
+        if long_counters {
+            PMCR_EL0.modify(PMCR_EL0::LP::Overflow64);
+        }
+        PMCR_EL0.modify(PMCR_EL0::LC::Overflow64 + PMCR_EL0::E::Enabled);
+        barrier::isb(barrier::SY);
+
+        Pmu {
+            num_counters,
+            long_counters,
+            high: [0; 31],
+        }
+    }
+
+    /// Get the number of event counters accessible at this Exception level
+    pub fn num_counters(&self) -> u8 {
+        self.num_counters
+    }
+
+    /// Whether the event counters are 64 bits wide in hardware (FEAT_PMUv3p5)
+    pub fn has_long_counters(&self) -> bool {
+        self.long_counters
+    }
+
+    /// Program event counter `counter` to count `event` in the Exception levels selected by
+    /// `filter`
+    ///
+    /// The counter is stopped and reset to zero. Use [`Pmu::enable`] to start it.
+    pub fn configure(&mut self, counter: u8, event: Event, filter: Filter) -> Result<(), Error> {
+        let bit = self.counter_bit(counter)?;
+
+        PMCNTENCLR_EL0.set(bit);
+        self.select(counter);
+        PMXEVTYPER_EL0.set(filter.bits() | PMEVTYPER_EL0::evtCount.val(event.0 as u64).value);
+        PMXEVCNTR_EL0.set(0);
+        PMOVSCLR_EL0.set(bit);
+        self.high[counter as usize] = 0;
+
+        Ok(())
+    }
+
+    /// Start event counter `counter`
+    pub fn enable(&mut self, counter: u8) -> Result<(), Error> {
+        PMCNTENSET_EL0.set(self.counter_bit(counter)?);
+        Ok(())
+    }
+
+    /// Stop event counter `counter`
+    pub fn disable(&mut self, counter: u8) -> Result<(), Error> {
+        PMCNTENCLR_EL0.set(self.counter_bit(counter)?);
+        Ok(())
+    }
+
+    /// Read the 64-bit value of event counter `counter`
+    pub fn read(&mut self, counter: u8) -> Result<u64, Error> {
+        let bit = self.counter_bit(counter)?;
+
+        self.select(counter);
+        if self.long_counters {
+            return Ok(PMXEVCNTR_EL0.get());
+        }
+
+        // A set overflow flag means the low word wrapped since the last read. If it is set again
+        // after reading the low word, the wrap happened in between, so read it again.
+        let high = &mut self.high[counter as usize];
+        if PMOVSCLR_EL0.get() & bit != 0 {
+            PMOVSCLR_EL0.set(bit);
+            *high = high.wrapping_add(1);
+        }
+        let mut low = PMXEVCNTR_EL0.get() as u32;
+        if PMOVSCLR_EL0.get() & bit != 0 {
+            PMOVSCLR_EL0.set(bit);
+            *high = high.wrapping_add(1);
+            low = PMXEVCNTR_EL0.get() as u32;
+        }
+
+        Ok((*high as u64) << 32 | low as u64)
+    }
+
+    /// Count cycles in the Exception levels selected by `filter`
+    pub fn configure_cycles(&mut self, filter: Filter) {
+        PMCCFILTR_EL0.set(filter.bits());
+    }
+
+    /// Start the cycle counter
+    pub fn enable_cycles(&mut self) {
+        PMCNTENSET_EL0.set(CYCLE_COUNTER);
+    }
+
+    /// Stop the cycle counter
+    pub fn disable_cycles(&mut self) {
+        PMCNTENCLR_EL0.set(CYCLE_COUNTER);
+    }
+
+    /// Read the cycle counter
+    pub fn cycles(&self) -> u64 {
+        PMCCNTR_EL0.get()
+    }
+
+    /// Reset the cycle counter and every event counter accessible at this Exception level to zero
+    pub fn reset(&mut self) {
+        PMCR_EL0.modify(PMCR_EL0::C::SET + PMCR_EL0::P::SET);
+        PMOVSCLR_EL0.set(CYCLE_COUNTER | ALL_COUNTERS);
+        self.high = [0; 31];
+    }
+
+    /// Control whether code at EL0 can read the counters
+    ///
+    /// This does not allow EL0 to reprogram them. Only has an effect when called from EL1 or
+    /// higher.
+    pub fn el0_access(&mut self, access: bool) {
+        PMUSERENR_EL0.write(if access {
+            PMUSERENR_EL0::ER::Enable + PMUSERENR_EL0::CR::Enable
+        } else {
+            PMUSERENR_EL0::ER::Trap + PMUSERENR_EL0::CR::Trap
+        });
+    }
+
+    /// Check `counter` is accessible and return its bit in the counter bitmask registers
+    fn counter_bit(&self, counter: u8) -> Result<u64, Error> {
+        if counter < self.num_counters {
+            Ok(1 << counter)
+        } else {
+            Err(Error::InvalidCounter(counter))
+        }
+    }
+
+    /// Select `counter` for access through `PMXEVTYPER_EL0` and `PMXEVCNTR_EL0`
+    fn select(&self, counter: u8) {
+        PMSELR_EL0.write(PMSELR_EL0::SEL.val(counter as u64));
+        barrier::isb(barrier::SY);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn filter_bits() {
+        assert_eq!(Filter::ALL.bits(), 1 << 27);
+        // P set; U, M, NSH clear
+        assert_eq!(Filter::EL0.bits(), 1 << 31);
+        // U, M set
+        assert_eq!(Filter::EL1.bits(), 1 << 30 | 1 << 26);
+        // P, U, NSH set
+        assert_eq!(Filter::EL2.bits(), 1 << 31 | 1 << 30 | 1 << 27);
+    }
+}
@@ -42,15 +42,22 @@ mod cntvoff_el2;
 mod contextidr_el1;
 mod cpacr_el1;
 mod cptr_el2;
+mod cptr_el3;
 mod csselr_el1;
+mod ctr_el0;
 mod currentel;
 mod dacr32_el2;
 mod daif;
 mod daifclr;
 mod daifset;
+mod dbgbcr_el1;
+mod dbgbvr_el1;
 mod dbgdtr_el0;
 mod dbgdtrrx_el0;
 mod dbgdtrtx_el0;
+mod dbgwcr_el1;
+mod dbgwvr_el1;
+mod dczid_el0;
 mod elr_el1;
 mod elr_el2;
 mod elr_el3;
@@ -61,6 +68,9 @@ mod far_el1;
 mod far_el2;
 mod far_el3;
 mod fp;
+mod fpcr;
+mod fpsr;
+mod gcr_el1;
 mod hafgrtr_el2;
 mod hcr_el2;
 mod hdfgrtr_el2;
@@ -69,8 +79,27 @@ mod hfgitr_el2;
 mod hfgrtr_el2;
 mod hfgwtr_el2;
 mod hpfar_el2;
+mod icc_asgi1r_el1;
+mod icc_bpr0_el1;
+mod icc_bpr1_el1;
 mod icc_ctlr_el1;
+mod icc_ctlr_el3;
+mod icc_dir_el1;
+mod icc_eoir0_el1;
+mod icc_eoir1_el1;
+mod icc_hppir0_el1;
+mod icc_hppir1_el1;
+mod icc_iar0_el1;
+mod icc_iar1_el1;
+mod icc_igrpen0_el1;
+mod icc_igrpen1_el1;
+mod icc_pmr_el1;
+mod icc_rpr_el1;
+mod icc_sgi0r_el1;
+mod icc_sgi1r_el1;
+mod icc_sre_el1;
 mod icc_sre_el2;
+mod icc_sre_el3;
 mod ich_ap0r0_el2;
 mod ich_ap0r1_el2;
 mod ich_ap0r2_el2;
@@ -105,11 +134,14 @@ mod id_aa64dfr0_el1;
 mod id_aa64dfr1_el1;
 mod id_aa64isar0_el1;
 mod id_aa64isar1_el1;
+mod id_aa64isar2_el1;
 mod id_aa64mmfr0_el1;
 mod id_aa64mmfr1_el1;
 mod id_aa64mmfr2_el1;
 mod id_aa64pfr0_el1;
 mod id_aa64pfr1_el1;
+mod id_aa64smfr0_el1;
+mod id_aa64zfr0_el1;
 mod lr;
 mod mair_el1;
 mod mair_el2;
@@ -120,8 +152,25 @@ mod midr_el1;
 mod mpidr_el1;
 mod mpuir_el1;
 mod mpuir_el2;
+mod nzcv;
+mod osdlr_el1;
 mod oslar_el1;
+mod oslsr_el1;
 mod par_el1;
+mod pmccfiltr_el0;
+mod pmccntr_el0;
+mod pmcntenclr_el0;
+mod pmcntenset_el0;
+mod pmcr_el0;
+mod pmevcntr_el0;
+mod pmevtyper_el0;
+mod pmintenclr_el1;
+mod pmintenset_el1;
+mod pmovsclr_el0;
+mod pmselr_el0;
+mod pmuserenr_el0;
+mod pmxevcntr_el0;
+mod pmxevtyper_el0;
 #[cfg(arm_architecture = "v8-r")]
 mod prbar_el1;
 #[cfg(arm_architecture = "v8-r")]
@@ -132,6 +181,7 @@ mod prlar_el1;
 mod prlar_el2;
 mod prselr_el1;
 mod prselr_el2;
+mod rgsr_el1;
 mod rvbar_el1;
 mod rvbar_el2;
 mod rvbar_el3;
@@ -139,6 +189,10 @@ mod scr_el3;
 mod sctlr_el1;
 mod sctlr_el2;
 mod sctlr_el3;
+mod smcr_el1;
+mod smcr_el2;
+mod smcr_el3;
+mod smpri_el1;
 mod sp;
 mod sp_el0;
 mod sp_el1;
@@ -148,9 +202,14 @@ mod spsel;
 mod spsr_el1;
 mod spsr_el2;
 mod spsr_el3;
+mod svcr;
 mod tcr2_el1;
 mod tcr_el1;
 mod tcr_el2;
+mod tfsr_el1;
+mod tfsr_el2;
+mod tfsr_el3;
+mod tfsre0_el1;
 mod tpidr_el0;
 mod tpidr_el1;
 mod tpidr_el2;
@@ -164,6 +223,9 @@ mod vbar_el3;
 mod vmpidr_el2;
 mod vtcr_el2;
 mod vttbr_el2;
+mod zcr_el1;
+mod zcr_el2;
+mod zcr_el3;
 
 pub use actlr_el1::ACTLR_EL1;
 pub use actlr_el2::ACTLR_EL2;
@@ -201,16 +263,39 @@ pub use cntvct_el0::CNTVCT_EL0;
 pub use cntvoff_el2::CNTVOFF_EL2;
 pub use contextidr_el1::CONTEXTIDR_EL1;
 pub use cpacr_el1::CPACR_EL1;
-pub use cptr_el2::CPTR_EL2;
+pub use cptr_el2::{CPTR_EL2, CPTR_EL2_E2H};
+pub use cptr_el3::CPTR_EL3;
 pub use csselr_el1::CSSELR_EL1;
+pub use ctr_el0::CTR_EL0;
 pub use currentel::CurrentEL;
 pub use dacr32_el2::DACR32_EL2;
 pub use daif::DAIF;
 pub use daifclr::DAIFClr;
 pub use daifset::DAIFSet;
+pub use dbgbcr_el1::{
+    DBGBCR0_EL1, DBGBCR10_EL1, DBGBCR11_EL1, DBGBCR12_EL1, DBGBCR13_EL1, DBGBCR14_EL1,
+    DBGBCR15_EL1, DBGBCR1_EL1, DBGBCR2_EL1, DBGBCR3_EL1, DBGBCR4_EL1, DBGBCR5_EL1, DBGBCR6_EL1,
+    DBGBCR7_EL1, DBGBCR8_EL1, DBGBCR9_EL1, DBGBCR_EL1,
+};
+pub use dbgbvr_el1::{
+    DBGBVR0_EL1, DBGBVR10_EL1, DBGBVR11_EL1, DBGBVR12_EL1, DBGBVR13_EL1, DBGBVR14_EL1,
+    DBGBVR15_EL1, DBGBVR1_EL1, DBGBVR2_EL1, DBGBVR3_EL1, DBGBVR4_EL1, DBGBVR5_EL1, DBGBVR6_EL1,
+    DBGBVR7_EL1, DBGBVR8_EL1, DBGBVR9_EL1,
+};
 pub use dbgdtr_el0::DBGDTR_EL0;
 pub use dbgdtrrx_el0::DBGDTRRX_EL0;
 pub use dbgdtrtx_el0::DBGDTRTX_EL0;
+pub use dbgwcr_el1::{
+    DBGWCR0_EL1, DBGWCR10_EL1, DBGWCR11_EL1, DBGWCR12_EL1, DBGWCR13_EL1, DBGWCR14_EL1,
+    DBGWCR15_EL1, DBGWCR1_EL1, DBGWCR2_EL1, DBGWCR3_EL1, DBGWCR4_EL1, DBGWCR5_EL1, DBGWCR6_EL1,
+    DBGWCR7_EL1, DBGWCR8_EL1, DBGWCR9_EL1, DBGWCR_EL1,
+};
+pub use dbgwvr_el1::{
+    DBGWVR0_EL1, DBGWVR10_EL1, DBGWVR11_EL1, DBGWVR12_EL1, DBGWVR13_EL1, DBGWVR14_EL1,
+    DBGWVR15_EL1, DBGWVR1_EL1, DBGWVR2_EL1, DBGWVR3_EL1, DBGWVR4_EL1, DBGWVR5_EL1, DBGWVR6_EL1,
+    DBGWVR7_EL1, DBGWVR8_EL1, DBGWVR9_EL1,
+};
+pub use dczid_el0::DCZID_EL0;
 pub use elr_el1::ELR_EL1;
 pub use elr_el2::ELR_EL2;
 pub use elr_el3::ELR_EL3;
@@ -221,6 +306,9 @@ pub use far_el1::FAR_EL1;
 pub use far_el2::FAR_EL2;
 pub use far_el3::FAR_EL3;
 pub use fp::FP;
+pub use fpcr::FPCR;
+pub use fpsr::FPSR;
+pub use gcr_el1::GCR_EL1;
 pub use hafgrtr_el2::HAFGRTR_EL2;
 pub use hcr_el2::HCR_EL2;
 pub use hdfgrtr_el2::HDFGRTR_EL2;
@@ -229,8 +317,27 @@ pub use hfgitr_el2::HFGITR_EL2;
 pub use hfgrtr_el2::HFGRTR_EL2;
 pub use hfgwtr_el2::HFGWTR_EL2;
 pub use hpfar_el2::HPFAR_EL2;
+pub use icc_asgi1r_el1::ICC_ASGI1R_EL1;
+pub use icc_bpr0_el1::ICC_BPR0_EL1;
+pub use icc_bpr1_el1::ICC_BPR1_EL1;
 pub use icc_ctlr_el1::ICC_CTLR_EL1;
+pub use icc_ctlr_el3::ICC_CTLR_EL3;
+pub use icc_dir_el1::ICC_DIR_EL1;
+pub use icc_eoir0_el1::ICC_EOIR0_EL1;
+pub use icc_eoir1_el1::ICC_EOIR1_EL1;
+pub use icc_hppir0_el1::ICC_HPPIR0_EL1;
+pub use icc_hppir1_el1::ICC_HPPIR1_EL1;
+pub use icc_iar0_el1::ICC_IAR0_EL1;
+pub use icc_iar1_el1::ICC_IAR1_EL1;
+pub use icc_igrpen0_el1::ICC_IGRPEN0_EL1;
+pub use icc_igrpen1_el1::ICC_IGRPEN1_EL1;
+pub use icc_pmr_el1::ICC_PMR_EL1;
+pub use icc_rpr_el1::ICC_RPR_EL1;
+pub use icc_sgi0r_el1::ICC_SGI0R_EL1;
+pub use icc_sgi1r_el1::ICC_SGI1R_EL1;
+pub use icc_sre_el1::ICC_SRE_EL1;
 pub use icc_sre_el2::ICC_SRE_EL2;
+pub use icc_sre_el3::ICC_SRE_EL3;
 pub use ich_ap0r0_el2::ICH_AP0R0_EL2;
 pub use ich_ap0r1_el2::ICH_AP0R1_EL2;
 pub use ich_ap0r2_el2::ICH_AP0R2_EL2;
@@ -265,11 +372,14 @@ pub use id_aa64dfr0_el1::ID_AA64DFR0_EL1;
 pub use id_aa64dfr1_el1::ID_AA64DFR1_EL1;
 pub use id_aa64isar0_el1::ID_AA64ISAR0_EL1;
 pub use id_aa64isar1_el1::ID_AA64ISAR1_EL1;
+pub use id_aa64isar2_el1::ID_AA64ISAR2_EL1;
 pub use id_aa64mmfr0_el1::ID_AA64MMFR0_EL1;
 pub use id_aa64mmfr1_el1::ID_AA64MMFR1_EL1;
 pub use id_aa64mmfr2_el1::ID_AA64MMFR2_EL1;
 pub use id_aa64pfr0_el1::ID_AA64PFR0_EL1;
 pub use id_aa64pfr1_el1::ID_AA64PFR1_EL1;
+pub use id_aa64smfr0_el1::ID_AA64SMFR0_EL1;
+pub use id_aa64zfr0_el1::ID_AA64ZFR0_EL1;
 pub use lr::LR;
 pub use mair_el1::MAIR_EL1;
 pub use mair_el2::MAIR_EL2;
@@ -280,8 +390,40 @@ pub use midr_el1::MIDR_EL1;
 pub use mpidr_el1::MPIDR_EL1;
 pub use mpuir_el1::MPUIR_EL1;
 pub use mpuir_el2::MPUIR_EL2;
+pub use nzcv::NZCV;
+pub use osdlr_el1::OSDLR_EL1;
 pub use oslar_el1::OSLAR_EL1;
+pub use oslsr_el1::OSLSR_EL1;
 pub use par_el1::PAR_EL1;
+pub use pmccfiltr_el0::PMCCFILTR_EL0;
+pub use pmccntr_el0::PMCCNTR_EL0;
+pub use pmcntenclr_el0::PMCNTENCLR_EL0;
+pub use pmcntenset_el0::PMCNTENSET_EL0;
+pub use pmcr_el0::PMCR_EL0;
+pub use pmevcntr_el0::{
+    PMEVCNTR0_EL0, PMEVCNTR10_EL0, PMEVCNTR11_EL0, PMEVCNTR12_EL0, PMEVCNTR13_EL0, PMEVCNTR14_EL0,
+    PMEVCNTR15_EL0, PMEVCNTR16_EL0, PMEVCNTR17_EL0, PMEVCNTR18_EL0, PMEVCNTR19_EL0, PMEVCNTR1_EL0,
+    PMEVCNTR20_EL0, PMEVCNTR21_EL0, PMEVCNTR22_EL0, PMEVCNTR23_EL0, PMEVCNTR24_EL0, PMEVCNTR25_EL0,
+    PMEVCNTR26_EL0, PMEVCNTR27_EL0, PMEVCNTR28_EL0, PMEVCNTR29_EL0, PMEVCNTR2_EL0, PMEVCNTR30_EL0,
+    PMEVCNTR3_EL0, PMEVCNTR4_EL0, PMEVCNTR5_EL0, PMEVCNTR6_EL0, PMEVCNTR7_EL0, PMEVCNTR8_EL0,
+    PMEVCNTR9_EL0,
+};
+pub use pmevtyper_el0::{
+    PMEVTYPER0_EL0, PMEVTYPER10_EL0, PMEVTYPER11_EL0, PMEVTYPER12_EL0, PMEVTYPER13_EL0,
+    PMEVTYPER14_EL0, PMEVTYPER15_EL0, PMEVTYPER16_EL0, PMEVTYPER17_EL0, PMEVTYPER18_EL0,
+    PMEVTYPER19_EL0, PMEVTYPER1_EL0, PMEVTYPER20_EL0, PMEVTYPER21_EL0, PMEVTYPER22_EL0,
+    PMEVTYPER23_EL0, PMEVTYPER24_EL0, PMEVTYPER25_EL0, PMEVTYPER26_EL0, PMEVTYPER27_EL0,
+    PMEVTYPER28_EL0, PMEVTYPER29_EL0, PMEVTYPER2_EL0, PMEVTYPER30_EL0, PMEVTYPER3_EL0,
+    PMEVTYPER4_EL0, PMEVTYPER5_EL0, PMEVTYPER6_EL0, PMEVTYPER7_EL0, PMEVTYPER8_EL0, PMEVTYPER9_EL0,
+    PMEVTYPER_EL0,
+};
+pub use pmintenclr_el1::PMINTENCLR_EL1;
+pub use pmintenset_el1::PMINTENSET_EL1;
+pub use pmovsclr_el0::PMOVSCLR_EL0;
+pub use pmselr_el0::PMSELR_EL0;
+pub use pmuserenr_el0::PMUSERENR_EL0;
+pub use pmxevcntr_el0::PMXEVCNTR_EL0;
+pub use pmxevtyper_el0::PMXEVTYPER_EL0;
 #[cfg(arm_architecture = "v8-r")]
 pub use prbar_el1::PRBAR_EL1;
 #[cfg(arm_architecture = "v8-r")]
@@ -292,6 +434,7 @@ pub use prlar_el1::PRLAR_EL1;
 pub use prlar_el2::PRLAR_EL2;
 pub use prselr_el1::PRSELR_EL1;
 pub use prselr_el2::PRSELR_EL2;
+pub use rgsr_el1::RGSR_EL1;
 pub use rvbar_el1::RVBAR_EL1;
 pub use rvbar_el2::RVBAR_EL2;
 pub use rvbar_el3::RVBAR_EL3;
@@ -299,6 +442,10 @@ pub use scr_el3::SCR_EL3;
 pub use sctlr_el1::SCTLR_EL1;
 pub use sctlr_el2::SCTLR_EL2;
 pub use sctlr_el3::SCTLR_EL3;
+pub use smcr_el1::SMCR_EL1;
+pub use smcr_el2::SMCR_EL2;
+pub use smcr_el3::SMCR_EL3;
+pub use smpri_el1::SMPRI_EL1;
 pub use sp::SP;
 pub use sp_el0::SP_EL0;
 pub use sp_el1::SP_EL1;
@@ -308,9 +455,14 @@ pub use spsel::SPSel;
 pub use spsr_el1::SPSR_EL1;
 pub use spsr_el2::SPSR_EL2;
 pub use spsr_el3::SPSR_EL3;
+pub use svcr::SVCR;
 pub use tcr2_el1::TCR2_EL1;
 pub use tcr_el1::TCR_EL1;
 pub use tcr_el2::TCR_EL2;
+pub use tfsr_el1::TFSR_EL1;
+pub use tfsr_el2::TFSR_EL2;
+pub use tfsr_el3::TFSR_EL3;
+pub use tfsre0_el1::TFSRE0_EL1;
 pub use tpidr_el0::TPIDR_EL0;
 pub use tpidr_el1::TPIDR_EL1;
 pub use tpidr_el2::TPIDR_EL2;
@@ -324,6 +476,9 @@ pub use vbar_el3::VBAR_EL3;
 pub use vmpidr_el2::VMPIDR_EL2;
 pub use vtcr_el2::VTCR_EL2;
 pub use vttbr_el2::VTTBR_EL2;
+pub use zcr_el1::ZCR_EL1;
+pub use zcr_el2::ZCR_EL2;
+pub use zcr_el3::ZCR_EL3;
 
 #[doc(inline)]
 pub use tock_registers::interfaces::{ReadWriteable, Readable, Writeable};
@@ -17,14 +17,14 @@ impl Readable for Reg {
     type T = u64;
     type R = ();
 
-    sys_coproc_read_raw!(u64, "APIBKeyHi_EL1", "x");
+    sys_coproc_read_raw!(u64, "S3_0_C2_C1_3", "x");
 }
 
 impl Writeable for Reg {
     type T = u64;
     type R = ();
 
-    sys_coproc_write_raw!(u64, "APIBKeyHi_EL1", "x");
+    sys_coproc_write_raw!(u64, "S3_0_C2_C1_3", "x");
 }
 
 pub const APIBKEYHI_EL1: Reg = Reg {};
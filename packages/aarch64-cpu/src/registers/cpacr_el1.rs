@@ -7,7 +7,8 @@
 
 //! Architectural Feature Access Control Register - EL1
 //!
-//! Controls access to trace, SVE, and Advanced SIMD and floating-point functionality.
+//! Controls access to trace, SME, Streaming SVE, SVE, and Advanced SIMD and floating-point
+//! functionality.
 
 use tock_registers::{
     interfaces::{Readable, Writeable},
@@ -47,6 +48,28 @@ register_bitfields! {u64,
             TrapTrace = 0b1
         ],
 
+        /// **When FEAT_SME is implemented:**
+        ///
+        /// Traps execution at EL0 and EL1 of SME and Streaming SVE instructions, and of
+        /// instructions that directly access the SVCR or SMCR_EL1 System registers, to EL1, or
+        /// to EL2 when EL2 is implemented and enabled in the current Security state and
+        /// HCR_EL2.TGE is 1. The exception is reported using ESR_ELx.EC value 0x1D.
+        ///
+        /// **Otherwise:**
+        ///
+        /// Reserved.
+        SMEN OFFSET(24) NUMBITS(2) [
+            /// This control causes execution of these instructions at EL0 and EL1 to be trapped.
+            TrapEl0El1 = 0b00,
+            /// This control causes execution of these instructions at EL0 to be trapped, but
+            /// does not cause execution of any instructions at EL1 to be trapped.
+            TrapEl0 = 0b01,
+            /// This control causes execution of these instructions at EL1 and EL0 to be trapped.
+            TrapEl1El0 = 0b10,
+            /// This control does not cause execution of any instructions to be trapped.
+            TrapNothing = 0b11
+        ],
+
         /// Traps execution at EL0 and EL1 of instructions that access the Advanced SIMD
         /// and floating-point registers from both Execution states to EL1, reported using
         /// ESR_ELx.EC value 0x07, or to EL2 reported using ESR_ELx.EC value 0x00 when EL2
@@ -9,6 +9,12 @@
 //!
 //! Controls trapping to EL2 of accesses to CPACR, CPACR_EL1, trace, Activity Monitor, SME,
 //! Streaming SVE, SVE, and Advanced SIMD and floating-point functionality.
+//!
+//! The layout of the register depends on `HCR_EL2.E2H`: [`CPTR_EL2`] describes the layout when
+//! `HCR_EL2.E2H` is 0 and [`CPTR_EL2_E2H`] the layout when it is 1. Both access the same register.
+//!
+//! When `HCR_EL2.E2H` is 0, bits 13, 9 and 7:0 are RES1, as are TSM and TZ when SME and SVE are
+//! not implemented. Use `modify` rather than `write` to keep them set.
 
 use tock_registers::{
     interfaces::{Readable, Writeable},
@@ -17,6 +23,14 @@ use tock_registers::{
 
 register_bitfields! {u64,
     pub CPTR_EL2 [
+        /// Traps EL1 accesses to CPACR_EL1 to EL2, when EL2 is enabled in the current Security
+        /// state.
+        ///
+        /// 0 This control does not cause any instructions to be trapped.
+        ///
+        /// 1 EL1 accesses to CPACR_EL1 are trapped to EL2.
+        TCPAC OFFSET(31) NUMBITS(1) [],
+
         /// Trap Activity Monitor access. Traps EL1 and EL0 accesses to all Activity Monitor
         /// registers to EL2.
         ///
@@ -25,6 +39,119 @@ register_bitfields! {u64,
         /// 1 Accesses from EL1 and EL0 to Activity Monitor registers are trapped to EL2,
         /// when EL2 is enabled in the current Security state.
         TAM  OFFSET(30) NUMBITS(1) [],
+
+        /// Traps System register accesses to all implemented trace registers to EL2, from EL2,
+        /// and from EL1 and EL0 when EL2 is enabled in the current Security state.
+        TTA OFFSET(20) NUMBITS(1) [
+            NoTrap = 0,
+            Trap = 1
+        ],
+
+        /// **When FEAT_SME is implemented:**
+        ///
+        /// Traps execution of SME and Streaming SVE instructions, and of instructions that
+        /// directly access the SVCR, SMCR_EL1 or SMCR_EL2 System registers, to EL2. The exception
+        /// is reported using ESR_EL2.EC value 0x1D.
+        ///
+        /// **Otherwise:**
+        ///
+        /// Reserved, RES1.
+        TSM OFFSET(12) NUMBITS(1) [
+            NoTrap = 0,
+            Trap = 1
+        ],
+
+        /// Traps execution of instructions which access the Advanced SIMD and floating-point
+        /// functionality, from both Execution states, to EL2. The exception is reported using
+        /// ESR_EL2.EC value 0x07.
+        ///
+        /// This also traps SVE instructions, with lower priority than a trap due to TZ.
+        TFP OFFSET(10) NUMBITS(1) [
+            NoTrap = 0,
+            Trap = 1
+        ],
+
+        /// **When FEAT_SVE is implemented:**
+        ///
+        /// Traps execution of SVE instructions, and of instructions that directly access the
+        /// ZCR_EL2 or ZCR_EL1 System registers, to EL2. The exception is reported using
+        /// ESR_EL2.EC value 0x19.
+        ///
+        /// **Otherwise:**
+        ///
+        /// Reserved, RES1.
+        TZ OFFSET(8) NUMBITS(1) [
+            NoTrap = 0,
+            Trap = 1
+        ]
+    ],
+
+    pub CPTR_EL2_E2H [
+        /// Traps EL1 accesses to CPACR_EL1 to EL2, when EL2 is enabled in the current Security
+        /// state and HCR_EL2.TGE is 0.
+        TCPAC OFFSET(31) NUMBITS(1) [],
+
+        /// Trap Activity Monitor access. Traps EL1 and EL0 accesses to all Activity Monitor
+        /// registers to EL2, when EL2 is enabled in the current Security state.
+        TAM OFFSET(30) NUMBITS(1) [],
+
+        /// Traps System register accesses to all implemented trace registers to EL2, from EL2,
+        /// and from EL0 when HCR_EL2.TGE is 1.
+        TTA OFFSET(28) NUMBITS(1) [
+            NoTrap = 0,
+            Trap = 1
+        ],
+
+        /// **When FEAT_SME is implemented:**
+        ///
+        /// Traps execution of SME and Streaming SVE instructions, and of instructions that
+        /// directly access the SVCR, SMCR_EL1 or SMCR_EL2 System registers, to EL2. The exception
+        /// is reported using ESR_EL2.EC value 0x1D.
+        ///
+        /// **Otherwise:**
+        ///
+        /// Reserved.
+        SMEN OFFSET(24) NUMBITS(2) [
+            /// Execution at EL2, and at EL0 when HCR_EL2.TGE is 1, is trapped.
+            TrapEl2El0 = 0b00,
+            /// Execution at EL0 is trapped when HCR_EL2.TGE is 1. Execution at EL2 is not
+            /// trapped.
+            TrapEl0 = 0b01,
+            /// This control does not cause execution of any instructions to be trapped.
+            TrapNothing = 0b11
+        ],
+
+        /// Traps execution of instructions which access the Advanced SIMD and floating-point
+        /// functionality, and of SVE instructions, to EL2. The exception is reported using
+        /// ESR_EL2.EC value 0x07.
+        FPEN OFFSET(20) NUMBITS(2) [
+            /// Execution at EL2, and at EL0 when HCR_EL2.TGE is 1, is trapped.
+            TrapEl2El0 = 0b00,
+            /// Execution at EL0 is trapped when HCR_EL2.TGE is 1. Execution at EL2 is not
+            /// trapped.
+            TrapEl0 = 0b01,
+            /// This control does not cause execution of any instructions to be trapped.
+            TrapNothing = 0b11
+        ],
+
+        /// **When FEAT_SVE is implemented:**
+        ///
+        /// Traps execution of SVE instructions, and of instructions that directly access the
+        /// ZCR_EL2 or ZCR_EL1 System registers, to EL2. The exception is reported using
+        /// ESR_EL2.EC value 0x19.
+        ///
+        /// **Otherwise:**
+        ///
+        /// Reserved.
+        ZEN OFFSET(16) NUMBITS(2) [
+            /// Execution at EL2, and at EL0 when HCR_EL2.TGE is 1, is trapped.
+            TrapEl2El0 = 0b00,
+            /// Execution at EL0 is trapped when HCR_EL2.TGE is 1. Execution at EL2 is not
+            /// trapped.
+            TrapEl0 = 0b01,
+            /// This control does not cause execution of any instructions to be trapped.
+            TrapNothing = 0b11
+        ]
     ]
 }
 
@@ -44,4 +171,21 @@ impl Writeable for Reg {
     sys_coproc_write_raw!(u64, "CPTR_EL2", "x");
 }
 
+pub struct RegE2h;
+
+impl Readable for RegE2h {
+    type T = u64;
+    type R = CPTR_EL2_E2H::Register;
+
+    sys_coproc_read_raw!(u64, "CPTR_EL2", "x");
+}
+
+impl Writeable for RegE2h {
+    type T = u64;
+    type R = CPTR_EL2_E2H::Register;
+
+    sys_coproc_write_raw!(u64, "CPTR_EL2", "x");
+}
+
 pub const CPTR_EL2: Reg = Reg {};
+pub const CPTR_EL2_E2H: RegE2h = RegE2h {};
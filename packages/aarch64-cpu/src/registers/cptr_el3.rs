@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Architectural Feature Trap Register - EL3
+//!
+//! Controls trapping to EL3 of accesses to CPACR, CPACR_EL1, CPTR_EL2, trace, Activity Monitor,
+//! SME, Streaming SVE, SVE, and Advanced SIMD and floating-point functionality.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub CPTR_EL3 [
+        /// Traps EL2 accesses to CPTR_EL2, and EL2 and EL1 accesses to CPACR_EL1, to EL3.
+        TCPAC OFFSET(31) NUMBITS(1) [],
+
+        /// Trap Activity Monitor access. Traps EL2, EL1 and EL0 accesses to all Activity Monitor
+        /// registers to EL3.
+        TAM OFFSET(30) NUMBITS(1) [],
+
+        /// Traps System register accesses to all implemented trace registers to EL3.
+        TTA OFFSET(20) NUMBITS(1) [
+            NoTrap = 0,
+            Trap = 1
+        ],
+
+        /// **When FEAT_SME is implemented:**
+        ///
+        /// Enables execution of SME and Streaming SVE instructions, and of instructions that
+        /// directly access the SMCR_EL3, SMCR_EL2, SMCR_EL1 or SVCR System registers, at all
+        /// Exception levels. When disabled, they are trapped to EL3 and reported using
+        /// ESR_EL3.EC value 0x1D.
+        ///
+        /// **Otherwise:**
+        ///
+        /// Reserved.
+        ESM OFFSET(12) NUMBITS(1) [
+            Trap = 0,
+            NoTrap = 1
+        ],
+
+        /// Traps execution of instructions which access the Advanced SIMD and floating-point
+        /// functionality, from all Exception levels, to EL3. The exception is reported using
+        /// ESR_EL3.EC value 0x07.
+        TFP OFFSET(10) NUMBITS(1) [
+            NoTrap = 0,
+            Trap = 1
+        ],
+
+        /// **When FEAT_SVE is implemented:**
+        ///
+        /// Enables execution of SVE instructions, and of instructions that directly access the
+        /// ZCR_EL3, ZCR_EL2 or ZCR_EL1 System registers, at all Exception levels. When disabled,
+        /// they are trapped to EL3 and reported using ESR_EL3.EC value 0x19.
+        ///
+        /// **Otherwise:**
+        ///
+        /// Reserved.
+        EZ OFFSET(8) NUMBITS(1) [
+            Trap = 0,
+            NoTrap = 1
+        ]
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = CPTR_EL3::Register;
+
+    sys_coproc_read_raw!(u64, "CPTR_EL3", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = CPTR_EL3::Register;
+
+    sys_coproc_write_raw!(u64, "CPTR_EL3", "x");
+}
+
+pub const CPTR_EL3: Reg = Reg {};
@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Debug Breakpoint Control Registers - EL1
+//!
+//! DBGBCR<n>_EL1 holds control information for breakpoint n. All the registers share the
+//! `DBGBCR_EL1` field definitions.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub DBGBCR_EL1 [
+        /// Breakpoint Type.
+        BT OFFSET(20) NUMBITS(4) [
+            UnlinkedAddressMatch = 0b0000,
+            LinkedAddressMatch = 0b0001,
+            UnlinkedContextIdMatch = 0b0010,
+            LinkedContextIdMatch = 0b0011,
+            UnlinkedAddressMismatch = 0b0100,
+            LinkedAddressMismatch = 0b0101,
+            UnlinkedContextId1Match = 0b0110,
+            LinkedContextId1Match = 0b0111,
+            UnlinkedVmidMatch = 0b1000,
+            LinkedVmidMatch = 0b1001,
+            UnlinkedVmidContextIdMatch = 0b1010,
+            LinkedVmidContextIdMatch = 0b1011,
+            UnlinkedContextId2Match = 0b1100,
+            LinkedContextId2Match = 0b1101,
+            UnlinkedFullContextIdMatch = 0b1110,
+            LinkedFullContextIdMatch = 0b1111
+        ],
+
+        /// Linked Breakpoint Number. For linked matching, the number of the breakpoint that this
+        /// comparator is linked to.
+        LBN OFFSET(16) NUMBITS(4) [],
+
+        /// Security State Control. Determines the Security states in which a match can occur,
+        /// together with HMC and PMC.
+        SSC OFFSET(14) NUMBITS(2) [],
+
+        /// Higher Mode Control. Determines the debug perspective for deciding when a debug event
+        /// is generated, together with SSC and PMC.
+        HMC OFFSET(13) NUMBITS(1) [],
+
+        /// Byte address select. For address matching, defines which half-words an A64
+        /// instruction must start at to match. Must be 0b1111 for A64 instructions and for
+        /// context matching.
+        BAS OFFSET(5) NUMBITS(4) [],
+
+        /// Privilege mode control. Determines the Exception level or levels at which a breakpoint
+        /// debug event is generated, together with SSC and HMC.
+        PMC OFFSET(1) NUMBITS(2) [],
+
+        /// Enable breakpoint DBGBVR<n>_EL1.
+        E OFFSET(0) NUMBITS(1) [
+            Disabled = 0,
+            Enabled = 1
+        ]
+    ]
+}
+
+macro_rules! dbgbcr {
+    ($($reg:ident: $name:ident = $asm:literal;)*) => {
+        $(
+            pub struct $reg;
+
+            impl Readable for $reg {
+                type T = u64;
+                type R = DBGBCR_EL1::Register;
+
+                sys_coproc_read_raw!(u64, $asm, "x");
+            }
+
+            impl Writeable for $reg {
+                type T = u64;
+                type R = DBGBCR_EL1::Register;
+
+                sys_coproc_write_raw!(u64, $asm, "x");
+            }
+
+            pub const $name: $reg = $reg {};
+        )*
+    };
+}
+
+dbgbcr! {
+    Reg0: DBGBCR0_EL1 = "DBGBCR0_EL1";
+    Reg1: DBGBCR1_EL1 = "DBGBCR1_EL1";
+    Reg2: DBGBCR2_EL1 = "DBGBCR2_EL1";
+    Reg3: DBGBCR3_EL1 = "DBGBCR3_EL1";
+    Reg4: DBGBCR4_EL1 = "DBGBCR4_EL1";
+    Reg5: DBGBCR5_EL1 = "DBGBCR5_EL1";
+    Reg6: DBGBCR6_EL1 = "DBGBCR6_EL1";
+    Reg7: DBGBCR7_EL1 = "DBGBCR7_EL1";
+    Reg8: DBGBCR8_EL1 = "DBGBCR8_EL1";
+    Reg9: DBGBCR9_EL1 = "DBGBCR9_EL1";
+    Reg10: DBGBCR10_EL1 = "DBGBCR10_EL1";
+    Reg11: DBGBCR11_EL1 = "DBGBCR11_EL1";
+    Reg12: DBGBCR12_EL1 = "DBGBCR12_EL1";
+    Reg13: DBGBCR13_EL1 = "DBGBCR13_EL1";
+    Reg14: DBGBCR14_EL1 = "DBGBCR14_EL1";
+    Reg15: DBGBCR15_EL1 = "DBGBCR15_EL1";
+}
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Debug Breakpoint Value Registers - EL1
+//!
+//! DBGBVR<n>_EL1 holds a virtual address, or a Context ID and/or VMID, for use in breakpoint
+//! matching. It forms breakpoint n together with DBGBCR<n>_EL1. Only the breakpoints below
+//! ID_AA64DFR0_EL1.BRPs + 1 are implemented.
+//!
+//! For address matching, bits \[1:0\] are RES0 and the bits above the implemented virtual address
+//! size must be a sign extension of the top implemented bit.
+
+use tock_registers::interfaces::{Readable, Writeable};
+
+macro_rules! dbgbvr {
+    ($($reg:ident: $name:ident = $asm:literal;)*) => {
+        $(
+            pub struct $reg;
+
+            impl Readable for $reg {
+                type T = u64;
+                type R = ();
+
+                sys_coproc_read_raw!(u64, $asm, "x");
+            }
+
+            impl Writeable for $reg {
+                type T = u64;
+                type R = ();
+
+                sys_coproc_write_raw!(u64, $asm, "x");
+            }
+
+            pub const $name: $reg = $reg {};
+        )*
+    };
+}
+
+dbgbvr! {
+    Reg0: DBGBVR0_EL1 = "DBGBVR0_EL1";
+    Reg1: DBGBVR1_EL1 = "DBGBVR1_EL1";
+    Reg2: DBGBVR2_EL1 = "DBGBVR2_EL1";
+    Reg3: DBGBVR3_EL1 = "DBGBVR3_EL1";
+    Reg4: DBGBVR4_EL1 = "DBGBVR4_EL1";
+    Reg5: DBGBVR5_EL1 = "DBGBVR5_EL1";
+    Reg6: DBGBVR6_EL1 = "DBGBVR6_EL1";
+    Reg7: DBGBVR7_EL1 = "DBGBVR7_EL1";
+    Reg8: DBGBVR8_EL1 = "DBGBVR8_EL1";
+    Reg9: DBGBVR9_EL1 = "DBGBVR9_EL1";
+    Reg10: DBGBVR10_EL1 = "DBGBVR10_EL1";
+    Reg11: DBGBVR11_EL1 = "DBGBVR11_EL1";
+    Reg12: DBGBVR12_EL1 = "DBGBVR12_EL1";
+    Reg13: DBGBVR13_EL1 = "DBGBVR13_EL1";
+    Reg14: DBGBVR14_EL1 = "DBGBVR14_EL1";
+    Reg15: DBGBVR15_EL1 = "DBGBVR15_EL1";
+}
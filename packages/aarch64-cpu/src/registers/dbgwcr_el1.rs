@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Debug Watchpoint Control Registers - EL1
+//!
+//! DBGWCR<n>_EL1 holds control information for watchpoint n. All the registers share the
+//! `DBGWCR_EL1` field definitions.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub DBGWCR_EL1 [
+        /// Address mask. Only objects up to 2GB can be watched using a single mask.
+        ///
+        /// 0b00000 No mask. 0b00001 and 0b00010 are reserved. Otherwise the number of low-order
+        /// address bits that are masked out of the comparison, from 3 (8 bytes) to 31 (2GB).
+        MASK OFFSET(24) NUMBITS(5) [],
+
+        /// Watchpoint type.
+        WT OFFSET(20) NUMBITS(1) [
+            Unlinked = 0,
+            Linked = 1
+        ],
+
+        /// Linked Breakpoint Number. For linked matching, the number of the breakpoint that this
+        /// comparator is linked to.
+        LBN OFFSET(16) NUMBITS(4) [],
+
+        /// Security State Control. Determines the Security states in which a match can occur,
+        /// together with HMC and PMC.
+        SSC OFFSET(14) NUMBITS(2) [],
+
+        /// Higher Mode Control. Determines the debug perspective for deciding when a debug event
+        /// is generated, together with SSC and PMC.
+        HMC OFFSET(13) NUMBITS(1) [],
+
+        /// Byte address select. Each bit of this field selects whether a byte from within the
+        /// word or double-word addressed by DBGWVR<n>_EL1 is being watched. Must be 0b1111_1111
+        /// when MASK is used.
+        BAS OFFSET(5) NUMBITS(8) [],
+
+        /// Load/store control. Controls which types of access trigger the watchpoint.
+        LSC OFFSET(3) NUMBITS(2) [
+            Load = 0b01,
+            Store = 0b10,
+            LoadStore = 0b11
+        ],
+
+        /// Privilege of access control. Determines the Exception level or levels at which a
+        /// watchpoint debug event is generated, together with SSC and HMC.
+        PAC OFFSET(1) NUMBITS(2) [],
+
+        /// Enable watchpoint n.
+        E OFFSET(0) NUMBITS(1) [
+            Disabled = 0,
+            Enabled = 1
+        ]
+    ]
+}
+
+macro_rules! dbgwcr {
+    ($($reg:ident: $name:ident = $asm:literal;)*) => {
+        $(
+            pub struct $reg;
+
+            impl Readable for $reg {
+                type T = u64;
+                type R = DBGWCR_EL1::Register;
+
+                sys_coproc_read_raw!(u64, $asm, "x");
+            }
+
+            impl Writeable for $reg {
+                type T = u64;
+                type R = DBGWCR_EL1::Register;
+
+                sys_coproc_write_raw!(u64, $asm, "x");
+            }
+
+            pub const $name: $reg = $reg {};
+        )*
+    };
+}
+
+dbgwcr! {
+    Reg0: DBGWCR0_EL1 = "DBGWCR0_EL1";
+    Reg1: DBGWCR1_EL1 = "DBGWCR1_EL1";
+    Reg2: DBGWCR2_EL1 = "DBGWCR2_EL1";
+    Reg3: DBGWCR3_EL1 = "DBGWCR3_EL1";
+    Reg4: DBGWCR4_EL1 = "DBGWCR4_EL1";
+    Reg5: DBGWCR5_EL1 = "DBGWCR5_EL1";
+    Reg6: DBGWCR6_EL1 = "DBGWCR6_EL1";
+    Reg7: DBGWCR7_EL1 = "DBGWCR7_EL1";
+    Reg8: DBGWCR8_EL1 = "DBGWCR8_EL1";
+    Reg9: DBGWCR9_EL1 = "DBGWCR9_EL1";
+    Reg10: DBGWCR10_EL1 = "DBGWCR10_EL1";
+    Reg11: DBGWCR11_EL1 = "DBGWCR11_EL1";
+    Reg12: DBGWCR12_EL1 = "DBGWCR12_EL1";
+    Reg13: DBGWCR13_EL1 = "DBGWCR13_EL1";
+    Reg14: DBGWCR14_EL1 = "DBGWCR14_EL1";
+    Reg15: DBGWCR15_EL1 = "DBGWCR15_EL1";
+}
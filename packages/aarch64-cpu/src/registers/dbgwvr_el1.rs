@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Debug Watchpoint Value Registers - EL1
+//!
+//! DBGWVR<n>_EL1 holds a data address value for use in watchpoint matching. It forms watchpoint n
+//! together with DBGWCR<n>_EL1. Only the watchpoints below ID_AA64DFR0_EL1.WRPs + 1 are
+//! implemented.
+//!
+//! Bits \[1:0\] are RES0 and the bits above the implemented virtual address size must be a sign
+//! extension of the top implemented bit.
+
+use tock_registers::interfaces::{Readable, Writeable};
+
+macro_rules! dbgwvr {
+    ($($reg:ident: $name:ident = $asm:literal;)*) => {
+        $(
+            pub struct $reg;
+
+            impl Readable for $reg {
+                type T = u64;
+                type R = ();
+
+                sys_coproc_read_raw!(u64, $asm, "x");
+            }
+
+            impl Writeable for $reg {
+                type T = u64;
+                type R = ();
+
+                sys_coproc_write_raw!(u64, $asm, "x");
+            }
+
+            pub const $name: $reg = $reg {};
+        )*
+    };
+}
+
+dbgwvr! {
+    Reg0: DBGWVR0_EL1 = "DBGWVR0_EL1";
+    Reg1: DBGWVR1_EL1 = "DBGWVR1_EL1";
+    Reg2: DBGWVR2_EL1 = "DBGWVR2_EL1";
+    Reg3: DBGWVR3_EL1 = "DBGWVR3_EL1";
+    Reg4: DBGWVR4_EL1 = "DBGWVR4_EL1";
+    Reg5: DBGWVR5_EL1 = "DBGWVR5_EL1";
+    Reg6: DBGWVR6_EL1 = "DBGWVR6_EL1";
+    Reg7: DBGWVR7_EL1 = "DBGWVR7_EL1";
+    Reg8: DBGWVR8_EL1 = "DBGWVR8_EL1";
+    Reg9: DBGWVR9_EL1 = "DBGWVR9_EL1";
+    Reg10: DBGWVR10_EL1 = "DBGWVR10_EL1";
+    Reg11: DBGWVR11_EL1 = "DBGWVR11_EL1";
+    Reg12: DBGWVR12_EL1 = "DBGWVR12_EL1";
+    Reg13: DBGWVR13_EL1 = "DBGWVR13_EL1";
+    Reg14: DBGWVR14_EL1 = "DBGWVR14_EL1";
+    Reg15: DBGWVR15_EL1 = "DBGWVR15_EL1";
+}
@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Data Cache Zero ID Register - EL0
+//!
+//! Indicates the block size that is written with byte values of 0 by the `DC ZVA` (Data Cache
+//! Zero by Address) System instruction.
+
+use tock_registers::{interfaces::Readable, register_bitfields};
+
+register_bitfields! {u64,
+    pub DCZID_EL0 [
+        /// Data Zero Prohibited. Indicates whether use of `DC ZVA`, `DC GVA` and `DC GZVA`
+        /// instructions is permitted or prohibited.
+        DZP OFFSET(4) NUMBITS(1) [
+            Permitted = 0,
+            Prohibited = 1
+        ],
+
+        /// Log2 of the block size in words. The maximum size supported is 2KB (value == 9).
+        BS OFFSET(0) NUMBITS(4) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = DCZID_EL0::Register;
+
+    sys_coproc_read_raw!(u64, "DCZID_EL0", "x");
+}
+
+pub const DCZID_EL0: Reg = Reg {};
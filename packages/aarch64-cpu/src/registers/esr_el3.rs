@@ -120,6 +120,14 @@ register_bitfields! {u64,
 
 pub struct Reg;
 
+impl Reg {
+    /// Reads [`ESR_EL3`] and decodes it into a [`Syndrome`](crate::syndrome::Syndrome).
+    #[inline(always)]
+    pub fn get_syndrome(&self) -> crate::syndrome::Syndrome {
+        crate::syndrome::Syndrome::decode(self.get())
+    }
+}
+
 impl Readable for Reg {
     type T = u64;
     type R = ESR_EL3::Register;
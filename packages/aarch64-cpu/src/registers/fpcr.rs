@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Floating-point Control Register
+//!
+//! Controls floating-point behavior: rounding, denormal and NaN handling, and which IEEE 754
+//! floating-point exceptions are trapped instead of only being accumulated in FPSR.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub FPCR [
+        /// Alternative half-precision control bit.
+        ///
+        /// 0 IEEE half-precision format selected.
+        ///
+        /// 1 Alternative half-precision format selected.
+        AHP OFFSET(26) NUMBITS(1) [
+            IEEE = 0,
+            Alternative = 1
+        ],
+
+        /// Default NaN use for NaN propagation.
+        ///
+        /// 0 NaN operands propagate through to the output of a floating-point operation.
+        ///
+        /// 1 Any operation involving one or more NaNs returns the Default NaN.
+        DN OFFSET(25) NUMBITS(1) [
+            Propagate = 0,
+            DefaultNaN = 1
+        ],
+
+        /// Flushing denormalized numbers to zero control bit, for single-precision and
+        /// double-precision operations.
+        FZ OFFSET(24) NUMBITS(1) [
+            Disabled = 0,
+            FlushToZero = 1
+        ],
+
+        /// Rounding Mode control field.
+        RMode OFFSET(22) NUMBITS(2) [
+            /// Round to Nearest
+            RN = 0b00,
+            /// Round towards Plus Infinity
+            RP = 0b01,
+            /// Round towards Minus Infinity
+            RM = 0b10,
+            /// Round towards Zero
+            RZ = 0b11
+        ],
+
+        /// Flushing denormalized numbers to zero control bit, for half-precision operations
+        /// (FEAT_FP16).
+        FZ16 OFFSET(19) NUMBITS(1) [
+            Disabled = 0,
+            FlushToZero = 1
+        ],
+
+        /// Input Denormal floating-point exception trap enable.
+        ///
+        /// The trap enable bits are RAZ/WI on implementations that do not support trapping of
+        /// floating-point exceptions.
+        IDE OFFSET(15) NUMBITS(1) [
+            Untrapped = 0,
+            Trapped = 1
+        ],
+
+        /// Inexact floating-point exception trap enable.
+        IXE OFFSET(12) NUMBITS(1) [
+            Untrapped = 0,
+            Trapped = 1
+        ],
+
+        /// Underflow floating-point exception trap enable.
+        UFE OFFSET(11) NUMBITS(1) [
+            Untrapped = 0,
+            Trapped = 1
+        ],
+
+        /// Overflow floating-point exception trap enable.
+        OFE OFFSET(10) NUMBITS(1) [
+            Untrapped = 0,
+            Trapped = 1
+        ],
+
+        /// Divide by Zero floating-point exception trap enable.
+        DZE OFFSET(9) NUMBITS(1) [
+            Untrapped = 0,
+            Trapped = 1
+        ],
+
+        /// Invalid Operation floating-point exception trap enable.
+        IOE OFFSET(8) NUMBITS(1) [
+            Untrapped = 0,
+            Trapped = 1
+        ]
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = FPCR::Register;
+
+    sys_coproc_read_raw!(u64, "S3_3_C4_C4_0", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = FPCR::Register;
+
+    sys_coproc_write_raw!(u64, "S3_3_C4_C4_0", "x");
+}
+
+pub const FPCR: Reg = Reg {};
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Floating-point Status Register
+//!
+//! Provides floating-point system status information. The cumulative exception bits are set
+//! when the corresponding IEEE 754 floating-point exception occurs and is not trapped, and stay
+//! set until software clears them.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub FPSR [
+        /// Cumulative saturation bit, Advanced SIMD only. Set when saturation has occurred.
+        QC OFFSET(27) NUMBITS(1) [],
+
+        /// Input Denormal cumulative floating-point exception bit.
+        IDC OFFSET(7) NUMBITS(1) [],
+
+        /// Inexact cumulative floating-point exception bit.
+        IXC OFFSET(4) NUMBITS(1) [],
+
+        /// Underflow cumulative floating-point exception bit.
+        UFC OFFSET(3) NUMBITS(1) [],
+
+        /// Overflow cumulative floating-point exception bit.
+        OFC OFFSET(2) NUMBITS(1) [],
+
+        /// Divide by Zero cumulative floating-point exception bit.
+        DZC OFFSET(1) NUMBITS(1) [],
+
+        /// Invalid Operation cumulative floating-point exception bit.
+        IOC OFFSET(0) NUMBITS(1) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = FPSR::Register;
+
+    sys_coproc_read_raw!(u64, "S3_3_C4_C4_1", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = FPSR::Register;
+
+    sys_coproc_write_raw!(u64, "S3_3_C4_C4_1", "x");
+}
+
+pub const FPSR: Reg = Reg {};
@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Tag Control Register - EL1
+//!
+//! Configures the generation of Allocation Tags by the IRG, ADDG and SUBG instructions (FEAT_MTE2).
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub GCR_EL1 [
+        /// Random Allocation Tag selection.
+        ///
+        /// 0 IRG generates tags with the pseudo-random algorithm seeded by RGSR_EL1.
+        ///
+        /// 1 IRG generates tags with an IMPLEMENTATION DEFINED algorithm, and RGSR_EL1 is not
+        /// used.
+        RRND OFFSET(16) NUMBITS(1) [],
+
+        /// Allocation Tag exclusion mask. Bit n set excludes tag n from the tags generated by
+        /// IRG, ADDG and SUBG.
+        Exclude OFFSET(0) NUMBITS(16) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = GCR_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "S3_0_C1_C0_6", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = GCR_EL1::Register;
+
+    sys_coproc_write_raw!(u64, "S3_0_C1_C0_6", "x");
+}
+
+pub const GCR_EL1: Reg = Reg {};
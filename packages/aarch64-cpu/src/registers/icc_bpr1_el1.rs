@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Interrupt Controller Binary Point Register 1 - EL1
+//!
+//! Defines the point at which the priority value fields split into two parts, the group priority
+//! field and the subpriority field. The group priority field determines Group 1 interrupt
+//! preemption.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub ICC_BPR1_EL1 [
+        /// The value of this field controls how the 8-bit interrupt priority field is split into a
+        /// group priority field, that determines interrupt preemption, and a subpriority field.
+        BinaryPoint OFFSET(0) NUMBITS(3) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = ICC_BPR1_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "ICC_BPR1_EL1", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = ICC_BPR1_EL1::Register;
+
+    sys_coproc_write_raw!(u64, "ICC_BPR1_EL1", "x");
+}
+
+pub const ICC_BPR1_EL1: Reg = Reg {};
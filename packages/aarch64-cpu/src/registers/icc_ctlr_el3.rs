@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Interrupt Controller Control Register - EL3
+//!
+//! Controls aspects of the behavior of the GIC CPU interface and provides information about the
+//! features implemented.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub ICC_CTLR_EL3 [
+        /// Extended INTID range (read-only).
+        ExtRange OFFSET(19) NUMBITS(1) [],
+
+        /// Range Selector Support (read-only). Indicates whether the SGI generation registers can
+        /// target Aff0 values above 15.
+        RSS OFFSET(18) NUMBITS(1) [],
+
+        /// No Disable Security (read-only). Indicates whether GICD_CTLR.DS can be set.
+        nDS OFFSET(17) NUMBITS(1) [],
+
+        /// Affinity 3 Valid (read-only). Indicates whether the CPU interface supports non-zero
+        /// values of the Aff3 field in the SGI generation registers.
+        A3V OFFSET(15) NUMBITS(1) [],
+
+        /// SEI Support (read-only). Indicates whether the CPU interface supports local generation
+        /// of SEIs.
+        SEIS OFFSET(14) NUMBITS(1) [],
+
+        /// Identifier bits (read-only). The number of physical interrupt identifier bits
+        /// supported.
+        IDbits OFFSET(11) NUMBITS(3) [
+            Bits16 = 0b000,
+            Bits24 = 0b001
+        ],
+
+        /// Priority bits (read-only). The number of priority bits implemented, minus one.
+        PRIbits OFFSET(8) NUMBITS(3) [],
+
+        /// Priority Mask Hint Enable.
+        PMHE OFFSET(6) NUMBITS(1) [],
+
+        /// Routing Modifier.
+        RM OFFSET(5) NUMBITS(1) [],
+
+        /// EOI mode for interrupts handled at Non-secure EL1 and EL2.
+        ///
+        /// 0 ICC_EOIR0_EL1 and ICC_EOIR1_EL1 provide both priority drop and interrupt
+        ///   deactivation functionality.
+        ///
+        /// 1 ICC_EOIR0_EL1 and ICC_EOIR1_EL1 provide priority drop functionality only.
+        ///   ICC_DIR_EL1 provides interrupt deactivation functionality.
+        EOImode_EL1NS OFFSET(4) NUMBITS(1) [],
+
+        /// EOI mode for interrupts handled at Secure EL1.
+        EOImode_EL1S OFFSET(3) NUMBITS(1) [],
+
+        /// EOI mode for interrupts handled at EL3.
+        EOImode_EL3 OFFSET(2) NUMBITS(1) [],
+
+        /// Common Binary Point Register, EL1 Non-secure. Controls whether the same register is
+        /// used for interrupt preemption of both Group 0 and Group 1 Non-secure interrupts.
+        CBPR_EL1NS OFFSET(1) NUMBITS(1) [],
+
+        /// Common Binary Point Register, EL1 Secure.
+        CBPR_EL1S OFFSET(0) NUMBITS(1) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = ICC_CTLR_EL3::Register;
+
+    sys_coproc_read_raw!(u64, "ICC_CTLR_EL3", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = ICC_CTLR_EL3::Register;
+
+    sys_coproc_write_raw!(u64, "ICC_CTLR_EL3", "x");
+}
+
+pub const ICC_CTLR_EL3: Reg = Reg {};
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Interrupt Controller Deactivate Interrupt Register - EL1
+//!
+//! When interrupt priority drop is separated from interrupt deactivation (ICC_CTLR_EL1.EOImode is
+//! 1), a write to this register deactivates the specified interrupt.
+
+use tock_registers::{interfaces::Writeable, register_bitfields};
+
+register_bitfields! {u64,
+    pub ICC_DIR_EL1 [
+        /// The INTID of the interrupt to be deactivated.
+        INTID OFFSET(0) NUMBITS(24) []
+    ]
+}
+
+pub struct Reg;
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = ICC_DIR_EL1::Register;
+
+    sys_coproc_write_raw!(u64, "ICC_DIR_EL1", "x");
+}
+
+pub const ICC_DIR_EL1: Reg = Reg {};
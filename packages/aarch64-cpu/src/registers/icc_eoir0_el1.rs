@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Interrupt Controller End Of Interrupt Register 0 - EL1
+//!
+//! A PE writes to this register to inform the CPU interface that it has completed the processing of
+//! the specified Group 0 interrupt.
+
+use tock_registers::{interfaces::Writeable, register_bitfields};
+
+register_bitfields! {u64,
+    pub ICC_EOIR0_EL1 [
+        /// The INTID of the interrupt, as returned by the corresponding read of ICC_IAR0_EL1.
+        INTID OFFSET(0) NUMBITS(24) []
+    ]
+}
+
+pub struct Reg;
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = ICC_EOIR0_EL1::Register;
+
+    sys_coproc_write_raw!(u64, "ICC_EOIR0_EL1", "x");
+}
+
+pub const ICC_EOIR0_EL1: Reg = Reg {};
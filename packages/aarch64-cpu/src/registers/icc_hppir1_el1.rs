@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Interrupt Controller Highest Priority Pending Interrupt Register 1 - EL1
+//!
+//! Indicates the highest priority pending Group 1 interrupt on the CPU interface.
+
+use tock_registers::{interfaces::Readable, register_bitfields};
+
+register_bitfields! {u64,
+    pub ICC_HPPIR1_EL1 [
+        /// The INTID of the highest priority pending interrupt, if that interrupt is observable at
+        /// the current Security state and Exception level.
+        INTID OFFSET(0) NUMBITS(24) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = ICC_HPPIR1_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "ICC_HPPIR1_EL1", "x");
+}
+
+pub const ICC_HPPIR1_EL1: Reg = Reg {};
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Interrupt Controller Interrupt Acknowledge Register 1 - EL1
+//!
+//! The PE reads this register to obtain the INTID of the signaled Group 1 interrupt. This read acts
+//! as an acknowledge for the interrupt.
+
+use tock_registers::{interfaces::Readable, register_bitfields};
+
+register_bitfields! {u64,
+    pub ICC_IAR1_EL1 [
+        /// The INTID of the signaled interrupt. Special INTIDs 1020-1023 indicate that no interrupt
+        /// was acknowledged.
+        INTID OFFSET(0) NUMBITS(24) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = ICC_IAR1_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "ICC_IAR1_EL1", "x");
+}
+
+pub const ICC_IAR1_EL1: Reg = Reg {};
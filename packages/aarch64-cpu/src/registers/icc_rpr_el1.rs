@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Interrupt Controller Running Priority Register - EL1
+//!
+//! Indicates the Running priority of the CPU interface.
+
+use tock_registers::{interfaces::Readable, register_bitfields};
+
+register_bitfields! {u64,
+    pub ICC_RPR_EL1 [
+        /// Non-maskable property of the current running priority (FEAT_GICv3_NMI).
+        NMI OFFSET(63) NUMBITS(1) [],
+
+        /// Non-secure non-maskable property of the current running priority (FEAT_GICv3_NMI).
+        NMI_NS OFFSET(62) NUMBITS(1) [],
+
+        /// The current running priority on the CPU interface. This is the group priority of the
+        /// current active interrupt, or 0xFF if there is no active interrupt.
+        Priority OFFSET(0) NUMBITS(8) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = ICC_RPR_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "ICC_RPR_EL1", "x");
+}
+
+pub const ICC_RPR_EL1: Reg = Reg {};
@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Interrupt Controller Software Generated Interrupt Group 0 Register - EL1
+//!
+//! Generates Secure Group 0 SGIs.
+
+use tock_registers::{interfaces::Writeable, register_bitfields};
+
+register_bitfields! {u64,
+    pub ICC_SGI0R_EL1 [
+        /// Affinity level 3 of the target PEs.
+        Aff3 OFFSET(48) NUMBITS(8) [],
+
+        /// RangeSelector. Controls which group of 16 values is represented by the TargetList
+        /// field: TargetList\[n\] represents Aff0 value `RS * 16 + n`.
+        RS OFFSET(44) NUMBITS(4) [],
+
+        /// Interrupt Routing Mode.
+        IRM OFFSET(40) NUMBITS(1) [
+            /// Interrupts routed to the PEs specified by Aff3.Aff2.Aff1.<target list>.
+            TargetList = 0,
+            /// Interrupts routed to all PEs in the system, excluding "self".
+            AllButSelf = 1
+        ],
+
+        /// Affinity level 2 of the target PEs.
+        Aff2 OFFSET(32) NUMBITS(8) [],
+
+        /// The INTID of the SGI.
+        INTID OFFSET(24) NUMBITS(4) [],
+
+        /// Affinity level 1 of the target PEs.
+        Aff1 OFFSET(16) NUMBITS(8) [],
+
+        /// Target List. The set of PEs for which SGI interrupts will be generated. Each bit
+        /// corresponds to the PE within a cluster with an Aff0 value `RS * 16 + bit`.
+        TargetList OFFSET(0) NUMBITS(16) []
+    ]
+}
+
+pub struct Reg;
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = ICC_SGI0R_EL1::Register;
+
+    sys_coproc_write_raw!(u64, "ICC_SGI0R_EL1", "x");
+}
+
+pub const ICC_SGI0R_EL1: Reg = Reg {};
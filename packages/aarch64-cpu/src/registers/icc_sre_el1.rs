@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Interrupt Controller System Register Enable register - EL1
+//!
+//! Controls whether the System register interface or the memory-mapped interface to the GIC CPU
+//! interface is used for EL0 and EL1.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub ICC_SRE_EL1 [
+        /// Disable IRQ bypass.
+        DIB OFFSET(2) NUMBITS(1) [],
+
+        /// Disable FIQ bypass.
+        DFB OFFSET(1) NUMBITS(1) [],
+
+        /// System Register Enable.
+        ///
+        /// 0 The memory-mapped interface must be used.
+        ///
+        /// 1 The System register interface for the current Security state is enabled.
+        SRE OFFSET(0) NUMBITS(1) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = ICC_SRE_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "ICC_SRE_EL1", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = ICC_SRE_EL1::Register;
+
+    sys_coproc_write_raw!(u64, "ICC_SRE_EL1", "x");
+}
+
+pub const ICC_SRE_EL1: Reg = Reg {};
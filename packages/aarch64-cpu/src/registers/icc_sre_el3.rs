@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Interrupt Controller System Register Enable register - EL3
+//!
+//! Controls whether the System register interface or the memory-mapped interface to the GIC CPU
+//! interface is used for EL3.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub ICC_SRE_EL3 [
+        /// Enables lower Exception level access to ICC_SRE_EL1 and ICC_SRE_EL2.
+        ///
+        /// 0 EL1 and EL2 accesses to ICC_SRE_EL1 or ICC_SRE_EL2 trap to EL3.
+        ///
+        /// 1 EL2 accesses to ICC_SRE_EL1 and ICC_SRE_EL2 do not trap to EL3.
+        ENABLE OFFSET(3) NUMBITS(1) [],
+
+        /// Disable IRQ bypass.
+        DIB OFFSET(2) NUMBITS(1) [],
+
+        /// Disable FIQ bypass.
+        DFB OFFSET(1) NUMBITS(1) [],
+
+        /// System Register Enable.
+        ///
+        /// 0 The memory-mapped interface must be used.
+        ///
+        /// 1 The System register interface for the current Security state is enabled.
+        SRE OFFSET(0) NUMBITS(1) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = ICC_SRE_EL3::Register;
+
+    sys_coproc_read_raw!(u64, "ICC_SRE_EL3", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = ICC_SRE_EL3::Register;
+
+    sys_coproc_write_raw!(u64, "ICC_SRE_EL3", "x");
+}
+
+pub const ICC_SRE_EL3: Reg = Reg {};
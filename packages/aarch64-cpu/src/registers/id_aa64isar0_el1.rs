@@ -23,6 +23,34 @@ register_bitfields! {u64,
             Supported = 0b0001,
             NotSupported = 0b0000
         ],
+
+        /// Indicates support for Outer Shareable and TLB range maintenance instructions.
+        ///
+        /// 0000 Outer Shareable and TLB range maintenance instructions are not implemented
+        /// 0001 Outer Shareable TLB maintenance instructions are implemented (FEAT_TLBIOS)
+        /// 0010 Outer Shareable and TLB range maintenance instructions are implemented
+        ///      (FEAT_TLBIOS and FEAT_TLBIRANGE)
+        ///
+        /// All other values are reserved.
+        TLB OFFSET(56) NUMBITS(4) [
+            NotSupported = 0b0000,
+            OuterShareable = 0b0001,
+            OuterShareableAndRange = 0b0010
+        ],
+
+        /// Indicates support for Atomic instructions in AArch64 state.
+        ///
+        /// 0000 No Atomic instructions implemented
+        /// 0010 LDADD, LDCLR, LDEOR, LDSET, LDSMAX, LDSMIN, LDUMAX, LDUMIN, CAS, CASP, and SWP
+        ///      instructions implemented (FEAT_LSE)
+        /// 0011 As 0010, adding 128-bit atomics (FEAT_LSE128)
+        ///
+        /// All other values are reserved.
+        Atomic OFFSET(20) NUMBITS(4) [
+            NotSupported = 0b0000,
+            LSE = 0b0010,
+            LSE128 = 0b0011
+        ],
     ]
 }
 
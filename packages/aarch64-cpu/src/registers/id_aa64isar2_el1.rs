@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! AArch64 Instruction Set Attribute Register 2 - EL1
+//!
+//! Provides information about the features and instructions implemented in AArch64 state.
+
+use tock_registers::{interfaces::Readable, register_bitfields};
+
+register_bitfields! {u64,
+    pub ID_AA64ISAR2_EL1 [
+        /// Indicates whether the QARMA3 algorithm is implemented in the PE for address
+        /// authentication, in AArch64 state. This applies to all Pointer Authentication
+        /// instructions other than the PACGA instruction.
+        APA3 OFFSET(12) NUMBITS(4) [],
+
+        /// Indicates whether the QARMA3 algorithm is implemented in the PE for generic code
+        /// authentication in AArch64 state.
+        GPA3 OFFSET(8) NUMBITS(4) [
+            NotSupported = 0b0000,
+            Supported = 0b0001
+        ],
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = ID_AA64ISAR2_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "S3_0_C0_C6_2", "x");
+}
+
+pub const ID_AA64ISAR2_EL1: Reg = Reg {};
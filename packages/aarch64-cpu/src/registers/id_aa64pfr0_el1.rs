@@ -13,11 +13,210 @@ use tock_registers::{interfaces::Readable, register_bitfields};
 
 register_bitfields! {u64,
     pub ID_AA64PFR0_EL1 [
-        /// Indicates support for Activity Monitors Extension.
-        AMU OFFSET(44) NUMBITS(4) [],
+        /// Speculative use of faulting data. Defined values are:
+        ///
+        /// 0000 Not disclosed whether data loaded under speculation with a permission or domain
+        ///      fault can be used to form an address or generate condition codes or SVE predicate
+        ///      values to be used by other instructions in the speculative sequence.
+        /// 0001 Data loaded under speculation with a permission or domain fault cannot be used to
+        ///      form an address, generate condition codes or SVE predicate values to be used by
+        ///      other instructions in the speculative sequence (FEAT_CSV3).
+        ///
+        /// All other values are reserved.
+        CSV3 OFFSET(60) NUMBITS(4) [
+            NotDisclosed = 0b0000,
+            Supported = 0b0001
+        ],
 
-        /// Scalable Vector Extension.
-        SVE OFFSET(32) NUMBITS(4) [],
+        /// Speculative use of out of context branch targets. Defined values are:
+        ///
+        /// 0000 Not disclosed whether the implementation limits the speculative use of branch
+        ///      targets trained in a different hardware context.
+        /// 0001 FEAT_CSV2 is implemented.
+        /// 0010 FEAT_CSV2_2 is implemented, adding `SCXTNUM_ELx`.
+        /// 0011 FEAT_CSV2_3 is implemented.
+        ///
+        /// All other values are reserved.
+        CSV2 OFFSET(56) NUMBITS(4) [
+            NotDisclosed = 0b0000,
+            CSV2 = 0b0001,
+            CSV2_2 = 0b0010,
+            CSV2_3 = 0b0011
+        ],
+
+        /// Realm Management Extension. Defined values are:
+        ///
+        /// 0000 Realm Management Extension not implemented.
+        /// 0001 RMEv1 is implemented.
+        ///
+        /// All other values are reserved.
+        RME OFFSET(52) NUMBITS(4) [
+            NotImplemented = 0b0000,
+            RMEv1 = 0b0001
+        ],
+
+        /// Data Independent Timing. Defined values are:
+        ///
+        /// 0000 The PE does not guarantee that the timing of instructions is independent of the
+        ///      data values.
+        /// 0001 `PSTATE.DIT` is implemented (FEAT_DIT).
+        ///
+        /// All other values are reserved.
+        DIT OFFSET(48) NUMBITS(4) [
+            NotImplemented = 0b0000,
+            Implemented = 0b0001
+        ],
+
+        /// Indicates support for Activity Monitors Extension. Defined values are:
+        ///
+        /// 0000 Activity Monitors Extension is not implemented.
+        /// 0001 FEAT_AMUv1 is implemented.
+        /// 0010 FEAT_AMUv1p1 is implemented, adding virtual offsets.
+        ///
+        /// All other values are reserved.
+        AMU OFFSET(44) NUMBITS(4) [
+            NotImplemented = 0b0000,
+            AMUv1 = 0b0001,
+            AMUv1p1 = 0b0010
+        ],
+
+        /// Major version of the Memory Partitioning and Monitoring Extension. Defined values are:
+        ///
+        /// 0000 The major version number of the MPAM extension is 0.
+        /// 0001 The major version number of the MPAM extension is 1.
+        ///
+        /// All other values are reserved. The minor version is reported in `ID_AA64PFR1_EL1`.
+        MPAM OFFSET(40) NUMBITS(4) [
+            Version0 = 0b0000,
+            Version1 = 0b0001
+        ],
+
+        /// Secure EL2. Defined values are:
+        ///
+        /// 0000 Secure EL2 is not implemented.
+        /// 0001 Secure EL2 is implemented (FEAT_SEL2).
+        ///
+        /// All other values are reserved.
+        SEL2 OFFSET(36) NUMBITS(4) [
+            NotImplemented = 0b0000,
+            Implemented = 0b0001
+        ],
+
+        /// Scalable Vector Extension. Defined values are:
+        ///
+        /// 0000 SVE architectural state and programmers' model are not implemented.
+        /// 0001 SVE architectural state and programmers' model are implemented (FEAT_SVE).
+        ///
+        /// All other values are reserved.
+        SVE OFFSET(32) NUMBITS(4) [
+            NotImplemented = 0b0000,
+            Implemented = 0b0001
+        ],
+
+        /// RAS Extension version. Defined values are:
+        ///
+        /// 0000 No RAS Extension.
+        /// 0001 RAS Extension implemented (FEAT_RAS).
+        /// 0010 FEAT_RASv1p1 is implemented.
+        /// 0011 FEAT_RASv2 is implemented.
+        ///
+        /// All other values are reserved.
+        RAS OFFSET(28) NUMBITS(4) [
+            NotImplemented = 0b0000,
+            RAS = 0b0001,
+            RASv1p1 = 0b0010,
+            RASv2 = 0b0011
+        ],
+
+        /// System register GIC CPU interface. Defined values are:
+        ///
+        /// 0000 GIC CPU interface system registers not implemented.
+        /// 0001 System register interface to versions 3.0 and 4.0 of the GIC CPU interface is
+        ///      supported.
+        /// 0011 System register interface to version 4.1 of the GIC CPU interface is supported.
+        ///
+        /// All other values are reserved.
+        GIC OFFSET(24) NUMBITS(4) [
+            NotImplemented = 0b0000,
+            GICv3 = 0b0001,
+            GICv4p1 = 0b0011
+        ],
+
+        /// Advanced SIMD. Defined values are:
+        ///
+        /// 0000 Advanced SIMD is implemented, including support for single-precision,
+        ///      double-precision and integer arithmetic.
+        /// 0001 As 0000, adding half-precision floating-point arithmetic (FEAT_FP16).
+        /// 1111 Advanced SIMD is not implemented.
+        ///
+        /// All other values are reserved.
+        AdvSIMD OFFSET(20) NUMBITS(4) [
+            Implemented = 0b0000,
+            ImplementedFP16 = 0b0001,
+            NotImplemented = 0b1111
+        ],
+
+        /// Floating-point. Defined values are:
+        ///
+        /// 0000 Floating-point is implemented, including support for single-precision and
+        ///      double-precision arithmetic.
+        /// 0001 As 0000, adding half-precision arithmetic (FEAT_FP16).
+        /// 1111 Floating-point is not implemented.
+        ///
+        /// All other values are reserved.
+        FP OFFSET(16) NUMBITS(4) [
+            Implemented = 0b0000,
+            ImplementedFP16 = 0b0001,
+            NotImplemented = 0b1111
+        ],
+
+        /// EL3 Exception level handling. Defined values are:
+        ///
+        /// 0000 EL3 is not implemented.
+        /// 0001 EL3 can be executed in AArch64 state only.
+        /// 0010 EL3 can be executed in either AArch64 or AArch32 state.
+        ///
+        /// All other values are reserved.
+        EL3 OFFSET(12) NUMBITS(4) [
+            NotImplemented = 0b0000,
+            AArch64 = 0b0001,
+            AArch64AndAArch32 = 0b0010
+        ],
+
+        /// EL2 Exception level handling. Defined values are:
+        ///
+        /// 0000 EL2 is not implemented.
+        /// 0001 EL2 can be executed in AArch64 state only.
+        /// 0010 EL2 can be executed in either AArch64 or AArch32 state.
+        ///
+        /// All other values are reserved.
+        EL2 OFFSET(8) NUMBITS(4) [
+            NotImplemented = 0b0000,
+            AArch64 = 0b0001,
+            AArch64AndAArch32 = 0b0010
+        ],
+
+        /// EL1 Exception level handling. Defined values are:
+        ///
+        /// 0001 EL1 can be executed in AArch64 state only.
+        /// 0010 EL1 can be executed in either AArch64 or AArch32 state.
+        ///
+        /// All other values are reserved.
+        EL1 OFFSET(4) NUMBITS(4) [
+            AArch64 = 0b0001,
+            AArch64AndAArch32 = 0b0010
+        ],
+
+        /// EL0 Exception level handling. Defined values are:
+        ///
+        /// 0001 EL0 can be executed in AArch64 state only.
+        /// 0010 EL0 can be executed in either AArch64 or AArch32 state.
+        ///
+        /// All other values are reserved.
+        EL0 OFFSET(0) NUMBITS(4) [
+            AArch64 = 0b0001,
+            AArch64AndAArch32 = 0b0010
+        ]
     ]
 }
 
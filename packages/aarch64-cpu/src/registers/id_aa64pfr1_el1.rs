@@ -13,8 +13,32 @@ use tock_registers::{interfaces::Readable, register_bitfields};
 
 register_bitfields! {u64,
     pub ID_AA64PFR1_EL1 [
+        /// Support for the Scalable Matrix Extension.
+        ///
+        /// 0000 SME is not implemented
+        /// 0001 SME is implemented (FEAT_SME)
+        /// 0010 SME2 is implemented (FEAT_SME2)
+        ///
+        /// All other values are reserved.
+        SME OFFSET(24) NUMBITS(4) [
+            NotImplemented = 0b0000,
+            SME = 0b0001,
+            SME2 = 0b0010
+        ],
+
         /// Support for the Memory Tagging Extension.
         MTE OFFSET(8) NUMBITS(4) [],
+
+        /// Support for Branch Target Identification.
+        ///
+        /// 0000 The Branch Target Identification mechanism is not implemented
+        /// 0001 The Branch Target Identification mechanism is implemented (FEAT_BTI)
+        ///
+        /// All other values are reserved.
+        BT OFFSET(0) NUMBITS(4) [
+            NotImplemented = 0b0000,
+            Implemented = 0b0001
+        ],
     ]
 }
 
@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! SME Feature ID Register 0
+//!
+//! Provides information about the implemented features of the AArch64 Scalable Matrix Extension,
+//! when FEAT_SME is implemented.
+
+use tock_registers::{interfaces::Readable, register_bitfields};
+
+register_bitfields! {u64,
+    pub ID_AA64SMFR0_EL1 [
+        /// The full A64 instruction set is supported in Streaming SVE mode (FEAT_SME_FA64).
+        FA64 OFFSET(63) NUMBITS(1) [],
+
+        /// Scalable Matrix Extension version.
+        SMEver OFFSET(56) NUMBITS(4) [
+            SME = 0b0000,
+            SME2 = 0b0001,
+            SME2p1 = 0b0010
+        ],
+
+        /// Support for the instructions that accumulate 16-bit integer outer products into 64-bit
+        /// integer tiles (FEAT_SME_I16I64), when `0b1111`.
+        I16I64 OFFSET(52) NUMBITS(4) [],
+
+        /// Support for the instructions that accumulate FP64 outer products into FP64 tiles
+        /// (FEAT_SME_F64F64).
+        F64F64 OFFSET(48) NUMBITS(1) [],
+
+        /// Support for the instructions that accumulate 16-bit integer outer products into 32-bit
+        /// integer tiles, when `0b0101`.
+        I16I32 OFFSET(44) NUMBITS(4) [],
+
+        /// Support for the SME2 non-widening BFloat16 instructions (FEAT_SME_B16B16).
+        B16B16 OFFSET(43) NUMBITS(1) [],
+
+        /// Support for the SME2 non-widening half-precision instructions (FEAT_SME_F16F16).
+        F16F16 OFFSET(42) NUMBITS(1) [],
+
+        /// Support for the instructions that accumulate 8-bit integer outer products into 32-bit
+        /// integer tiles, when `0b1111`.
+        I8I32 OFFSET(36) NUMBITS(4) [],
+
+        /// Support for the instructions that accumulate FP16 outer products into FP32 tiles.
+        F16F32 OFFSET(35) NUMBITS(1) [],
+
+        /// Support for the instructions that accumulate BFloat16 outer products into FP32 tiles.
+        B16F32 OFFSET(34) NUMBITS(1) [],
+
+        /// Support for the 1-bit binary outer product instructions that accumulate into 32-bit
+        /// integer tiles.
+        BI32I32 OFFSET(33) NUMBITS(1) [],
+
+        /// Support for the instructions that accumulate FP32 outer products into FP32 tiles.
+        F32F32 OFFSET(32) NUMBITS(1) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = ID_AA64SMFR0_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "S3_0_C0_C4_5", "x");
+}
+
+pub const ID_AA64SMFR0_EL1: Reg = Reg {};
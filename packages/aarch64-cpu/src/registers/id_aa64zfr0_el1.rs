@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! SVE Feature ID Register 0
+//!
+//! Provides additional information about the implemented features of the AArch64 Scalable Vector
+//! Extension, when FEAT_SVE or FEAT_SME is implemented.
+
+use tock_registers::{interfaces::Readable, register_bitfields};
+
+register_bitfields! {u64,
+    pub ID_AA64ZFR0_EL1 [
+        /// Support for the SVE FP64 double-precision floating-point matrix multiplication
+        /// instructions (FEAT_F64MM).
+        F64MM OFFSET(56) NUMBITS(4) [],
+
+        /// Support for the SVE FP32 single-precision floating-point matrix multiplication
+        /// instruction (FEAT_F32MM).
+        F32MM OFFSET(52) NUMBITS(4) [],
+
+        /// Support for the SVE Int8 matrix multiplication instructions (FEAT_I8MM).
+        I8MM OFFSET(44) NUMBITS(4) [],
+
+        /// Support for the SVE2 SM4 instructions (FEAT_SVE_SM4).
+        SM4 OFFSET(40) NUMBITS(4) [],
+
+        /// Support for the SVE2 SHA3 instructions (FEAT_SVE_SHA3).
+        SHA3 OFFSET(32) NUMBITS(4) [],
+
+        /// Support for the SVE non-widening BFloat16 instructions (FEAT_SVE_B16B16).
+        B16B16 OFFSET(24) NUMBITS(4) [],
+
+        /// Support for the SVE BFloat16 instructions.
+        ///
+        /// 0b0001 FEAT_BF16 is implemented
+        ///
+        /// 0b0010 FEAT_EBF16 is also implemented
+        BF16 OFFSET(20) NUMBITS(4) [],
+
+        /// Support for the SVE2 bit permute instructions (FEAT_SVE_BitPerm).
+        BitPerm OFFSET(16) NUMBITS(4) [],
+
+        /// Support for the SVE2 AES instructions.
+        AES OFFSET(4) NUMBITS(4) [
+            NotImplemented = 0b0000,
+            /// AESE, AESD, AESMC and AESIMC are implemented (FEAT_SVE_AES)
+            AES = 0b0001,
+            /// PMULLB and PMULLT with 128-bit destination elements are also implemented
+            /// (FEAT_SVE_PMULL128)
+            AESAndPMULL128 = 0b0010
+        ],
+
+        /// Scalable Vector Extension version.
+        SVEver OFFSET(0) NUMBITS(4) [
+            SVE = 0b0000,
+            SVE2 = 0b0001,
+            SVE2p1 = 0b0010
+        ]
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = ID_AA64ZFR0_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "S3_0_C0_C4_4", "x");
+}
+
+pub const ID_AA64ZFR0_EL1: Reg = Reg {};
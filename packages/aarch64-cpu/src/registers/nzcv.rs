@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Condition Flags
+//!
+//! Allows access to the condition flags.
+//!
+//! The compiler is free to change the flags between two separate register accesses, so reading
+//! the flags set by a specific instruction requires reading them in the same `asm!` block.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub NZCV [
+        /// Negative condition flag. Set to bit 31 of the result of the last flag-setting
+        /// instruction, i.e. 1 if the result, regarded as a two's complement signed integer, is
+        /// negative.
+        N OFFSET(31) NUMBITS(1) [],
+
+        /// Zero condition flag. Set to 1 if the result of the last flag-setting instruction was
+        /// zero.
+        Z OFFSET(30) NUMBITS(1) [],
+
+        /// Carry condition flag. Set to 1 if the last flag-setting instruction resulted in a
+        /// carry condition, for example an unsigned overflow on an addition.
+        C OFFSET(29) NUMBITS(1) [],
+
+        /// Overflow condition flag. Set to 1 if the last flag-setting instruction resulted in an
+        /// overflow condition, for example a signed overflow on an addition.
+        V OFFSET(28) NUMBITS(1) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = NZCV::Register;
+
+    sys_coproc_read_raw!(u64, "NZCV", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = NZCV::Register;
+
+    sys_coproc_write_raw!(u64, "NZCV", "x");
+}
+
+pub const NZCV: Reg = Reg {};
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! OS Double Lock Register - EL1
+//!
+//! Used to control the OS Double Lock, which locks out an external debugger while the PE is
+//! powered down.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub OSDLR_EL1 [
+        /// OS Double Lock control bit. When set and the PE is in Non-debug state, external debug
+        /// and self-hosted debug (other than software breakpoints) are disabled.
+        DLK OFFSET(0) NUMBITS(1) [
+            Unlocked = 0,
+            Locked = 1
+        ]
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = OSDLR_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "OSDLR_EL1", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = OSDLR_EL1::Register;
+
+    sys_coproc_write_raw!(u64, "OSDLR_EL1", "x");
+}
+
+pub const OSDLR_EL1: Reg = Reg {};
@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! OS Lock Status Register - EL1
+//!
+//! Provides the status of the OS Lock.
+
+use tock_registers::{interfaces::Readable, register_bitfields};
+
+register_bitfields! {u64,
+    pub OSLSR_EL1 [
+        /// OS Lock model implemented, bit\[1\]. Together with OSLM0, 0b10 indicates that the OS
+        /// Lock is implemented and OSLAR_EL1 is accessible.
+        OSLM1 OFFSET(3) NUMBITS(1) [],
+
+        /// Not 32-bit access. RAZ in AArch64 state.
+        nTT OFFSET(2) NUMBITS(1) [],
+
+        /// OS Lock Status. Use OSLAR_EL1 to lock or unlock the OS Lock.
+        OSLK OFFSET(1) NUMBITS(1) [
+            Unlocked = 0,
+            Locked = 1
+        ],
+
+        /// OS Lock model implemented, bit\[0\].
+        OSLM0 OFFSET(0) NUMBITS(1) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = OSLSR_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "OSLSR_EL1", "x");
+}
+
+pub const OSLSR_EL1: Reg = Reg {};
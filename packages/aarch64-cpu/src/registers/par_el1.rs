@@ -17,6 +17,12 @@ use tock_registers::{
 
 register_bitfields! {u64,
     pub PAR_EL1 [
+        /// Memory attributes for the returned output address, using the encoding of the MAIR_ELx
+        /// registers.
+        ///
+        /// Only valid when `F` is `TranslationSuccessfull`.
+        ATTR OFFSET(56) NUMBITS(8) [],
+
         /// Output address. The output address (OA) corresponding to the supplied input address.
         /// This field returns address bits[47:12].
         ///
@@ -30,6 +36,39 @@ register_bitfields! {u64,
         /// This field resets to an architecturally UNKNOWN value.
         PA OFFSET(12) NUMBITS(36) [],
 
+        /// Non-secure. For a translation that completed successfully in Secure state, indicates
+        /// whether the output address is in the Secure or Non-secure address space.
+        ///
+        /// Only valid when `F` is `TranslationSuccessfull`.
+        NS OFFSET(9) NUMBITS(1) [],
+
+        /// Shareability attribute of the output address.
+        ///
+        /// Only valid when `F` is `TranslationSuccessfull`.
+        SH OFFSET(7) NUMBITS(2) [
+            NonShareable = 0b00,
+            OuterShareable = 0b10,
+            InnerShareable = 0b11
+        ],
+
+        /// Indicates the translation stage at which the translation aborted.
+        ///
+        /// Only valid when `F` is `TranslationAborted`.
+        S OFFSET(9) NUMBITS(1) [
+            Stage1 = 0,
+            Stage2 = 1
+        ],
+
+        /// Indicates whether a stage 2 fault occurred during a stage 1 translation table walk.
+        ///
+        /// Only valid when `F` is `TranslationAborted`.
+        PTW OFFSET(8) NUMBITS(1) [],
+
+        /// Fault Status Code, using the encoding of the DFSC field of the ESR_ELx registers.
+        ///
+        /// Only valid when `F` is `TranslationAborted`.
+        FST OFFSET(1) NUMBITS(6) [],
+
         /// Indicates whether the instruction performed a successful address translation.
         ///
         /// 0 Address translation completed successfully.
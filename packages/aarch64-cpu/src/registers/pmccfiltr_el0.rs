@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Performance Monitors Cycle Count Filter Register - EL0
+//!
+//! Determines the modes in which the Cycle Counter, PMCCNTR_EL0, increments.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub PMCCFILTR_EL0 [
+        /// Privileged filtering bit. Controls counting in EL1. If 1, does not count in EL1.
+        P OFFSET(31) NUMBITS(1) [],
+
+        /// User filtering bit. Controls counting in EL0. If 1, does not count in EL0.
+        U OFFSET(30) NUMBITS(1) [],
+
+        /// Non-secure EL1 (kernel) modes filtering bit. If the value of this bit is not equal to
+        /// the value of P, counting in Non-secure EL1 is inverted.
+        NSK OFFSET(29) NUMBITS(1) [],
+
+        /// Non-secure EL0 (Unprivileged) filtering bit. If the value of this bit is not equal to
+        /// the value of U, counting in Non-secure EL0 is inverted.
+        NSU OFFSET(28) NUMBITS(1) [],
+
+        /// Non-secure EL2 (Hypervisor) filtering bit. If 1, counts in Non-secure EL2.
+        NSH OFFSET(27) NUMBITS(1) [],
+
+        /// Secure EL3 filtering bit. If the value of this bit is not equal to the value of P,
+        /// counting in EL3 is inverted.
+        M OFFSET(26) NUMBITS(1) [],
+
+        /// Secure EL2 filtering bit (FEAT_SEL2). If the value of this bit is not equal to the
+        /// value of NSH, counts in Secure EL2.
+        SH OFFSET(24) NUMBITS(1) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = PMCCFILTR_EL0::Register;
+
+    sys_coproc_read_raw!(u64, "PMCCFILTR_EL0", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = PMCCFILTR_EL0::Register;
+
+    sys_coproc_write_raw!(u64, "PMCCFILTR_EL0", "x");
+}
+
+pub const PMCCFILTR_EL0: Reg = Reg {};
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Performance Monitors Cycle Count Register - EL0
+//!
+//! Holds the value of the processor Cycle Counter, CCNT, that counts processor clock cycles.
+
+use tock_registers::interfaces::{Readable, Writeable};
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = ();
+
+    sys_coproc_read_raw!(u64, "PMCCNTR_EL0", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = ();
+
+    sys_coproc_write_raw!(u64, "PMCCNTR_EL0", "x");
+}
+
+pub const PMCCNTR_EL0: Reg = Reg {};
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Performance Monitors Count Enable Set register - EL0
+//!
+//! Enables the Cycle Count Register, PMCCNTR_EL0, and any implemented event counters
+//! PMEVCNTR<n>_EL0.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub PMCNTENSET_EL0 [
+        /// PMCCNTR_EL0 bit. Writing 1 enables the cycle counter; reads as the current state.
+        C OFFSET(31) NUMBITS(1) [],
+
+        /// Event counter bits, one per implemented PMEVCNTR<n>_EL0. Writing 1 to bit n
+        /// enables event counter n; reads as the current state.
+        P OFFSET(0) NUMBITS(31) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = PMCNTENSET_EL0::Register;
+
+    sys_coproc_read_raw!(u64, "PMCNTENSET_EL0", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = PMCNTENSET_EL0::Register;
+
+    sys_coproc_write_raw!(u64, "PMCNTENSET_EL0", "x");
+}
+
+pub const PMCNTENSET_EL0: Reg = Reg {};
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Performance Monitors Control Register - EL0
+//!
+//! Provides details of the Performance Monitors implementation, including the number of counters
+//! implemented, and configures and controls the counters.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub PMCR_EL0 [
+        /// Implementer code. Deprecated, use MIDR_EL1 instead.
+        IMP OFFSET(24) NUMBITS(8) [],
+
+        /// Identification code. Deprecated, use MIDR_EL1 instead.
+        IDCODE OFFSET(16) NUMBITS(8) [],
+
+        /// Number of event counters implemented.
+        ///
+        /// When read from EL1 or EL0 while EL2 is implemented and enabled, this returns
+        /// MDCR_EL2.HPMN.
+        N OFFSET(11) NUMBITS(5) [],
+
+        /// Long event counter enable. When FEAT_PMUv3p5 is implemented, event counter overflow
+        /// happens on increment that causes unsigned overflow of PMEVCNTR<n>_EL0[63:0], rather
+        /// than of PMEVCNTR<n>_EL0[31:0].
+        ///
+        /// Only applies to the counters below MDCR_EL2.HPMN.
+        LP OFFSET(7) NUMBITS(1) [
+            Overflow32 = 0,
+            Overflow64 = 1
+        ],
+
+        /// Long cycle counter enable. Determines when unsigned overflow is recorded by the cycle
+        /// counter overflow bit.
+        LC OFFSET(6) NUMBITS(1) [
+            Overflow32 = 0,
+            Overflow64 = 1
+        ],
+
+        /// Disable cycle counter when event counting is prohibited.
+        DP OFFSET(5) NUMBITS(1) [],
+
+        /// Enable export of events in an IMPLEMENTATION DEFINED PMU event export bus.
+        X OFFSET(4) NUMBITS(1) [],
+
+        /// Clock divider. When set, PMCCNTR_EL0 counts once every 64 clock cycles.
+        D OFFSET(3) NUMBITS(1) [],
+
+        /// Cycle counter reset. Write-only; writing 1 resets PMCCNTR_EL0 to zero.
+        C OFFSET(2) NUMBITS(1) [],
+
+        /// Event counter reset. Write-only; writing 1 resets all the event counters accessible at
+        /// the current Exception level to zero.
+        P OFFSET(1) NUMBITS(1) [],
+
+        /// Enable. Enables the cycle counter and the event counters below MDCR_EL2.HPMN.
+        E OFFSET(0) NUMBITS(1) [
+            Disabled = 0,
+            Enabled = 1
+        ]
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = PMCR_EL0::Register;
+
+    sys_coproc_read_raw!(u64, "PMCR_EL0", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = PMCR_EL0::Register;
+
+    sys_coproc_write_raw!(u64, "PMCR_EL0", "x");
+}
+
+pub const PMCR_EL0: Reg = Reg {};
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Performance Monitors Event Count Registers - EL0
+//!
+//! PMEVCNTR<n>_EL0 holds event counter n, which counts the events selected by
+//! PMEVTYPER<n>_EL0. Only the counters below PMCR_EL0.N are implemented.
+
+use tock_registers::interfaces::{Readable, Writeable};
+
+macro_rules! pmevcntr {
+    ($($reg:ident: $name:ident = $asm:literal;)*) => {
+        $(
+            pub struct $reg;
+
+            impl Readable for $reg {
+                type T = u64;
+                type R = ();
+
+                sys_coproc_read_raw!(u64, $asm, "x");
+            }
+
+            impl Writeable for $reg {
+                type T = u64;
+                type R = ();
+
+                sys_coproc_write_raw!(u64, $asm, "x");
+            }
+
+            pub const $name: $reg = $reg {};
+        )*
+    };
+}
+
+pmevcntr! {
+    Reg0: PMEVCNTR0_EL0 = "PMEVCNTR0_EL0";
+    Reg1: PMEVCNTR1_EL0 = "PMEVCNTR1_EL0";
+    Reg2: PMEVCNTR2_EL0 = "PMEVCNTR2_EL0";
+    Reg3: PMEVCNTR3_EL0 = "PMEVCNTR3_EL0";
+    Reg4: PMEVCNTR4_EL0 = "PMEVCNTR4_EL0";
+    Reg5: PMEVCNTR5_EL0 = "PMEVCNTR5_EL0";
+    Reg6: PMEVCNTR6_EL0 = "PMEVCNTR6_EL0";
+    Reg7: PMEVCNTR7_EL0 = "PMEVCNTR7_EL0";
+    Reg8: PMEVCNTR8_EL0 = "PMEVCNTR8_EL0";
+    Reg9: PMEVCNTR9_EL0 = "PMEVCNTR9_EL0";
+    Reg10: PMEVCNTR10_EL0 = "PMEVCNTR10_EL0";
+    Reg11: PMEVCNTR11_EL0 = "PMEVCNTR11_EL0";
+    Reg12: PMEVCNTR12_EL0 = "PMEVCNTR12_EL0";
+    Reg13: PMEVCNTR13_EL0 = "PMEVCNTR13_EL0";
+    Reg14: PMEVCNTR14_EL0 = "PMEVCNTR14_EL0";
+    Reg15: PMEVCNTR15_EL0 = "PMEVCNTR15_EL0";
+    Reg16: PMEVCNTR16_EL0 = "PMEVCNTR16_EL0";
+    Reg17: PMEVCNTR17_EL0 = "PMEVCNTR17_EL0";
+    Reg18: PMEVCNTR18_EL0 = "PMEVCNTR18_EL0";
+    Reg19: PMEVCNTR19_EL0 = "PMEVCNTR19_EL0";
+    Reg20: PMEVCNTR20_EL0 = "PMEVCNTR20_EL0";
+    Reg21: PMEVCNTR21_EL0 = "PMEVCNTR21_EL0";
+    Reg22: PMEVCNTR22_EL0 = "PMEVCNTR22_EL0";
+    Reg23: PMEVCNTR23_EL0 = "PMEVCNTR23_EL0";
+    Reg24: PMEVCNTR24_EL0 = "PMEVCNTR24_EL0";
+    Reg25: PMEVCNTR25_EL0 = "PMEVCNTR25_EL0";
+    Reg26: PMEVCNTR26_EL0 = "PMEVCNTR26_EL0";
+    Reg27: PMEVCNTR27_EL0 = "PMEVCNTR27_EL0";
+    Reg28: PMEVCNTR28_EL0 = "PMEVCNTR28_EL0";
+    Reg29: PMEVCNTR29_EL0 = "PMEVCNTR29_EL0";
+    Reg30: PMEVCNTR30_EL0 = "PMEVCNTR30_EL0";
+}
@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Performance Monitors Event Type Registers - EL0
+//!
+//! PMEVTYPER<n>_EL0 configures event counter n: the event it counts and the modes in which it
+//! counts. All the registers share the `PMEVTYPER_EL0` field definitions, which are also used by
+//! PMXEVTYPER_EL0.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub PMEVTYPER_EL0 [
+        /// Privileged filtering bit. Controls counting in EL1. If 1, does not count in EL1.
+        P OFFSET(31) NUMBITS(1) [],
+
+        /// User filtering bit. Controls counting in EL0. If 1, does not count in EL0.
+        U OFFSET(30) NUMBITS(1) [],
+
+        /// Non-secure EL1 (kernel) modes filtering bit. If the value of this bit is not equal to
+        /// the value of P, counting in Non-secure EL1 is inverted.
+        NSK OFFSET(29) NUMBITS(1) [],
+
+        /// Non-secure EL0 (Unprivileged) filtering bit. If the value of this bit is not equal to
+        /// the value of U, counting in Non-secure EL0 is inverted.
+        NSU OFFSET(28) NUMBITS(1) [],
+
+        /// Non-secure EL2 (Hypervisor) filtering bit. If 1, counts in Non-secure EL2.
+        NSH OFFSET(27) NUMBITS(1) [],
+
+        /// Secure EL3 filtering bit. If the value of this bit is not equal to the value of P,
+        /// counting in EL3 is inverted.
+        M OFFSET(26) NUMBITS(1) [],
+
+        /// Multithreading. If 1, counts events from all PEs with the same level 1 affinity.
+        MT OFFSET(25) NUMBITS(1) [],
+
+        /// Secure EL2 filtering bit (FEAT_SEL2). If the value of this bit is not equal to the
+        /// value of NSH, counts in Secure EL2.
+        SH OFFSET(24) NUMBITS(1) [],
+
+        /// Event to count. Bits [15:10] are RES0 unless FEAT_PMUv3p1 is implemented.
+        evtCount OFFSET(0) NUMBITS(16) []
+    ]
+}
+
+macro_rules! pmevtyper {
+    ($($reg:ident: $name:ident = $asm:literal;)*) => {
+        $(
+            pub struct $reg;
+
+            impl Readable for $reg {
+                type T = u64;
+                type R = PMEVTYPER_EL0::Register;
+
+                sys_coproc_read_raw!(u64, $asm, "x");
+            }
+
+            impl Writeable for $reg {
+                type T = u64;
+                type R = PMEVTYPER_EL0::Register;
+
+                sys_coproc_write_raw!(u64, $asm, "x");
+            }
+
+            pub const $name: $reg = $reg {};
+        )*
+    };
+}
+
+pmevtyper! {
+    Reg0: PMEVTYPER0_EL0 = "PMEVTYPER0_EL0";
+    Reg1: PMEVTYPER1_EL0 = "PMEVTYPER1_EL0";
+    Reg2: PMEVTYPER2_EL0 = "PMEVTYPER2_EL0";
+    Reg3: PMEVTYPER3_EL0 = "PMEVTYPER3_EL0";
+    Reg4: PMEVTYPER4_EL0 = "PMEVTYPER4_EL0";
+    Reg5: PMEVTYPER5_EL0 = "PMEVTYPER5_EL0";
+    Reg6: PMEVTYPER6_EL0 = "PMEVTYPER6_EL0";
+    Reg7: PMEVTYPER7_EL0 = "PMEVTYPER7_EL0";
+    Reg8: PMEVTYPER8_EL0 = "PMEVTYPER8_EL0";
+    Reg9: PMEVTYPER9_EL0 = "PMEVTYPER9_EL0";
+    Reg10: PMEVTYPER10_EL0 = "PMEVTYPER10_EL0";
+    Reg11: PMEVTYPER11_EL0 = "PMEVTYPER11_EL0";
+    Reg12: PMEVTYPER12_EL0 = "PMEVTYPER12_EL0";
+    Reg13: PMEVTYPER13_EL0 = "PMEVTYPER13_EL0";
+    Reg14: PMEVTYPER14_EL0 = "PMEVTYPER14_EL0";
+    Reg15: PMEVTYPER15_EL0 = "PMEVTYPER15_EL0";
+    Reg16: PMEVTYPER16_EL0 = "PMEVTYPER16_EL0";
+    Reg17: PMEVTYPER17_EL0 = "PMEVTYPER17_EL0";
+    Reg18: PMEVTYPER18_EL0 = "PMEVTYPER18_EL0";
+    Reg19: PMEVTYPER19_EL0 = "PMEVTYPER19_EL0";
+    Reg20: PMEVTYPER20_EL0 = "PMEVTYPER20_EL0";
+    Reg21: PMEVTYPER21_EL0 = "PMEVTYPER21_EL0";
+    Reg22: PMEVTYPER22_EL0 = "PMEVTYPER22_EL0";
+    Reg23: PMEVTYPER23_EL0 = "PMEVTYPER23_EL0";
+    Reg24: PMEVTYPER24_EL0 = "PMEVTYPER24_EL0";
+    Reg25: PMEVTYPER25_EL0 = "PMEVTYPER25_EL0";
+    Reg26: PMEVTYPER26_EL0 = "PMEVTYPER26_EL0";
+    Reg27: PMEVTYPER27_EL0 = "PMEVTYPER27_EL0";
+    Reg28: PMEVTYPER28_EL0 = "PMEVTYPER28_EL0";
+    Reg29: PMEVTYPER29_EL0 = "PMEVTYPER29_EL0";
+    Reg30: PMEVTYPER30_EL0 = "PMEVTYPER30_EL0";
+}
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Performance Monitors Interrupt Enable Clear register - EL1
+//!
+//! Disables the generation of interrupt requests on overflows from the Cycle Count Register,
+//! PMCCNTR_EL0, and the event counters PMEVCNTR<n>_EL0.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub PMINTENCLR_EL1 [
+        /// PMCCNTR_EL0 bit. Writing 1 disables the cycle counter overflow interrupt; reads as the current state.
+        C OFFSET(31) NUMBITS(1) [],
+
+        /// Event counter bits, one per implemented PMEVCNTR<n>_EL0. Writing 1 to bit n
+        /// disables the overflow interrupt of event counter n; reads as the current state.
+        P OFFSET(0) NUMBITS(31) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = PMINTENCLR_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "PMINTENCLR_EL1", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = PMINTENCLR_EL1::Register;
+
+    sys_coproc_write_raw!(u64, "PMINTENCLR_EL1", "x");
+}
+
+pub const PMINTENCLR_EL1: Reg = Reg {};
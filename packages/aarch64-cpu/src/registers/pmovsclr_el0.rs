@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Performance Monitors Overflow Flag Status Clear Register - EL0
+//!
+//! Contains the state of the overflow bit for the Cycle Count Register, PMCCNTR_EL0, and each
+//! of the implemented event counters PMEVCNTR<n>_EL0.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub PMOVSCLR_EL0 [
+        /// PMCCNTR_EL0 bit. Writing 1 clears the cycle counter overflow flag; reads as the current state.
+        C OFFSET(31) NUMBITS(1) [],
+
+        /// Event counter bits, one per implemented PMEVCNTR<n>_EL0. Writing 1 to bit n
+        /// clears the overflow flag of event counter n; reads as the current state.
+        P OFFSET(0) NUMBITS(31) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = PMOVSCLR_EL0::Register;
+
+    sys_coproc_read_raw!(u64, "PMOVSCLR_EL0", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = PMOVSCLR_EL0::Register;
+
+    sys_coproc_write_raw!(u64, "PMOVSCLR_EL0", "x");
+}
+
+pub const PMOVSCLR_EL0: Reg = Reg {};
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Performance Monitors Event Counter Selection Register - EL0
+//!
+//! Selects the current event counter PMEVCNTR<n>_EL0 or the cycle counter, CCNT, for access
+//! through PMXEVTYPER_EL0 and PMXEVCNTR_EL0.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub PMSELR_EL0 [
+        /// Event counter select. Selects event counter n (`0..=30`), or the cycle counter
+        /// filter (`31`).
+        SEL OFFSET(0) NUMBITS(5) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = PMSELR_EL0::Register;
+
+    sys_coproc_read_raw!(u64, "PMSELR_EL0", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = PMSELR_EL0::Register;
+
+    sys_coproc_write_raw!(u64, "PMSELR_EL0", "x");
+}
+
+pub const PMSELR_EL0: Reg = Reg {};
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Performance Monitors User Enable Register - EL0
+//!
+//! Enables or disables EL0 access to the Performance Monitors.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub PMUSERENR_EL0 [
+        /// Event counter read enable. Allows EL0 reads of the event counters and PMSELR_EL0.
+        ER OFFSET(3) NUMBITS(1) [
+            Trap = 0,
+            Enable = 1
+        ],
+
+        /// Cycle counter read enable. Allows EL0 reads of PMCCNTR_EL0.
+        CR OFFSET(2) NUMBITS(1) [
+            Trap = 0,
+            Enable = 1
+        ],
+
+        /// Software Increment write enable. Allows EL0 writes to PMSWINC_EL0.
+        SW OFFSET(1) NUMBITS(1) [
+            Trap = 0,
+            Enable = 1
+        ],
+
+        /// Enables EL0 read/write access to all the Performance Monitors registers other than
+        /// PMUSERENR_EL0, PMINTENSET_EL1 and PMINTENCLR_EL1.
+        EN OFFSET(0) NUMBITS(1) [
+            Trap = 0,
+            Enable = 1
+        ]
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = PMUSERENR_EL0::Register;
+
+    sys_coproc_read_raw!(u64, "PMUSERENR_EL0", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = PMUSERENR_EL0::Register;
+
+    sys_coproc_write_raw!(u64, "PMUSERENR_EL0", "x");
+}
+
+pub const PMUSERENR_EL0: Reg = Reg {};
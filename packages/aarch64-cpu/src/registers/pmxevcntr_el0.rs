@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Performance Monitors Selected Event Count Register - EL0
+//!
+//! Reads or writes the value of the event counter selected by PMSELR_EL0.SEL.
+
+use tock_registers::interfaces::{Readable, Writeable};
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = ();
+
+    sys_coproc_read_raw!(u64, "PMXEVCNTR_EL0", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = ();
+
+    sys_coproc_write_raw!(u64, "PMXEVCNTR_EL0", "x");
+}
+
+pub const PMXEVCNTR_EL0: Reg = Reg {};
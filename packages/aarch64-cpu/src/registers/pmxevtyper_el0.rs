@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Performance Monitors Selected Event Type Register - EL0
+//!
+//! Reads or writes the event type of the event counter selected by PMSELR_EL0.SEL, using the
+//! `PMEVTYPER_EL0` field definitions. When PMSELR_EL0.SEL is 31, accesses PMCCFILTR_EL0 instead.
+
+use super::pmevtyper_el0::PMEVTYPER_EL0;
+use tock_registers::interfaces::{Readable, Writeable};
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = PMEVTYPER_EL0::Register;
+
+    sys_coproc_read_raw!(u64, "PMXEVTYPER_EL0", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = PMEVTYPER_EL0::Register;
+
+    sys_coproc_write_raw!(u64, "PMXEVTYPER_EL0", "x");
+}
+
+pub const PMXEVTYPER_EL0: Reg = Reg {};
@@ -28,7 +28,7 @@ register_bitfields! {u64,
         ],
 
         /// Access Permissions Attribute
-        AP OFFSET(1) NUMBITS(2) [
+        AP OFFSET(2) NUMBITS(2) [
             ReadWriteEl1 = 0b00,
             ReadWriteEl1El0 = 0b01,
             ReadOnlyEl1 = 0b10,
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Random Allocation Tag Seed Register - EL1
+//!
+//! Holds the state of the pseudo-random number generator used by IRG when `GCR_EL1.RRND` is 0
+//! (FEAT_MTE2).
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub RGSR_EL1 [
+        /// Seed of the pseudo-random Allocation Tag generator.
+        ///
+        /// A seed of 0 makes IRG always return a tag of 0 (before exclusion), so software should
+        /// initialize it with a non-zero value.
+        SEED OFFSET(8) NUMBITS(16) [],
+
+        /// The last Allocation Tag generated by IRG.
+        TAG OFFSET(0) NUMBITS(4) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = RGSR_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "S3_0_C1_C0_5", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = RGSR_EL1::Register;
+
+    sys_coproc_write_raw!(u64, "S3_0_C1_C0_5", "x");
+}
+
+pub const RGSR_EL1: Reg = Reg {};
@@ -19,6 +19,22 @@ use tock_registers::{
 
 register_bitfields! {u64,
     pub SCR_EL3 [
+        /// Allocation Tag Access (FEAT_MTE2).
+        ///
+        /// 0 Accesses at EL2, EL1 and EL0 to Allocation Tags are prevented, and the tag control
+        ///   registers are trapped to EL3.
+        /// 1 This control does not prevent access to Allocation Tags at EL2, EL1 and EL0.
+        ATA OFFSET(26) NUMBITS(1) [],
+
+        /// Secure EL2 Enable (FEAT_SEL2).
+        ///
+        /// 0 All behaviors associated with Secure EL2 are disabled.
+        /// 1 All behaviors associated with Secure EL2 are enabled.
+        EEL2 OFFSET(18) NUMBITS(1) [
+            Disabled = 0,
+            Enabled = 1
+        ],
+
         /// Execution state control for lower Exception levels:
         ///
         /// 0 Lower levels are all AArch32.
@@ -16,6 +16,122 @@ use tock_registers::{
 
 register_bitfields! {u64,
     pub SCTLR_EL1 [
+        /// Allocation Tag Access in EL1 (FEAT_MTE2).
+        ///
+        /// Controls access to Allocation Tags and Tag Check operations in EL1.
+        ///
+        /// - 0b0: Access to Allocation Tags is prevented at EL1.
+        ///        Memory accesses at EL1 are not subject to a Tag Check operation.
+        ///
+        /// - 0b1: This control does not prevent access to Allocation Tags at EL1.
+        ///        Tag Checked memory accesses at EL1 are subject to a Tag Check operation.
+        ATA OFFSET(43) NUMBITS(1) [
+            Prevent = 0,
+            NoPrevent = 1
+        ],
+
+        /// Allocation Tag Access in EL0 (FEAT_MTE2).
+        ///
+        /// Controls access to Allocation Tags and Tag Check operations in EL0.
+        ///
+        /// - 0b0: Access to Allocation Tags is prevented at EL0.
+        ///        Memory accesses at EL0 are not subject to a Tag Check operation.
+        ///
+        /// - 0b1: This control does not prevent access to Allocation Tags at EL0.
+        ///        Tag Checked memory accesses at EL0 are subject to a Tag Check operation.
+        ATA0 OFFSET(42) NUMBITS(1) [
+            Prevent = 0,
+            NoPrevent = 1
+        ],
+
+        /// Tag Check Fault in EL1 (FEAT_MTE2).
+        ///
+        /// Controls the effect of Tag Check Faults due to Loads and Stores in EL1.
+        /// If FEAT_MTE3 is not implemented, the value 0b11 is reserved.
+        ///
+        /// - 0b00: Tag Check Faults have no effect on the PE.
+        ///
+        /// - 0b01: Tag Check Faults cause a synchronous exception.
+        ///
+        /// - 0b10: Tag Check Faults are asynchronously accumulated.
+        ///
+        /// - 0b11: When FEAT_MTE3 is implemented:
+        ///         Tag Check Faults cause a synchronous exception on reads, and are asynchronously
+        ///         accumulated on writes.
+        TCF OFFSET(40) NUMBITS(2) [
+            NoEffect = 0,
+            SyncException = 1,
+            AsyncAccumulated = 2,
+            SyncReadAsyncWrite = 3
+        ],
+
+        /// Tag Check Fault in EL0 (FEAT_MTE2).
+        ///
+        /// Controls the effect of Tag Check Faults due to Loads and Stores in EL0.
+        /// If FEAT_MTE3 is not implemented, the value 0b11 is reserved.
+        ///
+        /// - 0b00: Tag Check Faults have no effect on the PE.
+        ///
+        /// - 0b01: Tag Check Faults cause a synchronous exception.
+        ///
+        /// - 0b10: Tag Check Faults are asynchronously accumulated.
+        ///
+        /// - 0b11: When FEAT_MTE3 is implemented:
+        ///         Tag Check Faults cause a synchronous exception on reads, and are asynchronously
+        ///         accumulated on writes.
+        TCF0 OFFSET(38) NUMBITS(2) [
+            NoEffect = 0,
+            SyncException = 1,
+            AsyncAccumulated = 2,
+            SyncReadAsyncWrite = 3
+        ],
+
+        /// When synchronous exceptions are not being generated by Tag Check Faults, this field
+        /// controls whether on exception entry into EL1, all Tag Check Faults due to instructions
+        /// executed before exception entry, that are reported asynchronously, are synchronized
+        /// into TFSRE0_EL1 and TFSR_ELx registers (FEAT_MTE2).
+        ///
+        /// - 0b0: Tag Check Faults are not synchronized on entry to EL1.
+        ///
+        /// - 0b1: Tag Check Faults are synchronized on entry to EL1.
+        ITFSB OFFSET(37) NUMBITS(1) [
+            NoSyncEntry = 0,
+            SyncEntry = 1
+        ],
+
+        /// Controls enabling of pointer authentication (using the APIAKey_EL1 key) of instruction addresses
+        /// in the EL1&0 translation regime (FEAT_PAuth).
+        ///
+        /// 0 Pointer authentication is not enabled; the corresponding instructions are NOPs or
+        ///   return the input unchanged.
+        /// 1 Pointer authentication is enabled.
+        EnIA OFFSET(31) NUMBITS(1) [
+            Disable = 0,
+            Enable = 1,
+        ],
+
+        /// Controls enabling of pointer authentication (using the APIBKey_EL1 key) of instruction addresses
+        /// in the EL1&0 translation regime (FEAT_PAuth).
+        ///
+        /// 0 Pointer authentication is not enabled; the corresponding instructions are NOPs or
+        ///   return the input unchanged.
+        /// 1 Pointer authentication is enabled.
+        EnIB OFFSET(30) NUMBITS(1) [
+            Disable = 0,
+            Enable = 1,
+        ],
+
+        /// Controls enabling of pointer authentication (using the APDAKey_EL1 key) of data addresses
+        /// in the EL1&0 translation regime (FEAT_PAuth).
+        ///
+        /// 0 Pointer authentication is not enabled; the corresponding instructions are NOPs or
+        ///   return the input unchanged.
+        /// 1 Pointer authentication is enabled.
+        EnDA OFFSET(27) NUMBITS(1) [
+            Disable = 0,
+            Enable = 1,
+        ],
+
         /// Traps EL0 execution of cache maintenance instructions to EL1, from AArch64 state only.
         ///
         /// 0 Any attempt to execute a DC CVAU, DC CIVAC, DC CVAC, DC CVAP, or IC IVAU
@@ -126,6 +242,18 @@ register_bitfields! {u64,
             DontTrap = 1,
         ],
 
+        /// Background Region enable for EL1 (Armv8-R AArch64 only).
+        ///
+        /// 0 The background region is disabled. Any EL1 or EL0 access that does not match an MPU
+        ///   region causes a fault.
+        ///
+        /// 1 The background region is enabled for EL1 accesses. EL1 accesses that do not match an
+        ///   MPU region use the default memory map; EL0 accesses still fault.
+        BR OFFSET(17) NUMBITS(1) [
+            Disable = 0,
+            Enable = 1,
+        ],
+
         /// Traps EL0 executions of WFI instructions to EL1, from both execution states:
         ///
         /// 0 Any attempt to execute a WFI instruction at EL0 is trapped EL1, if the instruction would
@@ -178,6 +306,17 @@ register_bitfields! {u64,
             DontTrap = 1,
         ],
 
+        /// Controls enabling of pointer authentication (using the APDBKey_EL1 key) of data addresses
+        /// in the EL1&0 translation regime (FEAT_PAuth).
+        ///
+        /// 0 Pointer authentication is not enabled; the corresponding instructions are NOPs or
+        ///   return the input unchanged.
+        /// 1 Pointer authentication is enabled.
+        EnDB OFFSET(13) NUMBITS(1) [
+            Disable = 0,
+            Enable = 1,
+        ],
+
         /// Instruction access Cacheability control, for accesses at EL0 and
         /// EL1:
         ///
@@ -18,6 +18,54 @@ use tock_registers::{
 register_bitfields! {u64,
     pub SCTLR_EL2 [
 
+        /// Allocation Tag Access in EL2 (FEAT_MTE2).
+        ///
+        /// Controls access to Allocation Tags and Tag Check operations in EL2.
+        ///
+        /// - 0b0: Access to Allocation Tags is prevented at EL2.
+        ///        Memory accesses at EL2 are not subject to a Tag Check operation.
+        ///
+        /// - 0b1: This control does not prevent access to Allocation Tags at EL2.
+        ///        Tag Checked memory accesses at EL2 are subject to a Tag Check operation.
+        ATA OFFSET(43) NUMBITS(1) [
+            Prevent = 0,
+            NoPrevent = 1
+        ],
+
+        /// Tag Check Fault in EL2 (FEAT_MTE2).
+        ///
+        /// Controls the effect of Tag Check Faults due to Loads and Stores in EL2.
+        /// If FEAT_MTE3 is not implemented, the value 0b11 is reserved.
+        ///
+        /// - 0b00: Tag Check Faults have no effect on the PE.
+        ///
+        /// - 0b01: Tag Check Faults cause a synchronous exception.
+        ///
+        /// - 0b10: Tag Check Faults are asynchronously accumulated.
+        ///
+        /// - 0b11: When FEAT_MTE3 is implemented:
+        ///         Tag Check Faults cause a synchronous exception on reads, and are asynchronously
+        ///         accumulated on writes.
+        TCF OFFSET(40) NUMBITS(2) [
+            NoEffect = 0,
+            SyncException = 1,
+            AsyncAccumulated = 2,
+            SyncReadAsyncWrite = 3
+        ],
+
+        /// When synchronous exceptions are not being generated by Tag Check Faults, this field
+        /// controls whether on exception entry into EL2, all Tag Check Faults due to instructions
+        /// executed before exception entry, that are reported asynchronously, are synchronized
+        /// into TFSRE0_EL1 and TFSR_ELx registers (FEAT_MTE2).
+        ///
+        /// - 0b0: Tag Check Faults are not synchronized on entry to EL2.
+        ///
+        /// - 0b1: Tag Check Faults are synchronized on entry to EL2.
+        ITFSB OFFSET(37) NUMBITS(1) [
+            NoSyncEntry = 0,
+            SyncEntry = 1
+        ],
+
         /// Exception endianness. The possible values are:
         ///
         /// 0  Little endian.
@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! SME Control Register - EL2
+//!
+//! Controls aspects of Streaming SVE mode and SME visible at EL2 and lower Exception levels.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub SMCR_EL2 [
+        /// Full A64 instruction set in Streaming SVE mode (FEAT_SME_FA64).
+        ///
+        /// 0 Instructions that are illegal in Streaming SVE mode generate an exception.
+        ///
+        /// 1 The full A64 instruction set is legal in Streaming SVE mode.
+        FA64 OFFSET(31) NUMBITS(1) [],
+
+        /// Enables access to the ZT0 register (FEAT_SME2).
+        ///
+        /// 0 Instructions that access ZT0 are trapped.
+        ///
+        /// 1 Instructions that access ZT0 are not trapped by this control.
+        EZT0 OFFSET(30) NUMBITS(1) [],
+
+        /// Requests an Effective Streaming SVE vector length (SVL) of `(LEN + 1) * 128` bits.
+        ///
+        /// The Effective SVL is a power of two supported by the implementation, selected as the
+        /// largest one that is not larger than the requested length, or the smallest supported one
+        /// if none is, and is further constrained by the `SMCR_ELx.LEN` of higher Exception levels.
+        LEN OFFSET(0) NUMBITS(4) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = SMCR_EL2::Register;
+
+    sys_coproc_read_raw!(u64, "S3_4_C1_C2_6", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = SMCR_EL2::Register;
+
+    sys_coproc_write_raw!(u64, "S3_4_C1_C2_6", "x");
+}
+
+pub const SMCR_EL2: Reg = Reg {};
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Streaming Mode Priority Register - EL1
+//!
+//! Configures the streaming execution priority of the SME compute resources used by this PE.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub SMPRI_EL1 [
+        /// Streaming execution priority. A higher value is a higher priority when the SME compute
+        /// resources are shared with other PEs. Bits that the implementation does not support are
+        /// RAZ/WI.
+        Priority OFFSET(0) NUMBITS(4) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = SMPRI_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "S3_0_C1_C2_4", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = SMPRI_EL1::Register;
+
+    sys_coproc_write_raw!(u64, "S3_0_C1_C2_4", "x");
+}
+
+pub const SMPRI_EL1: Reg = Reg {};
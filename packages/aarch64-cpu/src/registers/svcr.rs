@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Streaming Vector Control Register
+//!
+//! Controls Streaming SVE mode and SME behavior. Writing `SM` or `ZA` has the same effect as the
+//! `SMSTART` and `SMSTOP` instructions, which includes zeroing the affected register state.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub SVCR [
+        /// Enables SME ZA storage (PSTATE.ZA).
+        ///
+        /// 0 ZA storage is invalid and instructions that access it are trapped.
+        ///
+        /// 1 ZA storage is valid and accessible.
+        ZA OFFSET(1) NUMBITS(1) [],
+
+        /// Enables Streaming SVE mode (PSTATE.SM).
+        ///
+        /// 0 The PE is not in Streaming SVE mode.
+        ///
+        /// 1 The PE is in Streaming SVE mode, where the Effective vector length is the Streaming
+        /// SVE vector length.
+        SM OFFSET(0) NUMBITS(1) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = SVCR::Register;
+
+    sys_coproc_read_raw!(u64, "S3_3_C4_C2_2", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = SVCR::Register;
+
+    sys_coproc_write_raw!(u64, "S3_3_C4_C2_2", "x");
+}
+
+pub const SVCR: Reg = Reg {};
@@ -16,6 +16,18 @@ use tock_registers::{
 
 register_bitfields! {u64,
     pub TCR_EL1 [
+        /// Unchecked accesses in the TTBR1_EL1 region (FEAT_MTE2).
+        ///
+        /// 0 Tag Checks are performed for accesses whose address has the logical tag 0b1111.
+        /// 1 Accesses at EL1 whose address has the logical tag 0b1111 are Tag Unchecked.
+        TCMA1 OFFSET(58) NUMBITS(1) [],
+
+        /// Unchecked accesses in the TTBR0_EL1 region (FEAT_MTE2).
+        ///
+        /// 0 Tag Checks are performed for accesses whose address has the logical tag 0b0000.
+        /// 1 Accesses at EL1 whose address has the logical tag 0b0000 are Tag Unchecked.
+        TCMA0 OFFSET(57) NUMBITS(1) [],
+
         /// When ARMv8.3-PAuth is implemented:
         ///     Controls the use of the top byte of instruction addresses for address matching.
         ///     0 TCR_EL1.TBI1 applies to Instruction and Data accesses.
@@ -18,6 +18,13 @@ use tock_registers::{
 register_bitfields! {u64,
     pub TCR_EL2 [
 
+        /// Unchecked accesses at EL2 (FEAT_MTE2).
+        ///
+        /// 0 Tag Checks are performed for accesses whose address has the logical tag 0b0000 or
+        ///   0b1111.
+        /// 1 Accesses whose address has the logical tag 0b0000 or 0b1111 are Tag Unchecked.
+        TCMA OFFSET(30) NUMBITS(1) [],
+
         /// When FEAT_HAFDBS is implemented hardware can update the dirty flags in the stage1
         /// descriptors
         HD OFFSET(22) NUMBITS(1) [
@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Tag Fault Status Register - EL3
+//!
+//! Holds the accumulated asynchronous Tag Check Faults of loads and stores at EL3 (FEAT_MTE2).
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub TFSR_EL3 [
+        /// Tag Check Fault. Set when an asynchronous Tag Check Fault is accumulated, and only
+        /// cleared by software.
+        TF0 OFFSET(0) NUMBITS(1) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = TFSR_EL3::Register;
+
+    sys_coproc_read_raw!(u64, "S3_6_C5_C6_0", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = TFSR_EL3::Register;
+
+    sys_coproc_write_raw!(u64, "S3_6_C5_C6_0", "x");
+}
+
+pub const TFSR_EL3: Reg = Reg {};
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! Tag Fault Status Register - EL0
+//!
+//! Holds the accumulated asynchronous Tag Check Faults of loads and stores at EL0 (FEAT_MTE2).
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub TFSRE0_EL1 [
+        /// Tag Check Fault in an address translated by TTBR1_EL1. Set when an asynchronous Tag
+        /// Check Fault is accumulated, and only cleared by software.
+        TF1 OFFSET(1) NUMBITS(1) [],
+
+        /// Tag Check Fault in an address translated by TTBR0_EL1. Set when an asynchronous Tag
+        /// Check Fault is accumulated, and only cleared by software.
+        TF0 OFFSET(0) NUMBITS(1) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = TFSRE0_EL1::Register;
+
+    sys_coproc_read_raw!(u64, "S3_0_C5_C6_1", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = TFSRE0_EL1::Register;
+
+    sys_coproc_write_raw!(u64, "S3_0_C5_C6_1", "x");
+}
+
+pub const TFSRE0_EL1: Reg = Reg {};
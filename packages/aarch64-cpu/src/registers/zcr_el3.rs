@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Copyright (c) 2026 by the author(s)
+
+//! SVE Control Register - EL3
+//!
+//! Controls aspects of SVE visible at all Exception levels.
+
+use tock_registers::{
+    interfaces::{Readable, Writeable},
+    register_bitfields,
+};
+
+register_bitfields! {u64,
+    pub ZCR_EL3 [
+        /// Requests an Effective SVE vector length (VL) of `(LEN + 1) * 128` bits.
+        ///
+        /// The Effective VL is the largest length supported by the implementation that is not
+        /// larger than the requested one, and is further constrained by the `ZCR_ELx.LEN` of
+        /// higher Exception levels.
+        LEN OFFSET(0) NUMBITS(4) []
+    ]
+}
+
+pub struct Reg;
+
+impl Readable for Reg {
+    type T = u64;
+    type R = ZCR_EL3::Register;
+
+    sys_coproc_read_raw!(u64, "S3_6_C1_C2_0", "x");
+}
+
+impl Writeable for Reg {
+    type T = u64;
+    type R = ZCR_EL3::Register;
+
+    sys_coproc_write_raw!(u64, "S3_6_C1_C2_0", "x");
+}
+
+pub const ZCR_EL3: Reg = Reg {};
@@ -0,0 +1,333 @@
+//! SMC Calling Convention (SMCCC) v1.2
+//!
+//! Calls to firmware and hypervisors are made with an SMC or HVC instruction, the [`Conduit`],
+//! with a [`FunctionId`] in W0 and the arguments in the following registers. SMC32 calls pass
+//! up to 7 arguments in W1-W7 and return up to 8 results in W0-W7; SMC64 calls pass up to 17
+//! arguments in X1-X17 and return up to 18 results in X0-X17.
+//!
+//! ```no_run
+//! use aarch64_cpu::smccc::{self, Conduit};
+//!
+//! let version = smccc::version(Conduit::Hvc).unwrap();
+//! assert!(version.major >= 1);
+//! ```
+//!
+//! See the [SMC Calling Convention][smccc] (DEN0028).
+//!
+//! [smccc]: https://developer.arm.com/documentation/den0028/latest/
+
+pub mod psci;
+
+/// The instruction used to make a call
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Conduit {
+    /// Secure Monitor Call, handled at EL3 (or trapped to EL2 with `HCR_EL2.TSC`)
+    Smc,
+    /// Hypervisor Call, handled at EL2
+    Hvc,
+}
+
+/// The service that a call belongs to, as found in bits \[29:24\] of the Function Identifier
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Owner {
+    /// Arm Architecture calls
+    Arch,
+    /// CPU Service calls
+    Cpu,
+    /// Silicon Partner (SiP) Service calls
+    Sip,
+    /// OEM Service calls
+    Oem,
+    /// Standard Secure Service calls, e.g. PSCI
+    StandardSecure,
+    /// Standard Hypervisor Service calls
+    StandardHypervisor,
+    /// Vendor Specific Hypervisor Service calls
+    VendorHypervisor,
+    /// Vendor Specific EL3 Monitor calls
+    VendorEl3Monitor,
+    /// Trusted Application calls, with the entity number (48-49)
+    TrustedApplication(u8),
+    /// Trusted OS calls, with the entity number (50-63)
+    TrustedOs(u8),
+    /// Reserved entity numbers (8-47)
+    Reserved(u8),
+}
+
+impl Owner {
+    /// Decode a 6-bit Owning Entity Number
+    pub const fn from_number(number: u8) -> Owner {
+        match number & 0b11_1111 {
+            0 => Owner::Arch,
+            1 => Owner::Cpu,
+            2 => Owner::Sip,
+            3 => Owner::Oem,
+            4 => Owner::StandardSecure,
+            5 => Owner::StandardHypervisor,
+            6 => Owner::VendorHypervisor,
+            7 => Owner::VendorEl3Monitor,
+            n @ 48..=49 => Owner::TrustedApplication(n),
+            n @ 50..=63 => Owner::TrustedOs(n),
+            n => Owner::Reserved(n),
+        }
+    }
+
+    /// The Owning Entity Number
+    pub const fn number(self) -> u8 {
+        match self {
+            Owner::Arch => 0,
+            Owner::Cpu => 1,
+            Owner::Sip => 2,
+            Owner::Oem => 3,
+            Owner::StandardSecure => 4,
+            Owner::StandardHypervisor => 5,
+            Owner::VendorHypervisor => 6,
+            Owner::VendorEl3Monitor => 7,
+            Owner::TrustedApplication(n) | Owner::TrustedOs(n) | Owner::Reserved(n) => n,
+        }
+    }
+}
+
+/// A Function Identifier, passed in W0
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FunctionId(pub u32);
+
+impl FunctionId {
+    const FAST: u32 = 1 << 31;
+    const SMC64: u32 = 1 << 30;
+    const OWNER_SHIFT: u32 = 24;
+
+    const fn new(flags: u32, owner: Owner, number: u16) -> FunctionId {
+        FunctionId(flags | (owner.number() as u32) << Self::OWNER_SHIFT | number as u32)
+    }
+
+    /// A Fast Call using the SMC32 calling convention
+    pub const fn fast32(owner: Owner, number: u16) -> FunctionId {
+        Self::new(Self::FAST, owner, number)
+    }
+
+    /// A Fast Call using the SMC64 calling convention
+    pub const fn fast64(owner: Owner, number: u16) -> FunctionId {
+        Self::new(Self::FAST | Self::SMC64, owner, number)
+    }
+
+    /// A Yielding Call using the SMC32 calling convention
+    pub const fn yielding32(owner: Owner, number: u16) -> FunctionId {
+        Self::new(0, owner, number)
+    }
+
+    /// A Yielding Call using the SMC64 calling convention
+    pub const fn yielding64(owner: Owner, number: u16) -> FunctionId {
+        Self::new(Self::SMC64, owner, number)
+    }
+
+    /// Returns `true` for a Fast Call, `false` for a Yielding Call
+    pub const fn is_fast(self) -> bool {
+        self.0 & Self::FAST != 0
+    }
+
+    /// Returns `true` for the SMC64 calling convention, `false` for SMC32
+    pub const fn is_smc64(self) -> bool {
+        self.0 & Self::SMC64 != 0
+    }
+
+    /// The service the call belongs to
+    pub const fn owner(self) -> Owner {
+        Owner::from_number((self.0 >> Self::OWNER_SHIFT) as u8)
+    }
+
+    /// The Function Number within the service
+    pub const fn number(self) -> u16 {
+        self.0 as u16
+    }
+}
+
+/// `SMCCC_VERSION`
+pub const SMCCC_VERSION: FunctionId = FunctionId::fast32(Owner::Arch, 0x0000);
+/// `SMCCC_ARCH_FEATURES`
+pub const SMCCC_ARCH_FEATURES: FunctionId = FunctionId::fast32(Owner::Arch, 0x0001);
+
+/// Returned in W0 for unknown Function Identifiers
+pub const NOT_SUPPORTED: i32 = -1;
+/// Returned by `SMCCC_ARCH_FEATURES` for workarounds that the PE does not need
+pub const NOT_REQUIRED: i32 = -2;
+/// Returned by `SMCCC_ARCH_FEATURES` for invalid arguments
+pub const INVALID_PARAMETER: i32 = -3;
+
+/// Ways the architecture calls can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The function is not implemented
+    NotSupported,
+    /// The function is implemented, but the PE does not need it
+    NotRequired,
+    /// An argument is invalid
+    InvalidParameter,
+    /// Any other negative return value
+    Unknown(i32),
+}
+
+impl Error {
+    /// Convert a return value, where negative values are errors
+    pub const fn check(value: i32) -> Result<i32, Error> {
+        match value {
+            NOT_SUPPORTED => Err(Error::NotSupported),
+            NOT_REQUIRED => Err(Error::NotRequired),
+            INVALID_PARAMETER => Err(Error::InvalidParameter),
+            v if v < 0 => Err(Error::Unknown(v)),
+            v => Ok(v),
+        }
+    }
+}
+
+/// A version number as returned by `SMCCC_VERSION` or `PSCI_VERSION`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    /// Major revision
+    pub major: u16,
+    /// Minor revision
+    pub minor: u16,
+}
+
+impl Version {
+    /// Decode a version with the major revision in bits \[30:16\] and the minor one in \[15:0\]
+    pub const fn from_bits(bits: u32) -> Version {
+        Version {
+            major: (bits >> 16) as u16 & 0x7fff,
+            minor: bits as u16,
+        }
+    }
+
+    /// Encode the version with the major revision in bits \[30:16\] and the minor one in \[15:0\]
+    pub const fn bits(self) -> u32 {
+        (self.major as u32 & 0x7fff) << 16 | self.minor as u32
+    }
+}
+
+/// Make an SMC32 call with up to 7 arguments and return W0-W7
+///
+/// Panics if more than 7 arguments are given.
+pub fn call32(conduit: Conduit, function: FunctionId, args: &[u32]) -> [u32; 8] {
+    assert!(args.len() <= 7, "SMC32 calls take at most 7 arguments");
+
+    let mut regs = [0; 18];
+    regs[0] = function.0 as u64;
+    for (reg, arg) in regs[1..].iter_mut().zip(args) {
+        *reg = *arg as u64;
+    }
+    call(conduit, &mut regs);
+
+    let mut results = [0; 8];
+    for (result, reg) in results.iter_mut().zip(regs) {
+        *result = reg as u32;
+    }
+    results
+}
+
+/// Make an SMC64 call with up to 17 arguments and return X0-X17
+///
+/// Panics if more than 17 arguments are given.
+pub fn call64(conduit: Conduit, function: FunctionId, args: &[u64]) -> [u64; 18] {
+    assert!(args.len() <= 17, "SMC64 calls take at most 17 arguments");
+
+    let mut regs = [0; 18];
+    regs[0] = function.0 as u64;
+    regs[1..=args.len()].copy_from_slice(args);
+    call(conduit, &mut regs);
+    regs
+}
+
+/// Get the implemented SMCCC version
+///
+/// SMCCC v1.0 does not implement `SMCCC_VERSION`, so [`Error::NotSupported`] means v1.0.
+pub fn version(conduit: Conduit) -> Result<Version, Error> {
+    let [w0, ..] = call32(conduit, SMCCC_VERSION, &[]);
+    Error::check(w0 as i32).map(|bits| Version::from_bits(bits as u32))
+}
+
+/// Check whether `function` is implemented, returning its feature flags if so
+///
+/// This needs SMCCC v1.1 or later.
+pub fn arch_features(conduit: Conduit, function: FunctionId) -> Result<i32, Error> {
+    let [w0, ..] = call32(conduit, SMCCC_ARCH_FEATURES, &[function.0]);
+    Error::check(w0 as i32)
+}
+
+/// Issue an SMC or HVC with X0-X17 taken from and written back to `regs`
+#[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+fn call(conduit: Conduit, regs: &mut [u64; 18]) {
+    #[cfg(target_arch = "aarch64")]
+    macro_rules! call {
+        ($instr:literal) => {
+            // SAFETY: the callee is expected to follow the SMCCC, which preserves all registers
+            // other than X0-X17
+            unsafe {
+                core::arch::asm!(
+                    $instr,
+                    inout("x0") regs[0],
+                    inout("x1") regs[1],
+                    inout("x2") regs[2],
+                    inout("x3") regs[3],
+                    inout("x4") regs[4],
+                    inout("x5") regs[5],
+                    inout("x6") regs[6],
+                    inout("x7") regs[7],
+                    inout("x8") regs[8],
+                    inout("x9") regs[9],
+                    inout("x10") regs[10],
+                    inout("x11") regs[11],
+                    inout("x12") regs[12],
+                    inout("x13") regs[13],
+                    inout("x14") regs[14],
+                    inout("x15") regs[15],
+                    inout("x16") regs[16],
+                    inout("x17") regs[17],
+                    options(nostack)
+                )
+            }
+        };
+    }
+
+    match conduit {
+        // `smc #0`, encoded by hand as the assembler rejects it for Armv8-R targets, which lack EL3
+        #[cfg(target_arch = "aarch64")]
+        Conduit::Smc => call!(".inst 0xd4000003"),
+        #[cfg(target_arch = "aarch64")]
+        Conduit::Hvc => call!("hvc #0"),
+
+        #[cfg(not(target_arch = "aarch64"))]
+        _ => unimplemented!(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn function_id() {
+        assert_eq!(SMCCC_VERSION.0, 0x8000_0000);
+
+        let cpu_on = FunctionId(0xc400_0003);
+        assert!(cpu_on.is_fast());
+        assert!(cpu_on.is_smc64());
+        assert_eq!(cpu_on.owner(), Owner::StandardSecure);
+        assert_eq!(cpu_on.number(), 3);
+        assert_eq!(FunctionId::fast64(Owner::StandardSecure, 3), cpu_on);
+
+        let trusted_os = FunctionId::yielding32(Owner::TrustedOs(50), 7);
+        assert_eq!(trusted_os.0, 0x3200_0007);
+        assert_eq!(trusted_os.owner(), Owner::TrustedOs(50));
+    }
+
+    #[test]
+    fn version_and_errors() {
+        assert_eq!(
+            Version::from_bits(0x0001_0002),
+            Version { major: 1, minor: 2 }
+        );
+        assert_eq!(Error::check(-1), Err(Error::NotSupported));
+        assert_eq!(Error::check(-7), Err(Error::Unknown(-7)));
+        assert_eq!(Error::check(0), Ok(0));
+    }
+}
@@ -0,0 +1,276 @@
+//! Power State Coordination Interface (PSCI) client
+//!
+//! PSCI is a Standard Secure Service, called through the [`Conduit`] that the platform documents
+//! (on device tree platforms, the `method` property of the `/psci` node). Firmware at EL3 is
+//! called with [`Conduit::Smc`]; a hypervisor that emulates PSCI for its guests expects
+//! [`Conduit::Hvc`].
+//!
+//! ```no_run
+//! use aarch64_cpu::smccc::{psci::Psci, Conduit};
+//!
+//! let psci = Psci::new(Conduit::Hvc);
+//! let version = psci.version().unwrap();
+//! assert!(version.major >= 1);
+//!
+//! psci.system_off();
+//! ```
+//!
+//! See the [Arm Power State Coordination Interface][psci] (DEN0022).
+//!
+//! [psci]: https://developer.arm.com/documentation/den0022/latest/
+
+use super::{call32, call64, Conduit, FunctionId, Owner, Version};
+
+/// `PSCI_VERSION`
+pub const PSCI_VERSION: FunctionId = FunctionId::fast32(Owner::StandardSecure, 0x0000);
+/// `CPU_SUSPEND` using SMC32
+pub const CPU_SUSPEND_32: FunctionId = FunctionId::fast32(Owner::StandardSecure, 0x0001);
+/// `CPU_SUSPEND` using SMC64
+pub const CPU_SUSPEND_64: FunctionId = FunctionId::fast64(Owner::StandardSecure, 0x0001);
+/// `CPU_OFF`
+pub const CPU_OFF: FunctionId = FunctionId::fast32(Owner::StandardSecure, 0x0002);
+/// `CPU_ON` using SMC32
+pub const CPU_ON_32: FunctionId = FunctionId::fast32(Owner::StandardSecure, 0x0003);
+/// `CPU_ON` using SMC64
+pub const CPU_ON_64: FunctionId = FunctionId::fast64(Owner::StandardSecure, 0x0003);
+/// `AFFINITY_INFO` using SMC32
+pub const AFFINITY_INFO_32: FunctionId = FunctionId::fast32(Owner::StandardSecure, 0x0004);
+/// `AFFINITY_INFO` using SMC64
+pub const AFFINITY_INFO_64: FunctionId = FunctionId::fast64(Owner::StandardSecure, 0x0004);
+/// `SYSTEM_OFF`
+pub const SYSTEM_OFF: FunctionId = FunctionId::fast32(Owner::StandardSecure, 0x0008);
+/// `SYSTEM_RESET`
+pub const SYSTEM_RESET: FunctionId = FunctionId::fast32(Owner::StandardSecure, 0x0009);
+/// `PSCI_FEATURES`
+pub const PSCI_FEATURES: FunctionId = FunctionId::fast32(Owner::StandardSecure, 0x000a);
+
+/// Ways the PSCI functions can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// `NOT_SUPPORTED` (-1)
+    NotSupported,
+    /// `INVALID_PARAMETERS` (-2)
+    InvalidParameters,
+    /// `DENIED` (-3)
+    Denied,
+    /// `ALREADY_ON` (-4): the target core is already on
+    AlreadyOn,
+    /// `ON_PENDING` (-5): a `CPU_ON` call for the target core is still in progress
+    OnPending,
+    /// `INTERNAL_FAILURE` (-6)
+    InternalFailure,
+    /// `NOT_PRESENT` (-7)
+    NotPresent,
+    /// `DISABLED` (-8)
+    Disabled,
+    /// `INVALID_ADDRESS` (-9): the entry point address is not valid
+    InvalidAddress,
+    /// Any other negative return value
+    Unknown(i32),
+}
+
+impl Error {
+    /// Convert a return value, where negative values are errors
+    pub const fn check(value: i32) -> Result<i32, Error> {
+        match value {
+            -1 => Err(Error::NotSupported),
+            -2 => Err(Error::InvalidParameters),
+            -3 => Err(Error::Denied),
+            -4 => Err(Error::AlreadyOn),
+            -5 => Err(Error::OnPending),
+            -6 => Err(Error::InternalFailure),
+            -7 => Err(Error::NotPresent),
+            -8 => Err(Error::Disabled),
+            -9 => Err(Error::InvalidAddress),
+            v if v < 0 => Err(Error::Unknown(v)),
+            v => Ok(v),
+        }
+    }
+
+    /// The return value that stands for this error
+    pub const fn code(&self) -> i32 {
+        match self {
+            Error::NotSupported => -1,
+            Error::InvalidParameters => -2,
+            Error::Denied => -3,
+            Error::AlreadyOn => -4,
+            Error::OnPending => -5,
+            Error::InternalFailure => -6,
+            Error::NotPresent => -7,
+            Error::Disabled => -8,
+            Error::InvalidAddress => -9,
+            Error::Unknown(code) => *code,
+        }
+    }
+}
+
+/// The power state of a core, as returned by `AFFINITY_INFO`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AffinityState {
+    /// At least one core in the affinity instance is on
+    On = 0,
+    /// All cores in the affinity instance are off
+    Off = 1,
+    /// At least one core in the affinity instance is being turned on
+    OnPending = 2,
+}
+
+/// Returns the affinity fields (Aff3, Aff2, Aff1 and Aff0) of an `MPIDR_EL1` value
+///
+/// This is the format of the `target_cpu` arguments, which must not have any other bits set.
+pub const fn affinity(mpidr: u64) -> u64 {
+    mpidr & 0xff_00ff_ffff
+}
+
+/// A PSCI client that makes its calls through a [`Conduit`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Psci {
+    conduit: Conduit,
+}
+
+impl Psci {
+    /// Creates a client that calls the PSCI implementation through `conduit`
+    pub const fn new(conduit: Conduit) -> Psci {
+        Psci { conduit }
+    }
+
+    /// The conduit this client uses
+    pub const fn conduit(&self) -> Conduit {
+        self.conduit
+    }
+
+    /// Get the implemented PSCI version
+    ///
+    /// PSCI v0.1 does not implement `PSCI_VERSION`, in which case this returns
+    /// [`Error::NotSupported`].
+    pub fn version(&self) -> Result<Version, Error> {
+        let [w0, ..] = call32(self.conduit, PSCI_VERSION, &[]);
+        Error::check(w0 as i32).map(|bits| Version::from_bits(bits as u32))
+    }
+
+    /// Check whether `function` is implemented, returning its feature flags if so
+    ///
+    /// This needs PSCI v1.0 or later.
+    pub fn features(&self, function: FunctionId) -> Result<i32, Error> {
+        let [w0, ..] = call32(self.conduit, PSCI_FEATURES, &[function.0]);
+        Error::check(w0 as i32)
+    }
+
+    /// Power up the core `target_cpu`, given in the format returned by [`affinity`]
+    ///
+    /// The core starts executing at `entry_point`, at the Exception Level of the caller, with
+    /// `context_id` in X0 and the MMU and caches disabled. Its other registers, including SP, are
+    /// UNKNOWN and traps such as those for FP/SIMD instructions are at their reset values.
+    ///
+    /// # Safety
+    ///
+    /// `entry_point` must be the physical address of code that can run in that state.
+    pub unsafe fn cpu_on(
+        &self,
+        target_cpu: u64,
+        entry_point: u64,
+        context_id: u64,
+    ) -> Result<(), Error> {
+        let [x0, ..] = call64(
+            self.conduit,
+            CPU_ON_64,
+            &[target_cpu, entry_point, context_id],
+        );
+        Error::check(x0 as i32).map(drop)
+    }
+
+    /// Power down the calling core
+    ///
+    /// This only returns if the core could not be powered down.
+    pub fn cpu_off(&self) -> Error {
+        let [w0, ..] = call32(self.conduit, CPU_OFF, &[]);
+        Error::check(w0 as i32)
+            .err()
+            .unwrap_or(Error::Unknown(w0 as i32))
+    }
+
+    /// Suspend the calling core to the IMPLEMENTATION DEFINED `power_state`
+    ///
+    /// Returns `Ok` once the core wakes up from a standby state. If `power_state` is a powerdown
+    /// state, the core instead wakes up at `entry_point`, as for [`cpu_on`](Psci::cpu_on).
+    ///
+    /// # Safety
+    ///
+    /// For powerdown states, `entry_point` must be the physical address of code that can run in
+    /// the state described by [`cpu_on`](Psci::cpu_on), and that resumes whatever the core was
+    /// running.
+    pub unsafe fn cpu_suspend(
+        &self,
+        power_state: u32,
+        entry_point: u64,
+        context_id: u64,
+    ) -> Result<(), Error> {
+        let [x0, ..] = call64(
+            self.conduit,
+            CPU_SUSPEND_64,
+            &[power_state.into(), entry_point, context_id],
+        );
+        Error::check(x0 as i32).map(drop)
+    }
+
+    /// Get the power state of the affinity instance containing `target_affinity`
+    ///
+    /// `lowest_affinity_level` selects the instance: 0 for the core itself, 1 for its cluster
+    /// and so on.
+    pub fn affinity_info(
+        &self,
+        target_affinity: u64,
+        lowest_affinity_level: u32,
+    ) -> Result<AffinityState, Error> {
+        let [x0, ..] = call64(
+            self.conduit,
+            AFFINITY_INFO_64,
+            &[target_affinity, lowest_affinity_level.into()],
+        );
+        match Error::check(x0 as i32)? {
+            0 => Ok(AffinityState::On),
+            1 => Ok(AffinityState::Off),
+            2 => Ok(AffinityState::OnPending),
+            v => Err(Error::Unknown(v)),
+        }
+    }
+
+    /// Shut down the system
+    ///
+    /// This only returns if the system could not be shut down.
+    pub fn system_off(&self) -> Error {
+        let [w0, ..] = call32(self.conduit, SYSTEM_OFF, &[]);
+        Error::check(w0 as i32)
+            .err()
+            .unwrap_or(Error::Unknown(w0 as i32))
+    }
+
+    /// Reset the system
+    ///
+    /// This only returns if the system could not be reset.
+    pub fn system_reset(&self) -> Error {
+        let [w0, ..] = call32(self.conduit, SYSTEM_RESET, &[]);
+        Error::check(w0 as i32)
+            .err()
+            .unwrap_or(Error::Unknown(w0 as i32))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn function_ids_and_errors() {
+        assert_eq!(PSCI_VERSION.0, 0x8400_0000);
+        assert_eq!(CPU_ON_64.0, 0xc400_0003);
+        assert_eq!(AFFINITY_INFO_32.0, 0x8400_0004);
+        assert_eq!(SYSTEM_RESET.0, 0x8400_0009);
+
+        for code in -10..0 {
+            assert_eq!(Error::check(code).unwrap_err().code(), code);
+        }
+        assert_eq!(Error::check(-4), Err(Error::AlreadyOn));
+        assert_eq!(affinity(0x8000_0001), 1);
+    }
+}
@@ -0,0 +1,381 @@
+//! Scalable Vector Extension (FEAT_SVE) vector length and state management
+//!
+//! The Effective vector length (VL) at an Exception level is the smallest of the lengths
+//! requested in `ZCR_ELx.LEN` at that level and the levels above it, rounded down to a length the
+//! implementation supports. In Streaming SVE mode (FEAT_SME) the Streaming SVE vector length
+//! (SVL), requested in `SMCR_ELx.LEN`, is used instead. All lengths in this module are in bytes.
+//!
+//! [`save`] and [`restore`] copy the Z0-Z31, P0-P15 and FFR registers to and from a buffer of
+//! [`state_size`] bytes, e.g. to switch between workloads that use SVE.
+//!
+//! SVE instructions and registers must not be trapped, i.e. `CPACR_EL1.ZEN` and the `CPTR_ELx` of
+//! the higher Exception levels must allow them. The Streaming SVE functions additionally need
+//! `CPACR_EL1.SMEN` and the `CPTR_ELx` SME controls.
+//!
+//! ```no_run
+//! use aarch64_cpu::{sve, ExceptionLevel};
+//!
+//! // limit EL1 and EL0 to 256-bit vectors
+//! sve::set_vector_length(ExceptionLevel::EL1, 32).unwrap();
+//!
+//! #[repr(C, align(16))]
+//! struct State([u8; sve::state_size(sve::MAX_VL)]);
+//!
+//! let mut state = State([0; sve::state_size(sve::MAX_VL)]);
+//! let size = sve::save(&mut state.0).unwrap();
+//! assert_eq!(size, sve::state_size(sve::vector_length()));
+//! ```
+
+use crate::{asm::barrier, registers::*, ExceptionLevel};
+
+/// The shortest vector length, in bytes
+pub const MIN_VL: usize = 16;
+
+/// The longest vector length allowed by the architecture, in bytes
+pub const MAX_VL: usize = 256;
+
+/// Ways this API can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The vector length is not a multiple of [`MIN_VL`] up to [`MAX_VL`]
+    InvalidVectorLength(usize),
+    /// The buffer is smaller than the given number of bytes
+    BufferTooSmall(usize),
+    /// The buffer is not 16-byte aligned
+    Unaligned,
+}
+
+/// The Exception level the PE is running at, whose `ZCR_ELx` and `SMCR_ELx` are used
+///
+/// Panics at EL0, where the control registers are not accessible.
+fn current_el() -> ExceptionLevel {
+    match ExceptionLevel::current() {
+        ExceptionLevel::EL0 => panic!("vector lengths cannot be controlled from EL0"),
+        el => el,
+    }
+}
+
+/// Get the Effective vector length, which is the Streaming SVE vector length in Streaming SVE
+/// mode
+#[inline(always)]
+pub fn vector_length() -> usize {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => {
+            let vl: usize;
+            unsafe {
+                core::arch::asm!(
+                    ".arch_extension sve",
+                    "RDVL {}, #1",
+                    out(reg) vl,
+                    options(nomem, nostack, preserves_flags)
+                )
+            };
+            vl
+        }
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+/// Get the Effective Streaming SVE vector length
+///
+/// This can be read outside Streaming SVE mode, but needs FEAT_SME.
+#[inline(always)]
+pub fn streaming_vector_length() -> usize {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => {
+            let svl: usize;
+            unsafe {
+                core::arch::asm!(
+                    ".arch_extension sme",
+                    "RDSVL {}, #1",
+                    out(reg) svl,
+                    options(nomem, nostack, preserves_flags)
+                )
+            };
+            svl
+        }
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+/// Get the longest vector length that the current Exception level can select
+///
+/// This briefly requests the longest length in the `ZCR_ELx` of the current Exception level, so
+/// it must not be called at EL0.
+pub fn max_vector_length() -> usize {
+    let el = current_el();
+    let saved = zcr_len(el);
+    set_zcr_len(el, 0b1111);
+    let vl = vector_length();
+    set_zcr_len(el, saved);
+    vl
+}
+
+/// Get the longest Streaming SVE vector length that the current Exception level can select
+///
+/// This briefly requests the longest length in the `SMCR_ELx` of the current Exception level, so
+/// it must not be called at EL0.
+pub fn max_streaming_vector_length() -> usize {
+    let el = current_el();
+    let saved = smcr_len(el);
+    set_smcr_len(el, 0b1111);
+    let svl = streaming_vector_length();
+    set_smcr_len(el, saved);
+    svl
+}
+
+/// Request a vector length of `vl` bytes for `el` and the Exception levels below it
+///
+/// This writes `ZCR_ELx` of `el`, where EL0 shares `ZCR_EL1` with EL1. The Effective vector
+/// length may be shorter, see [`vector_length`].
+pub fn set_vector_length(el: ExceptionLevel, vl: usize) -> Result<(), Error> {
+    set_zcr_len(el, len(vl)?);
+    Ok(())
+}
+
+/// Request a Streaming SVE vector length of `svl` bytes for `el` and the Exception levels below
+/// it
+///
+/// This writes `SMCR_ELx` of `el`, where EL0 shares `SMCR_EL1` with EL1. The Effective Streaming
+/// SVE vector length is always a power of two and may differ, see
+/// [`streaming_vector_length`].
+pub fn set_streaming_vector_length(el: ExceptionLevel, svl: usize) -> Result<(), Error> {
+    set_smcr_len(el, len(svl)?);
+    Ok(())
+}
+
+/// Enter Streaming SVE mode
+///
+/// ZA storage is not affected.
+///
+/// # Safety
+///
+/// Entering Streaming SVE mode sets the Z, P and FFR registers to zero, including the Advanced
+/// SIMD and floating-point registers V0-V31 that share storage with Z0-Z31. While in Streaming SVE
+/// mode, Advanced SIMD instructions are illegal unless `SMCR_ELx.FA64` is set, so the code that
+/// runs until [`stop_streaming`] must not use them.
+pub unsafe fn start_streaming() {
+    SVCR.modify(SVCR::SM::SET);
+}
+
+/// Leave Streaming SVE mode
+///
+/// ZA storage is not affected.
+///
+/// # Safety
+///
+/// Leaving Streaming SVE mode sets the Z, P and FFR registers to zero, including the Advanced SIMD
+/// and floating-point registers V0-V31 that share storage with Z0-Z31.
+pub unsafe fn stop_streaming() {
+    SVCR.modify(SVCR::SM::CLEAR);
+}
+
+/// Check if the PE is in Streaming SVE mode
+pub fn is_streaming() -> bool {
+    SVCR.is_set(SVCR::SM)
+}
+
+/// Get the size in bytes of the state saved by [`save`] for a vector length of `vl` bytes
+///
+/// The 32 Z registers are `vl` bytes each, followed by the 16 P registers and FFR, which are
+/// `vl / 8` bytes each.
+pub const fn state_size(vl: usize) -> usize {
+    32 * vl + 17 * (vl / 8)
+}
+
+/// Save Z0-Z31, P0-P15 and FFR to `buffer`
+///
+/// The state is saved at the Effective vector length, so `buffer` must be at least
+/// `state_size(vector_length())` bytes long and 16-byte aligned. In Streaming SVE mode FFR is not
+/// accessible and its space in `buffer` is left unchanged.
+///
+/// Returns the number of bytes written.
+pub fn save(buffer: &mut [u8]) -> Result<usize, Error> {
+    let vl = vector_length();
+    check_buffer(buffer, vl)?;
+
+    let z = buffer.as_mut_ptr();
+    // SAFETY: the predicates follow the 32 Z registers in `buffer`, which `check_buffer` has
+    // confirmed is long enough
+    let p = unsafe { z.add(32 * vl) };
+    save_registers(z, p, !is_streaming());
+
+    Ok(state_size(vl))
+}
+
+/// Restore Z0-Z31, P0-P15 and FFR from `buffer`
+///
+/// `buffer` must hold state that [`save`] wrote at the current Effective vector length and in the
+/// same Streaming SVE mode. As with [`save`], FFR is not restored in Streaming SVE mode.
+///
+/// # Safety
+///
+/// Z0-Z31 share storage with the Advanced SIMD and floating-point registers V0-V31, including
+/// the callee-saved D8-D15, which are replaced without the compiler knowing. This must only be
+/// called where the compiler does not keep values in those registers, e.g. in code built for a
+/// soft-float target right before returning to the Exception level that owns the state.
+pub unsafe fn restore(buffer: &[u8]) -> Result<(), Error> {
+    let vl = vector_length();
+    check_buffer(buffer, vl)?;
+
+    let z = buffer.as_ptr();
+    // SAFETY: the predicates follow the 32 Z registers in `buffer`, which `check_buffer` has
+    // confirmed is long enough
+    let p = unsafe { z.add(32 * vl) };
+    restore_registers(z, p, !is_streaming());
+
+    Ok(())
+}
+
+/// Encode a vector length as a `ZCR_ELx.LEN` or `SMCR_ELx.LEN` value
+fn len(vl: usize) -> Result<u64, Error> {
+    if (MIN_VL..=MAX_VL).contains(&vl) && vl.is_multiple_of(MIN_VL) {
+        Ok((vl / MIN_VL - 1) as u64)
+    } else {
+        Err(Error::InvalidVectorLength(vl))
+    }
+}
+
+fn check_buffer(buffer: &[u8], vl: usize) -> Result<(), Error> {
+    if !(buffer.as_ptr() as usize).is_multiple_of(16) {
+        Err(Error::Unaligned)
+    } else if buffer.len() < state_size(vl) {
+        Err(Error::BufferTooSmall(state_size(vl)))
+    } else {
+        Ok(())
+    }
+}
+
+fn zcr_len(el: ExceptionLevel) -> u64 {
+    match el {
+        ExceptionLevel::EL0 | ExceptionLevel::EL1 => ZCR_EL1.read(ZCR_EL1::LEN),
+        ExceptionLevel::EL2 => ZCR_EL2.read(ZCR_EL2::LEN),
+        ExceptionLevel::EL3 => ZCR_EL3.read(ZCR_EL3::LEN),
+    }
+}
+
+fn set_zcr_len(el: ExceptionLevel, len: u64) {
+    match el {
+        ExceptionLevel::EL0 | ExceptionLevel::EL1 => ZCR_EL1.write(ZCR_EL1::LEN.val(len)),
+        ExceptionLevel::EL2 => ZCR_EL2.write(ZCR_EL2::LEN.val(len)),
+        ExceptionLevel::EL3 => ZCR_EL3.write(ZCR_EL3::LEN.val(len)),
+    }
+    barrier::isb(barrier::SY);
+}
+
+fn smcr_len(el: ExceptionLevel) -> u64 {
+    match el {
+        ExceptionLevel::EL0 | ExceptionLevel::EL1 => SMCR_EL1.read(SMCR_EL1::LEN),
+        ExceptionLevel::EL2 => SMCR_EL2.read(SMCR_EL2::LEN),
+        ExceptionLevel::EL3 => SMCR_EL3.read(SMCR_EL3::LEN),
+    }
+}
+
+fn set_smcr_len(el: ExceptionLevel, len: u64) {
+    match el {
+        ExceptionLevel::EL0 | ExceptionLevel::EL1 => SMCR_EL1.modify(SMCR_EL1::LEN.val(len)),
+        ExceptionLevel::EL2 => SMCR_EL2.modify(SMCR_EL2::LEN.val(len)),
+        ExceptionLevel::EL3 => SMCR_EL3.modify(SMCR_EL3::LEN.val(len)),
+    }
+    barrier::isb(barrier::SY);
+}
+
+/// Store the Z registers at `z`, the P registers at `p` and, if `ffr` is set, FFR after them
+#[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+fn save_registers(z: *mut u8, p: *mut u8, ffr: bool) {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => unsafe {
+            use core::arch::asm;
+
+            asm!(
+                ".arch_extension sve",
+                ".irp n, 0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23,24,25,26,27,28,29,30,31",
+                "STR z\\n, [{z}, #\\n, MUL VL]",
+                ".endr",
+                ".irp n, 0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15",
+                "STR p\\n, [{p}, #\\n, MUL VL]",
+                ".endr",
+                z = in(reg) z,
+                p = in(reg) p,
+                options(nostack, preserves_flags)
+            );
+            if ffr {
+                // FFR can only be stored through a predicate register; P0 is reloaded afterwards
+                asm!(
+                    ".arch_extension sve",
+                    "RDFFR p0.b",
+                    "STR p0, [{p}, #16, MUL VL]",
+                    "LDR p0, [{p}]",
+                    p = in(reg) p,
+                    options(nostack, preserves_flags)
+                );
+            }
+        },
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+/// Load the Z registers from `z`, the P registers from `p` and, if `ffr` is set, FFR after them
+#[cfg_attr(not(target_arch = "aarch64"), allow(unused_variables))]
+fn restore_registers(z: *const u8, p: *const u8, ffr: bool) {
+    match () {
+        #[cfg(target_arch = "aarch64")]
+        () => unsafe {
+            use core::arch::asm;
+
+            if ffr {
+                // FFR can only be loaded through a predicate register; P0 is loaded afterwards
+                asm!(
+                    ".arch_extension sve",
+                    "LDR p0, [{p}, #16, MUL VL]",
+                    "WRFFR p0.b",
+                    p = in(reg) p,
+                    clobber_abi("C"),
+                    options(readonly, nostack, preserves_flags)
+                );
+            }
+            asm!(
+                ".arch_extension sve",
+                ".irp n, 0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15",
+                "LDR p\\n, [{p}, #\\n, MUL VL]",
+                ".endr",
+                ".irp n, 0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23,24,25,26,27,28,29,30,31",
+                "LDR z\\n, [{z}, #\\n, MUL VL]",
+                ".endr",
+                z = in(reg) z,
+                p = in(reg) p,
+                clobber_abi("C"),
+                options(readonly, nostack, preserves_flags)
+            );
+        },
+
+        #[cfg(not(target_arch = "aarch64"))]
+        () => unimplemented!(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lengths() {
+        assert_eq!(state_size(MIN_VL), 546);
+        assert_eq!(state_size(MAX_VL), 8736);
+        assert_eq!(len(MIN_VL), Ok(0));
+        assert_eq!(len(48), Ok(2));
+        assert_eq!(len(MAX_VL), Ok(15));
+        assert_eq!(len(0), Err(Error::InvalidVectorLength(0)));
+        assert_eq!(len(24), Err(Error::InvalidVectorLength(24)));
+        assert_eq!(len(272), Err(Error::InvalidVectorLength(272)));
+    }
+}
@@ -0,0 +1,623 @@
+//! Decoding of the Exception Syndrome Registers
+//!
+//! [`Syndrome`] turns the raw value of `ESR_EL1`, `ESR_EL2` or `ESR_EL3` into a
+//! typed description of the exception. The decoding is pure Rust and does not
+//! access any register, so it can be used on values saved by an exception
+//! handler or on values captured elsewhere.
+//!
+//! See section D24.2.40 "ESR_EL1, Exception Syndrome Register (EL1)" in the
+//! [ARM Architecture Reference Manual v8][armv8].
+//!
+//! [armv8]: https://developer.arm.com/documentation/ddi0487/latest/
+
+use core::ops::{BitAnd, BitOr, BitOrAssign, Not};
+
+/// Exception Class values, as found in `ESR_ELx.EC`
+mod ec {
+    pub const UNKNOWN: u8 = 0b00_0000;
+    pub const WFX: u8 = 0b00_0001;
+    pub const FP_SIMD_ACCESS: u8 = 0b00_0111;
+    pub const ILLEGAL_EXECUTION_STATE: u8 = 0b00_1110;
+    pub const SVC64: u8 = 0b01_0101;
+    pub const HVC64: u8 = 0b01_0110;
+    pub const SMC64: u8 = 0b01_0111;
+    pub const SYS_REG: u8 = 0b01_1000;
+    pub const SVE_ACCESS: u8 = 0b01_1001;
+    pub const INSTR_ABORT_LOWER: u8 = 0b10_0000;
+    pub const INSTR_ABORT_CURRENT: u8 = 0b10_0001;
+    pub const PC_ALIGNMENT: u8 = 0b10_0010;
+    pub const DATA_ABORT_LOWER: u8 = 0b10_0100;
+    pub const DATA_ABORT_CURRENT: u8 = 0b10_0101;
+    pub const SP_ALIGNMENT: u8 = 0b10_0110;
+    pub const TRAPPED_FP64: u8 = 0b10_1100;
+    pub const SERROR: u8 = 0b10_1111;
+    pub const BREAKPOINT_LOWER: u8 = 0b11_0000;
+    pub const BREAKPOINT_CURRENT: u8 = 0b11_0001;
+    pub const SOFTWARE_STEP_LOWER: u8 = 0b11_0010;
+    pub const SOFTWARE_STEP_CURRENT: u8 = 0b11_0011;
+    pub const WATCHPOINT_LOWER: u8 = 0b11_0100;
+    pub const WATCHPOINT_CURRENT: u8 = 0b11_0101;
+    pub const BRK64: u8 = 0b11_1100;
+}
+
+/// Extracts `width` bits starting at bit `offset`
+const fn bits(value: u64, offset: u32, width: u32) -> u64 {
+    (value >> offset) & ((1 << width) - 1)
+}
+
+const fn bit(value: u64, offset: u32) -> bool {
+    bits(value, offset, 1) != 0
+}
+
+/// A decoded exception syndrome
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Syndrome {
+    /// Exception for an unknown reason, e.g. an UNDEFINED instruction
+    Unknown,
+    /// Trapped WFI, WFE, WFIT or WFET instruction
+    WfxTrap {
+        /// Which instruction was trapped
+        kind: WfxKind,
+        /// Register holding the timeout argument of WFIT/WFET, if valid
+        rn: Option<u8>,
+    },
+    /// Access to SME, SVE, Advanced SIMD or floating-point functionality trapped by
+    /// `CPACR_EL1.FPEN`, `CPTR_EL2.FPEN`, `CPTR_EL2.TFP` or `CPTR_EL3.TFP`
+    FpSimdAccess,
+    /// Access to SVE functionality trapped
+    SveAccess,
+    /// Illegal Execution state
+    IllegalExecutionState,
+    /// SVC instruction executed in AArch64 state, with its immediate
+    Svc(u16),
+    /// HVC instruction executed in AArch64 state, with its immediate
+    Hvc(u16),
+    /// SMC instruction executed in AArch64 state, with its immediate
+    Smc(u16),
+    /// Trapped MSR, MRS or System instruction executed in AArch64 state
+    SysRegTrap {
+        /// The Op0 value from the issued instruction
+        op0: u8,
+        /// The Op1 value from the issued instruction
+        op1: u8,
+        /// The CRn value from the issued instruction
+        crn: u8,
+        /// The CRm value from the issued instruction
+        crm: u8,
+        /// The Op2 value from the issued instruction
+        op2: u8,
+        /// The Rt value from the issued instruction, i.e. the general-purpose register used for
+        /// the transfer
+        rt: u8,
+        /// `true` for a read from a system register (MRS), `false` for a write (MSR) or a System
+        /// instruction
+        read: bool,
+    },
+    /// Instruction Abort
+    InstructionAbort {
+        /// `true` if the abort was taken from a lower Exception level
+        lower_el: bool,
+        /// Instruction Fault Status Code
+        ifsc: FaultStatus,
+        /// `true` if the fault was on a stage 2 translation for a stage 1 translation table walk
+        s1ptw: bool,
+        /// External abort type
+        ea: bool,
+        /// `true` if the value held in `FAR_ELx` is not valid
+        far_not_valid: bool,
+    },
+    /// Data Abort
+    DataAbort {
+        /// `true` if the abort was taken from a lower Exception level
+        lower_el: bool,
+        /// Data Fault Status Code
+        dfsc: FaultStatus,
+        /// `true` if the abort was caused by writing to memory, `false` for a read
+        wnr: bool,
+        /// Instruction Syndrome Valid; `access_size`, `sse`, `srt`, `sf` and `ar` only hold
+        /// meaningful values when this is `true`
+        isv: bool,
+        /// Size of the access attempted by the faulting operation
+        access_size: AccessSize,
+        /// `true` if the loaded value was sign-extended
+        sse: bool,
+        /// The register number of the Rt operand of the faulting instruction
+        srt: u8,
+        /// `true` if the instruction loads or stores a 64-bit register
+        sf: bool,
+        /// `true` if the instruction has acquire/release semantics
+        ar: bool,
+        /// `true` if the fault came from a cache maintenance or address translation instruction
+        cm: bool,
+        /// `true` if the fault was on a stage 2 translation for a stage 1 translation table walk
+        s1ptw: bool,
+        /// External abort type
+        ea: bool,
+        /// `true` if the value held in `FAR_ELx` is not valid
+        far_not_valid: bool,
+    },
+    /// PC alignment fault
+    PcAlignment,
+    /// SP alignment fault
+    SpAlignment,
+    /// Trapped IEEE 754 floating-point exception taken from AArch64 state
+    TrappedFp {
+        /// The exceptions that caused the trap, or `None` if the implementation does not record
+        /// them (`ISS.TFV` is 0)
+        exceptions: Option<Exceptions>,
+    },
+    /// SError exception
+    SError {
+        /// The Instruction Specific Syndrome of the SError
+        iss: u32,
+    },
+    /// Breakpoint exception
+    Breakpoint {
+        /// `true` if the exception was taken from a lower Exception level
+        lower_el: bool,
+    },
+    /// Software Step exception
+    SoftwareStep {
+        /// `true` if the exception was taken from a lower Exception level
+        lower_el: bool,
+        /// `true` if the stepped instruction was a Load-Exclusive, only meaningful if `isv` is
+        /// `true`
+        ex: bool,
+        /// Instruction Syndrome Valid
+        isv: bool,
+    },
+    /// Watchpoint exception
+    Watchpoint {
+        /// `true` if the exception was taken from a lower Exception level
+        lower_el: bool,
+        /// `true` if the access was a write, `false` for a read
+        wnr: bool,
+        /// `true` if the watchpoint was hit by a cache maintenance instruction
+        cm: bool,
+        /// `true` if the value held in `FAR_ELx` is not valid
+        far_not_valid: bool,
+    },
+    /// BRK instruction executed in AArch64 state, with its immediate
+    Brk(u16),
+    /// Any other Exception Class
+    Other {
+        /// The Exception Class
+        ec: u8,
+        /// The Instruction Specific Syndrome
+        iss: u32,
+    },
+}
+
+impl Syndrome {
+    /// Decode the raw value of an `ESR_EL1`, `ESR_EL2` or `ESR_EL3` register
+    pub const fn decode(esr: u64) -> Syndrome {
+        let ec = bits(esr, 26, 6) as u8;
+        let iss = bits(esr, 0, 25);
+
+        match ec {
+            ec::UNKNOWN => Syndrome::Unknown,
+            ec::WFX => Syndrome::WfxTrap {
+                kind: WfxKind::from_bits(bits(iss, 0, 2) as u8),
+                rn: if bit(iss, 2) {
+                    Some(bits(iss, 5, 5) as u8)
+                } else {
+                    None
+                },
+            },
+            ec::FP_SIMD_ACCESS => Syndrome::FpSimdAccess,
+            ec::SVE_ACCESS => Syndrome::SveAccess,
+            ec::ILLEGAL_EXECUTION_STATE => Syndrome::IllegalExecutionState,
+            ec::SVC64 => Syndrome::Svc(bits(iss, 0, 16) as u16),
+            ec::HVC64 => Syndrome::Hvc(bits(iss, 0, 16) as u16),
+            ec::SMC64 => Syndrome::Smc(bits(iss, 0, 16) as u16),
+            ec::SYS_REG => Syndrome::SysRegTrap {
+                op0: bits(iss, 20, 2) as u8,
+                op2: bits(iss, 17, 3) as u8,
+                op1: bits(iss, 14, 3) as u8,
+                crn: bits(iss, 10, 4) as u8,
+                rt: bits(iss, 5, 5) as u8,
+                crm: bits(iss, 1, 4) as u8,
+                read: bit(iss, 0),
+            },
+            ec::INSTR_ABORT_LOWER | ec::INSTR_ABORT_CURRENT => Syndrome::InstructionAbort {
+                lower_el: ec == ec::INSTR_ABORT_LOWER,
+                ifsc: FaultStatus::from_bits(bits(iss, 0, 6) as u8),
+                s1ptw: bit(iss, 7),
+                ea: bit(iss, 9),
+                far_not_valid: bit(iss, 10),
+            },
+            ec::DATA_ABORT_LOWER | ec::DATA_ABORT_CURRENT => Syndrome::DataAbort {
+                lower_el: ec == ec::DATA_ABORT_LOWER,
+                dfsc: FaultStatus::from_bits(bits(iss, 0, 6) as u8),
+                wnr: bit(iss, 6),
+                isv: bit(iss, 24),
+                access_size: AccessSize::from_bits(bits(iss, 22, 2) as u8),
+                sse: bit(iss, 21),
+                srt: bits(iss, 16, 5) as u8,
+                sf: bit(iss, 15),
+                ar: bit(iss, 14),
+                cm: bit(iss, 8),
+                s1ptw: bit(iss, 7),
+                ea: bit(iss, 9),
+                far_not_valid: bit(iss, 10),
+            },
+            ec::PC_ALIGNMENT => Syndrome::PcAlignment,
+            ec::SP_ALIGNMENT => Syndrome::SpAlignment,
+            ec::TRAPPED_FP64 => Syndrome::TrappedFp {
+                exceptions: if bit(iss, 23) {
+                    Some(Exceptions::from_bits(iss))
+                } else {
+                    None
+                },
+            },
+            ec::SERROR => Syndrome::SError { iss: iss as u32 },
+            ec::BREAKPOINT_LOWER | ec::BREAKPOINT_CURRENT => Syndrome::Breakpoint {
+                lower_el: ec == ec::BREAKPOINT_LOWER,
+            },
+            ec::SOFTWARE_STEP_LOWER | ec::SOFTWARE_STEP_CURRENT => Syndrome::SoftwareStep {
+                lower_el: ec == ec::SOFTWARE_STEP_LOWER,
+                ex: bit(iss, 6),
+                isv: bit(iss, 24),
+            },
+            ec::WATCHPOINT_LOWER | ec::WATCHPOINT_CURRENT => Syndrome::Watchpoint {
+                lower_el: ec == ec::WATCHPOINT_LOWER,
+                wnr: bit(iss, 6),
+                cm: bit(iss, 8),
+                far_not_valid: bit(iss, 10),
+            },
+            ec::BRK64 => Syndrome::Brk(bits(iss, 0, 16) as u16),
+            _ => Syndrome::Other {
+                ec,
+                iss: iss as u32,
+            },
+        }
+    }
+}
+
+impl From<u64> for Syndrome {
+    fn from(esr: u64) -> Self {
+        Syndrome::decode(esr)
+    }
+}
+
+/// Which WFx instruction was trapped
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WfxKind {
+    /// Wait For Interrupt
+    Wfi,
+    /// Wait For Event
+    Wfe,
+    /// Wait For Interrupt with Timeout (FEAT_WFxT)
+    Wfit,
+    /// Wait For Event with Timeout (FEAT_WFxT)
+    Wfet,
+}
+
+impl WfxKind {
+    const fn from_bits(ti: u8) -> WfxKind {
+        match ti & 0b11 {
+            0b00 => WfxKind::Wfi,
+            0b01 => WfxKind::Wfe,
+            0b10 => WfxKind::Wfit,
+            _ => WfxKind::Wfet,
+        }
+    }
+}
+
+/// Size of the access that caused a Data Abort
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AccessSize {
+    /// Byte
+    Byte = 0b00,
+    /// Halfword
+    Halfword = 0b01,
+    /// Word
+    Word = 0b10,
+    /// Doubleword
+    Doubleword = 0b11,
+}
+
+impl AccessSize {
+    const fn from_bits(sas: u8) -> AccessSize {
+        match sas & 0b11 {
+            0b00 => AccessSize::Byte,
+            0b01 => AccessSize::Halfword,
+            0b10 => AccessSize::Word,
+            _ => AccessSize::Doubleword,
+        }
+    }
+
+    /// The size of the access in bytes
+    pub const fn bytes(self) -> usize {
+        1 << (self as u8)
+    }
+}
+
+/// A decoded Instruction or Data Fault Status Code (IFSC/DFSC)
+///
+/// Translation table levels are reported as an `i8` because FEAT_LPA2 introduces
+/// faults at level -1.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FaultStatus {
+    /// Address size fault
+    AddressSize {
+        /// Translation table level
+        level: i8,
+    },
+    /// Translation fault
+    Translation {
+        /// Translation table level
+        level: i8,
+    },
+    /// Access flag fault
+    AccessFlag {
+        /// Translation table level
+        level: i8,
+    },
+    /// Permission fault
+    Permission {
+        /// Translation table level
+        level: i8,
+    },
+    /// Synchronous External abort, not on translation table walk or hardware update of
+    /// translation table
+    SyncExternal,
+    /// Synchronous Tag Check Fault (FEAT_MTE2)
+    TagCheck,
+    /// Synchronous External abort on translation table walk or hardware update of translation
+    /// table
+    SyncExternalOnWalk {
+        /// Translation table level
+        level: i8,
+    },
+    /// Synchronous parity or ECC error on memory access, not on translation table walk
+    SyncParityOrEcc,
+    /// Synchronous parity or ECC error on memory access on translation table walk or hardware
+    /// update of translation table
+    SyncParityOrEccOnWalk {
+        /// Translation table level
+        level: i8,
+    },
+    /// Alignment fault
+    Alignment,
+    /// Debug exception, reported by breakpoints, watchpoints and software step
+    Debug,
+    /// TLB conflict abort
+    TlbConflict,
+    /// Unsupported atomic hardware update fault
+    UnsupportedAtomicUpdate,
+    /// Any other (reserved or IMPLEMENTATION DEFINED) fault status code
+    Other(u8),
+}
+
+impl FaultStatus {
+    /// Decode the 6-bit IFSC/DFSC field
+    pub const fn from_bits(fsc: u8) -> FaultStatus {
+        let level = (fsc & 0b11) as i8;
+        match fsc & 0b11_1111 {
+            0b00_0000..=0b00_0011 => FaultStatus::AddressSize { level },
+            0b10_1001 => FaultStatus::AddressSize { level: -1 },
+            0b00_0100..=0b00_0111 => FaultStatus::Translation { level },
+            0b10_1011 => FaultStatus::Translation { level: -1 },
+            0b00_1000..=0b00_1011 => FaultStatus::AccessFlag { level },
+            0b00_1100..=0b00_1111 => FaultStatus::Permission { level },
+            0b01_0000 => FaultStatus::SyncExternal,
+            0b01_0001 => FaultStatus::TagCheck,
+            0b01_0011 => FaultStatus::SyncExternalOnWalk { level: -1 },
+            0b01_0100..=0b01_0111 => FaultStatus::SyncExternalOnWalk { level },
+            0b01_1000 => FaultStatus::SyncParityOrEcc,
+            0b01_1011 => FaultStatus::SyncParityOrEccOnWalk { level: -1 },
+            0b01_1100..=0b01_1111 => FaultStatus::SyncParityOrEccOnWalk { level },
+            0b10_0001 => FaultStatus::Alignment,
+            0b10_0010 => FaultStatus::Debug,
+            0b11_0000 => FaultStatus::TlbConflict,
+            0b11_0001 => FaultStatus::UnsupportedAtomicUpdate,
+            other => FaultStatus::Other(other),
+        }
+    }
+}
+
+/// A set of IEEE 754 floating-point exceptions
+///
+/// The bit positions match the cumulative exception bits of `FPSR` and the exception fields of
+/// the syndrome of a trapped floating-point exception.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Exceptions(u8);
+
+impl Exceptions {
+    /// No exception
+    pub const NONE: Exceptions = Exceptions(0);
+    /// Invalid Operation
+    pub const INVALID_OPERATION: Exceptions = Exceptions(1 << 0);
+    /// Divide by Zero
+    pub const DIVIDE_BY_ZERO: Exceptions = Exceptions(1 << 1);
+    /// Overflow
+    pub const OVERFLOW: Exceptions = Exceptions(1 << 2);
+    /// Underflow
+    pub const UNDERFLOW: Exceptions = Exceptions(1 << 3);
+    /// Inexact
+    pub const INEXACT: Exceptions = Exceptions(1 << 4);
+    /// Input Denormal
+    pub const INPUT_DENORMAL: Exceptions = Exceptions(1 << 7);
+    /// All exceptions
+    pub const ALL: Exceptions = Exceptions(0b1001_1111);
+
+    /// Creates a set from the `IOC`..`IDC` bits of `FPSR`, ignoring any other bit
+    pub const fn from_bits(bits: u64) -> Exceptions {
+        Exceptions(bits as u8 & Self::ALL.0)
+    }
+
+    /// The set as `IOC`..`IDC` bits of `FPSR`
+    pub const fn bits(self) -> u64 {
+        self.0 as u64
+    }
+
+    /// Returns `true` if the set is empty
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if all exceptions in `other` are in the set
+    pub const fn contains(self, other: Exceptions) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for Exceptions {
+    type Output = Exceptions;
+
+    fn bitor(self, rhs: Exceptions) -> Exceptions {
+        Exceptions(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Exceptions {
+    fn bitor_assign(&mut self, rhs: Exceptions) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for Exceptions {
+    type Output = Exceptions;
+
+    fn bitand(self, rhs: Exceptions) -> Exceptions {
+        Exceptions(self.0 & rhs.0)
+    }
+}
+
+impl Not for Exceptions {
+    type Output = Exceptions;
+
+    fn not(self) -> Exceptions {
+        Exceptions(!self.0 & Self::ALL.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const fn esr(ec: u64, iss: u64) -> u64 {
+        ec << 26 | 1 << 25 | iss
+    }
+
+    #[test]
+    fn svc_hvc_smc_brk() {
+        assert_eq!(Syndrome::decode(esr(0x15, 0x1234)), Syndrome::Svc(0x1234));
+        assert_eq!(Syndrome::decode(esr(0x16, 0xbeef)), Syndrome::Hvc(0xbeef));
+        assert_eq!(Syndrome::decode(esr(0x17, 1)), Syndrome::Smc(1));
+        assert_eq!(Syndrome::decode(esr(0x3c, 0xf000)), Syndrome::Brk(0xf000));
+    }
+
+    #[test]
+    fn data_abort() {
+        // STR W3, [X0] to an unmapped level 3 page, from the current EL
+        let iss = 1 << 24 | 0b10 << 22 | 3 << 16 | 1 << 6 | 0b00_0111;
+        assert_eq!(
+            Syndrome::decode(esr(0x25, iss)),
+            Syndrome::DataAbort {
+                lower_el: false,
+                dfsc: FaultStatus::Translation { level: 3 },
+                wnr: true,
+                isv: true,
+                access_size: AccessSize::Word,
+                sse: false,
+                srt: 3,
+                sf: false,
+                ar: false,
+                cm: false,
+                s1ptw: false,
+                ea: false,
+                far_not_valid: false,
+            }
+        );
+    }
+
+    #[test]
+    fn sys_reg_trap() {
+        // MRS X5, CNTVCT_EL0 -> op0=3, op1=3, CRn=14, CRm=0, op2=2
+        let iss = 3 << 20 | 2 << 17 | 3 << 14 | 14 << 10 | 5 << 5 | 1;
+        assert_eq!(
+            Syndrome::decode(esr(0x18, iss)),
+            Syndrome::SysRegTrap {
+                op0: 3,
+                op1: 3,
+                crn: 14,
+                crm: 0,
+                op2: 2,
+                rt: 5,
+                read: true,
+            }
+        );
+    }
+
+    #[test]
+    fn wfx_trap() {
+        assert_eq!(
+            Syndrome::decode(esr(0x01, 0b01)),
+            Syndrome::WfxTrap {
+                kind: WfxKind::Wfe,
+                rn: None
+            }
+        );
+        assert_eq!(
+            Syndrome::decode(esr(0x01, 7 << 5 | 1 << 2 | 0b10)),
+            Syndrome::WfxTrap {
+                kind: WfxKind::Wfit,
+                rn: Some(7)
+            }
+        );
+    }
+
+    #[test]
+    fn trapped_fp() {
+        // FDIV by zero with TFV set
+        assert_eq!(
+            Syndrome::decode(esr(0x2c, 1 << 23 | 1 << 1)),
+            Syndrome::TrappedFp {
+                exceptions: Some(Exceptions::DIVIDE_BY_ZERO)
+            }
+        );
+        assert_eq!(
+            Syndrome::decode(esr(0x2c, 0)),
+            Syndrome::TrappedFp { exceptions: None }
+        );
+    }
+
+    #[test]
+    fn fault_status() {
+        assert_eq!(
+            FaultStatus::from_bits(0b00_1101),
+            FaultStatus::Permission { level: 1 }
+        );
+        assert_eq!(
+            FaultStatus::from_bits(0b10_1011),
+            FaultStatus::Translation { level: -1 }
+        );
+        assert_eq!(FaultStatus::from_bits(0b11_0100), FaultStatus::Other(0x34));
+    }
+
+    #[test]
+    fn other() {
+        assert_eq!(
+            Syndrome::decode(esr(0x1c, 0x2)),
+            Syndrome::Other { ec: 0x1c, iss: 2 }
+        );
+    }
+
+    #[test]
+    fn exceptions() {
+        let set = Exceptions::INVALID_OPERATION | Exceptions::INPUT_DENORMAL;
+        assert_eq!(set.bits(), 0x81);
+        assert!(set.contains(Exceptions::INPUT_DENORMAL));
+        assert!(!set.contains(Exceptions::ALL));
+        assert_eq!(!set & Exceptions::INVALID_OPERATION, Exceptions::NONE);
+        // QC and the AArch32 NZCV bits are not exceptions
+        assert_eq!(
+            Exceptions::from_bits(0xf800_0102),
+            Exceptions::DIVIDE_BY_ZERO
+        );
+    }
+}
@@ -1,10 +1,12 @@
-//! Provides linker script to top-level binary crate
+//! Provides linker script to top-level binary crate and sets up `--cfg` flags based on the
+//! compilation target (`$TARGET`)
 
 use std::{env, fs, path::Path};
 
 const SCRIPT: &str = "link.x";
 
 fn main() {
+    arm_targets::process();
     copy_linker_script_into_linker_search_path();
 }
 
@@ -14,18 +14,139 @@
 use core::arch::{asm, naked_asm};
 
 #[cfg(target_arch = "aarch64")]
-use aarch64_cpu::registers::{self, DAIF, Readable as _, Writeable as _};
+use aarch64_cpu::registers::{self, DAIF, ReadWriteable as _, Readable as _, Writeable as _};
 
 mod sections;
+pub mod smccc;
+pub mod smp;
+pub mod step;
+pub mod task;
+pub mod thread;
 
+#[doc(inline)]
+pub use aarch64_cpu::ExceptionLevel;
 #[doc(inline)]
 pub use sections::Section;
 
-/// Executes `f` at one lower Exception Level on the given `stack` memory
+/// Interrupt Mask Bits (DAIF); a `true` field masks the corresponding exceptions
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Daif {
+    /// Watchpoint, Breakpoint and Software Step exceptions
+    pub debug: bool,
+    /// SError exceptions
+    pub serror: bool,
+    /// IRQ exceptions
+    pub irq: bool,
+    /// FIQ exceptions
+    pub fiq: bool,
+}
+
+impl Daif {
+    /// All exceptions masked
+    pub const MASKED: Daif = Daif {
+        debug: true,
+        serror: true,
+        irq: true,
+        fiq: true,
+    };
+
+    /// No exception masked
+    pub const UNMASKED: Daif = Daif {
+        debug: false,
+        serror: false,
+        irq: false,
+        fiq: false,
+    };
+
+    /// Reads the Interrupt Mask Bits of the current EL
+    #[cfg(target_arch = "aarch64")]
+    pub fn current() -> Daif {
+        let daif = DAIF.extract();
+        Daif {
+            debug: daif.is_set(DAIF::D),
+            serror: daif.is_set(DAIF::A),
+            irq: daif.is_set(DAIF::I),
+            fiq: daif.is_set(DAIF::F),
+        }
+    }
+
+    /// [ARM-R64/C5.2.15] the mask bits have the same position in DAIF and SPSR
+    #[cfg(target_arch = "aarch64")]
+    fn bits(self) -> u64 {
+        (self.debug as u64) << 9
+            | (self.serror as u64) << 8
+            | (self.irq as u64) << 7
+            | (self.fiq as u64) << 6
+    }
+}
+
+/// Configuration for [`drop_exception_level_with`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DropConfig {
+    /// The Exception Level to run the entry point at
+    ///
+    /// Must be lower than the current EL.
+    pub el: ExceptionLevel,
+    /// The Interrupt Mask Bits the entry point starts with
+    pub daif: Daif,
+    /// Whether the Exception Levels below EL3 run in Non-secure state
+    ///
+    /// Only used when dropping from EL3. Dropping to Secure EL2 needs FEAT_SEL2.
+    pub non_secure: bool,
+}
+
+impl DropConfig {
+    /// Drops to the next lower implemented EL and inherits the current DAIF
+    ///
+    /// From EL3 this drops to Non-secure EL2, or to Non-secure EL1 if EL2 is not implemented.
+    #[cfg(target_arch = "aarch64")]
+    pub fn next_lower() -> DropConfig {
+        let el = match ExceptionLevel::current() {
+            ExceptionLevel::EL0 => panic!("already at EL0"),
+            ExceptionLevel::EL1 => ExceptionLevel::EL0,
+            ExceptionLevel::EL2 => ExceptionLevel::EL1,
+            ExceptionLevel::EL3 => {
+                if registers::ID_AA64PFR0_EL1
+                    .matches_all(registers::ID_AA64PFR0_EL1::EL2::NotImplemented)
+                {
+                    ExceptionLevel::EL1
+                } else {
+                    ExceptionLevel::EL2
+                }
+            }
+        };
+
+        DropConfig {
+            el,
+            daif: Daif::current(),
+            non_secure: true,
+        }
+    }
+}
+
+/// Executes `f` at the next lower Exception Level on the given `stack` memory
 ///
-/// `f` will inherit the current Interrupt Mask Bits (DAIF)
+/// `f` will inherit the current Interrupt Mask Bits (DAIF). See [`DropConfig::next_lower`] for the
+/// EL that `f` runs at and [`drop_exception_level_with`] to choose it.
 #[cfg(target_arch = "aarch64")]
 pub fn drop_exception_level(f: extern "C" fn() -> !, stack: Stack) -> ! {
+    drop_exception_level_with(DropConfig::next_lower(), f, stack)
+}
+
+/// Executes `f` at the Exception Level selected by `config` on the given `stack` memory
+///
+/// At EL1 and EL2, `f` starts with the vector table configured via `exception_handlers` installed.
+/// At EL0, the exceptions it takes, e.g. SVC calls, are handled by the `*_lower` handlers of the
+/// EL that dropped to it. When dropping from EL2 to EL0, `HCR_EL2.TGE` is set to route those
+/// exceptions to EL2; EL0 then runs with the EL1&0 stage 1 translation disabled or, when
+/// `HCR_EL2.E2H` is set, in the EL2&0 translation regime. Dropping from EL3 to EL0 is not
+/// supported, as nothing would handle its exceptions.
+///
+/// When dropping from EL3, `SCR_EL3` is configured for AArch64 lower ELs in the Security state
+/// selected by `config`, with HVC enabled when dropping to EL2. When dropping to EL1 with EL2
+/// enabled, `HCR_EL2` is configured for an AArch64 EL1 that takes its own exceptions.
+#[cfg(target_arch = "aarch64")]
+pub fn drop_exception_level_with(config: DropConfig, f: extern "C" fn() -> !, stack: Stack) -> ! {
     /// This function is the first thing that runs at the lower EL and serves
     /// as a "trampoline" into the user-defined entry point `f`. It does the
     /// following:
@@ -73,20 +194,81 @@ pub fn drop_exception_level(f: extern "C" fn() -> !, stack: Stack) -> ! {
         );
     }
 
+    /// Like `lower_el_entry` but for EL0, which cannot set up its own vector table nor read
+    /// `CurrentEL`. Setting SP here, rather than writing `SP_EL0` from the caller, also works when
+    /// the caller runs on SP_EL0.
+    #[unsafe(naked)]
+    extern "C" fn el0_entry(f: usize, initial_sp: usize) -> ! {
+        naked_asm!("mov SP, x1", "br x0");
+    }
+
     let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    let target = config.el as u64;
+    assert!(
+        target < el,
+        "cannot drop from EL{el} to EL{target}, which is not lower"
+    );
+    assert!(
+        !(el == 3 && config.el == ExceptionLevel::EL0),
+        "cannot drop from EL3 to EL0; drop to EL1 or EL2 first"
+    );
+
+    // [ARM-R64/C5.2.15] EL0t, EL1h or EL2h
+    let (entry, m) = match config.el {
+        ExceptionLevel::EL0 => (el0_entry as *const () as u64, 0b0000),
+        ExceptionLevel::EL1 => (lower_el_entry as *const () as u64, 0b0101),
+        ExceptionLevel::EL2 => (lower_el_entry as *const () as u64, 0b1001),
+        ExceptionLevel::EL3 => unreachable!(),
+    };
+    let spsr = config.daif.bits() | m;
 
     match el {
-        0 => panic!("already at EL0"),
-        1 => todo!(),
+        1 => {
+            registers::SPSR_EL1.set(spsr);
+            registers::ELR_EL1.set(entry);
+        }
         2 => {
-            let daif = DAIF.get();
-            // [ARM-R64/C5.2.15] EL1 with SP_EL1 (EL1h)
-            let m = 0b0101;
-            registers::SPSR_EL2.set(daif | m);
+            use registers::HCR_EL2;
 
-            registers::ELR_EL2.set(lower_el_entry as *const () as u64);
+            // with TGE set, EL0's exceptions are taken to EL2 instead of the unconfigured EL1
+            let tge = if config.el == ExceptionLevel::EL0 {
+                HCR_EL2::TGE::EnableTrapGeneralExceptionsToEl2
+            } else {
+                HCR_EL2::TGE::DisableTrapGeneralExceptionsToEl2
+            };
+            // an AArch32 EL1 would also force EL0 into AArch32, so RW is needed for both
+            HCR_EL2.modify(HCR_EL2::RW::EL1IsAarch64 + tge);
+            registers::SPSR_EL2.set(spsr);
+            registers::ELR_EL2.set(entry);
+        }
+        3 => {
+            use registers::SCR_EL3;
+
+            let security = if config.non_secure {
+                SCR_EL3::NS::NonSecure
+            } else {
+                SCR_EL3::NS::Secure
+            };
+            let (hvc, secure_el2) = if config.el == ExceptionLevel::EL2 {
+                (
+                    SCR_EL3::HCE::HvcEnabled,
+                    SCR_EL3::EEL2.val(!config.non_secure as u64),
+                )
+            } else {
+                (SCR_EL3::HCE::HvcDisabled, SCR_EL3::EEL2::Disabled)
+            };
+            SCR_EL3.modify(SCR_EL3::RW::NextELIsAarch64 + security + hvc + secure_el2);
+
+            // EL2 controls the Execution state of EL1 in Non-secure state
+            let el2_implemented = !registers::ID_AA64PFR0_EL1
+                .matches_all(registers::ID_AA64PFR0_EL1::EL2::NotImplemented);
+            if config.el != ExceptionLevel::EL2 && config.non_secure && el2_implemented {
+                registers::HCR_EL2.modify(registers::HCR_EL2::RW::EL1IsAarch64);
+            }
+
+            registers::SPSR_EL3.set(spsr);
+            registers::ELR_EL3.set(entry);
         }
-        3 => todo!(),
         _ => unreachable!(),
     }
 
@@ -103,6 +285,31 @@ pub fn drop_exception_level(f: extern "C" fn() -> !, stack: Stack) -> ! {
     }
 }
 
+/// Executes `f` on the given `stack` memory at the current Exception Level, using SP_EL0
+///
+/// This selects SP_EL0 (`SPSel` = 0), so `f` and everything it calls run on `stack` while the
+/// exceptions taken to the current EL keep running on SP_ELx, i.e. on the current stack. A stack
+/// overflow in `f` thus leaves the exception handlers with a known-good stack. Exceptions taken
+/// while running on SP_EL0 are handled by the `*_current_sp0` handlers of [`ExceptionHandlers`].
+///
+/// Must be called at EL1 or higher.
+#[cfg(target_arch = "aarch64")]
+pub fn run_on_sp_el0(f: extern "C" fn() -> !, stack: Stack) -> ! {
+    // SAFETY: the current stack is left to the exception handlers; `f` does not return
+    unsafe {
+        asm!(
+            "msr SP_EL0, {initial_sp}",
+            "msr SPSel, #0",
+            "br {f}",
+            // the stack grows downwards ("full descending stack") so the initial SP value is the
+            // higher boundary of the memory block
+            initial_sp = in(reg) stack.higher(),
+            f = in(reg) f,
+            options(noreturn),
+        )
+    }
+}
+
 /// Start-up code for AArch64 - supports both Armv8-A and Armv8-R, at EL1 or EL2
 ///
 /// This is our default start-up code. You may instead supply your own `_start` function, which may
@@ -114,12 +321,42 @@ pub fn drop_exception_level(f: extern "C" fn() -> !, stack: Stack) -> ! {
 pub unsafe extern "C" fn _default_start() -> ! {
     core::arch::naked_asm!(
         r#"
-            mrs     x0, CPACR_EL1       // disable trapping on FPU/SIMD instructions in EL1
+            mrs     x0, CurrentEL       // disable trapping on FPU/SIMD instructions in the
+            cmp     x0, #(2 << 2)       // current EL and the ELs below it
+            b.lo    fp_el1
+            b.eq    fp_el2
+            mrs     x0, CPTR_EL3        // EL3: clear CPTR_EL3.TFP
+            bic     x0, x0, #(1 << 10)
+            msr     CPTR_EL3, x0
+        fp_el2:
+            mrs     x0, HCR_EL2         // with HCR_EL2.E2H set, CPACR_EL1 accesses from EL2 are
+            tbnz    x0, #34, fp_el1     // redirected to CPTR_EL2, whose FPEN is set below
+            mrs     x0, CPTR_EL2        // otherwise clear CPTR_EL2.TFP
+            bic     x0, x0, #(1 << 10)
+            msr     CPTR_EL2, x0
+        fp_el1:
+            mrs     x0, CPACR_EL1       // set CPACR_EL1.FPEN
             orr     x0, x0, #(0b11 << 20)
             msr     CPACR_EL1, x0
             isb
             bl      {get_cpuid}         // Put CPUID in x0
             cbz     x0, core0_only      // Check if CPUID is zero
+            cmp     x0, #{max_cores}    // cores without a mailbox slot sleep forever
+            b.hs    loop_wfi
+            ldr     x1, ={mailbox}      // wait until `start_core` releases this core
+            add     x1, x1, x0, lsl #{slot_shift}
+        wait_release:
+            dc      civac, x1           // the releasing core may have the slot in its data cache,
+            dsb     SY                  // so read it from the Point of Coherency
+            ldar    x2, [x1]            // slot state
+            cmp     x2, #{released}
+            b.eq    released
+            wfe
+            b       wait_release
+        released:
+            ldp     x1, x2, [x1, #8]    // entry point and initial SP
+            mov     sp, x2
+            b       {secondary_start}   // called with the CPUID in x0 and the entry point in x1
         loop_wfi:
             dsb     SY                  // Clear all pending data accesses
             wfi                         // Go to sleep
@@ -139,6 +376,11 @@ pub unsafe extern "C" fn _default_start() -> ! {
         "#,
         rust_start = sym rust_start,
         get_cpuid = sym get_cpuid,
+        max_cores = const smp::MAX_CORES,
+        mailbox = sym smp::MAILBOX,
+        slot_shift = const smp::SLOT_SHIFT,
+        released = const smp::RELEASED,
+        secondary_start = sym smp::secondary_start,
     )
 }
 
@@ -212,6 +454,10 @@ extern "C" fn set_vbar() {
 }
 
 /// Registers pushed onto the stack upon entering an exception handler
+///
+/// Changes that a handler makes to the registers are written back to them before the exception
+/// returns, except for `sp` when the exception was taken from the current EL with SP_ELx or from
+/// AArch32, and for the `esr` and `far` snapshots.
 #[derive(Debug)]
 // we may want to add other registers as fields in the future
 #[non_exhaustive]
@@ -228,12 +474,68 @@ pub struct StackedRegisters {
     pub elr: usize,
     /// Saved Program Status Register
     pub spsr: usize,
+    /// Stack Pointer of the interrupted code
+    ///
+    /// For exceptions taken from the lower EL this is `SP_EL0` or the `SP_ELx` of that EL, as
+    /// selected by `spsr`, and for exceptions taken from the current EL with SP_EL0 this is
+    /// `SP_EL0`. For exceptions taken from the current EL with SP_ELx this is the value SP had
+    /// before the exception.
+    ///
+    /// For exceptions taken from AArch32 this is zero; the AArch32 registers are mapped onto
+    /// X0-X30 instead, e.g. the User mode SP onto X13.
+    pub sp: usize,
+    /// Exception Syndrome Register, as read upon entering the handler
+    pub esr: u64,
+    /// Fault Address Register, as read upon entering the handler
+    pub far: u64,
 }
 
 // NOTE this size must match stack space reserved in the prologue of the
 // exception handlers
 #[cfg(target_arch = "aarch64")]
-const _: () = assert!(8 * 24 == core::mem::size_of::<StackedRegisters>());
+const _: () = assert!(8 * 26 == core::mem::size_of::<StackedRegisters>());
+
+impl StackedRegisters {
+    /// [ARM-R64/C5.2.15] Software Step bit of the SPSR
+    const SPSR_SS: usize = 1 << 21;
+
+    /// Sets the saved Software Step bit (`PSTATE.SS`) that the exception return restores
+    ///
+    /// While software step is [enabled](step::enable), returning with this bit set lets the lower
+    /// EL execute one instruction before it takes the next Software Step exception; returning with
+    /// it clear takes that exception before any instruction is executed.
+    pub fn set_software_step(&mut self, active: bool) {
+        if active {
+            self.spsr |= Self::SPSR_SS;
+        } else {
+            self.spsr &= !Self::SPSR_SS;
+        }
+    }
+
+    /// Returns the saved Software Step bit (`PSTATE.SS`)
+    pub fn software_step(&self) -> bool {
+        self.spsr & Self::SPSR_SS != 0
+    }
+
+    /// Makes the exception return skip the instruction at `elr`
+    ///
+    /// Use this for exceptions whose preferred return address is the instruction that caused them,
+    /// e.g. BRK instructions, trapped instructions or aborts, to resume after it.
+    pub fn skip_instruction(&mut self) {
+        // all A64 instructions are 4 bytes long
+        self.elr += 4;
+    }
+
+    /// Returns the Exception Level the exception was taken from, as saved in `SPSR.M[3:2]`
+    pub fn exception_level(&self) -> ExceptionLevel {
+        match (self.spsr >> 2) & 0b11 {
+            0 => ExceptionLevel::EL0,
+            1 => ExceptionLevel::EL1,
+            2 => ExceptionLevel::EL2,
+            _ => ExceptionLevel::EL3,
+        }
+    }
+}
 
 /// Registers exception handlers
 ///
@@ -243,7 +545,7 @@ const _: () = assert!(8 * 24 == core::mem::size_of::<StackedRegisters>());
 macro_rules! exception_handlers {
     ($handlers:path) => {
         core::arch::global_asm!("
-.macro push_registers el:req
+.macro push_registers el:req, from:req
     // X19-X28 are callee-saved so any code called from here will push them
     // onto the stack in its prologue; everything else we need to push and
     // pop ourselves
@@ -252,8 +554,8 @@ macro_rules! exception_handlers {
     // SPSR at the current EL as a nested exception will overwrite those
     // system registers
     //
-    // `!` adds -8*22 to SP _before_ pushing x0, x1 on the stack
-    stp x0,  x1,  [sp, #-(8 * 24)]!
+    // `!` adds -8*26 to SP _before_ pushing x0, x1 on the stack
+    stp x0,  x1,  [sp, #-(8 * 26)]!
     stp x2,  x3,  [sp, #8 * 2]
     stp x4,  x5,  [sp, #8 * 4]
     stp x6,  x7,  [sp, #8 * 6]
@@ -266,10 +568,53 @@ macro_rules! exception_handlers {
     mrs x0,  elr_\\el
     stp x30, x0,  [sp, #8 * 20]
     mrs x1,  spsr_\\el
-    str x1,       [sp, #8 * 22]
+.ifc \\from, lower
+    // [ARM-R64/C5.2.15] SPSR.M[0] selects SP_EL0 or SP_ELx, and SPSR.M[3:2]
+    // the EL; only EL3 can be entered from EL2
+    tbz x1,  #0,  1f
+    tbnz x1, #3,  2f
+    mrs x0,  sp_el1
+    b 3f
+2:
+    mrs x0,  sp_el2
+    b 3f
+1:
+    mrs x0,  sp_el0
+3:
+.endif
+.ifc \\from, current
+    add x0,  sp,  #8 * 26
+.endif
+.ifc \\from, current_sp0
+    mrs x0,  sp_el0
+.endif
+.ifc \\from, lower_aarch32
+    mov x0,  xzr
+.endif
+    stp x1,  x0,  [sp, #8 * 22]
+    mrs x0,  esr_\\el
+    mrs x1,  far_\\el
+    stp x0,  x1,  [sp, #8 * 24]
 .endm
 
-.macro pop_registers el:req
+.macro pop_registers el:req, from:req
+.ifc \\from, lower
+    ldp x1,  x0,  [sp, #8 * 22]
+    tbz x1,  #0,  1f
+    tbnz x1, #3,  2f
+    msr sp_el1, x0
+    b 3f
+2:
+    msr sp_el2, x0
+    b 3f
+1:
+    msr sp_el0, x0
+3:
+.endif
+.ifc \\from, current_sp0
+    ldr x0,       [sp, #8 * 23]
+    msr sp_el0, x0
+.endif
     ldp x2,  x3,  [sp, #8 * 2]
     ldp x4,  x5,  [sp, #8 * 4]
     ldp x6,  x7,  [sp, #8 * 6]
@@ -283,7 +628,7 @@ macro_rules! exception_handlers {
     msr elr_\\el, x0
     ldr x1,       [sp, #8 * 22]
     msr spsr_\\el, x1
-    ldp x0,  x1,  [sp], #8 * 24
+    ldp x0,  x1,  [sp], #8 * 26
 .endm
 
 .macro exception_vectors el:req
@@ -293,142 +638,326 @@ macro_rules! exception_handlers {
     .section .text._vbar_\\el
 _vbar_\\el:
     // [ARM-R64/D1.3.1.6] describes the layout; each vector is 0x80 bytes in size
-    // 0x000: 'Current Exception level with SP_EL0' -- these entries are unused
-    nop
-    .balign 0x200
+    // so they branch to the handlers, which come after the vector table
+    // 0x000: 'Current Exception level with SP_EL0'
+    b vbar_sync_current_sp0_\\el
+    .balign 0x80
+    b vbar_irq_current_sp0_\\el
+    .balign 0x80
+    b vbar_fiq_current_sp0_\\el
+    .balign 0x80
+    b vbar_serror_current_sp0_\\el
+    .balign 0x80
 
     // 0x200: 'Current Exception level with SP_ELx, x > 0'
+    b vbar_sync_current_\\el
+    .balign 0x80
+    b vbar_irq_current_\\el
+    .balign 0x80
+    b vbar_fiq_current_\\el
+    .balign 0x80
+    b vbar_serror_current_\\el
+    .balign 0x80
+
+    // 0x400: 'Lower Exception level using AArch64'
+    b vbar_sync_lower_\\el
+    .balign 0x80
+    b vbar_irq_lower_\\el
+    .balign 0x80
+    b vbar_fiq_lower_\\el
+    .balign 0x80
+    b vbar_serror_lower_\\el
+    .balign 0x80
+
+    // 0x600: 'Lower Exception level using AArch32'
+    b vbar_sync_lower_aarch32_\\el
+    .balign 0x80
+    b vbar_irq_lower_aarch32_\\el
+    .balign 0x80
+    b vbar_fiq_lower_aarch32_\\el
+    .balign 0x80
+    b vbar_serror_lower_aarch32_\\el
+    .balign 0x80
+
+vbar_sync_current_sp0_\\el:
+    push_registers \\el, current_sp0
+    mov x0, sp
+    bl {sync_current_sp0}
+    pop_registers \\el, current_sp0
+    eret
+
+vbar_irq_current_sp0_\\el:
+    push_registers \\el, current_sp0
+    mov x0, sp
+    bl {irq_current_sp0}
+    pop_registers \\el, current_sp0
+    eret
+
+vbar_fiq_current_sp0_\\el:
+    push_registers \\el, current_sp0
+    mov x0, sp
+    bl {fiq_current_sp0}
+    pop_registers \\el, current_sp0
+    eret
+
+vbar_serror_current_sp0_\\el:
+    push_registers \\el, current_sp0
+    mov x0, sp
+    bl {serror_current_sp0}
+    pop_registers \\el, current_sp0
+    eret
+
 vbar_sync_current_\\el:
-    push_registers \\el
+    push_registers \\el, current
     mov x0, sp
     bl {sync_current}
-    pop_registers \\el
+    pop_registers \\el, current
     eret
-    .balign 0x80
 
 vbar_irq_current_\\el:
-    push_registers \\el
+    push_registers \\el, current
     mov x0, sp
     bl {irq_current}
-    pop_registers \\el
+    pop_registers \\el, current
     eret
-    .balign 0x80
 
 vbar_fiq_current_\\el:
-    push_registers \\el
+    push_registers \\el, current
     mov x0, sp
     bl {fiq_current}
-    pop_registers \\el
+    pop_registers \\el, current
     eret
-    .balign 0x80
 
 vbar_serror_current_\\el:
-    push_registers \\el
+    push_registers \\el, current
     mov x0, sp
     bl {serror_current}
-    pop_registers \\el
+    pop_registers \\el, current
     eret
-    .balign 0x80
 
-    // 0x400: 'Lower Exception level'
 vbar_sync_lower_\\el:
-    push_registers \\el
+    push_registers \\el, lower
     mov x0, sp
     bl {sync_lower}
-    pop_registers \\el
+    pop_registers \\el, lower
     eret
-    .balign 0x80
 
 vbar_irq_lower_\\el:
-    push_registers \\el
+    push_registers \\el, lower
     mov x0, sp
     bl {irq_lower}
-    pop_registers \\el
+    pop_registers \\el, lower
     eret
-    .balign 0x80
 
 vbar_fiq_lower_\\el:
-    push_registers \\el
+    push_registers \\el, lower
     mov x0, sp
     bl {fiq_lower}
-    pop_registers \\el
+    pop_registers \\el, lower
     eret
-    .balign 0x80
 
 vbar_serror_lower_\\el:
-    push_registers \\el
+    push_registers \\el, lower
     mov x0, sp
     bl {serror_lower}
-    pop_registers \\el
+    pop_registers \\el, lower
+    eret
+
+vbar_sync_lower_aarch32_\\el:
+    push_registers \\el, lower_aarch32
+    mov x0, sp
+    bl {sync_lower_aarch32}
+    pop_registers \\el, lower_aarch32
+    eret
+
+vbar_irq_lower_aarch32_\\el:
+    push_registers \\el, lower_aarch32
+    mov x0, sp
+    bl {irq_lower_aarch32}
+    pop_registers \\el, lower_aarch32
+    eret
+
+vbar_fiq_lower_aarch32_\\el:
+    push_registers \\el, lower_aarch32
+    mov x0, sp
+    bl {fiq_lower_aarch32}
+    pop_registers \\el, lower_aarch32
+    eret
+
+vbar_serror_lower_aarch32_\\el:
+    push_registers \\el, lower_aarch32
+    mov x0, sp
+    bl {serror_lower_aarch32}
+    pop_registers \\el, lower_aarch32
     eret
-    .balign 0x80
 .endm
 
     exception_vectors el1
     exception_vectors el2
     exception_vectors el3
 ",
+            sync_current_sp0 = sym <$handlers as $crate::ExceptionHandlers>::sync_current_sp0,
+            irq_current_sp0 = sym <$handlers as $crate::ExceptionHandlers>::irq_current_sp0,
+            fiq_current_sp0 = sym <$handlers as $crate::ExceptionHandlers>::fiq_current_sp0,
+            serror_current_sp0 = sym <$handlers as $crate::ExceptionHandlers>::serror_current_sp0,
             sync_current = sym <$handlers as $crate::ExceptionHandlers>::sync_current,
             irq_current = sym <$handlers as $crate::ExceptionHandlers>::irq_current,
             fiq_current = sym <$handlers as $crate::ExceptionHandlers>::fiq_current,
             serror_current = sym <$handlers as $crate::ExceptionHandlers>::serror_current,
-            sync_lower = sym <$handlers as $crate::ExceptionHandlers>::sync_lower,
+            sync_lower = sym $crate::_dispatch_sync_lower::<$handlers>,
             irq_lower = sym <$handlers as $crate::ExceptionHandlers>::irq_lower,
             fiq_lower = sym <$handlers as $crate::ExceptionHandlers>::fiq_lower,
             serror_lower = sym <$handlers as $crate::ExceptionHandlers>::serror_lower,
+            sync_lower_aarch32 = sym <$handlers as $crate::ExceptionHandlers>::sync_lower_aarch32,
+            irq_lower_aarch32 = sym <$handlers as $crate::ExceptionHandlers>::irq_lower_aarch32,
+            fiq_lower_aarch32 = sym <$handlers as $crate::ExceptionHandlers>::fiq_lower_aarch32,
+            serror_lower_aarch32 = sym <$handlers as $crate::ExceptionHandlers>::serror_lower_aarch32,
         );
     };
 }
 
+/// Routes Software Step exceptions, SVC calls and HVC and SMC calls taken from the lower EL to
+/// their own handlers
+#[doc(hidden)]
+pub extern "C" fn _dispatch_sync_lower<H: ExceptionHandlers>(context: &mut StackedRegisters) {
+    if step::is_software_step_lower() {
+        H::software_step_lower(context)
+    } else if task::is_svc(context.esr) {
+        H::svc_lower(context)
+    } else if smccc::is_hvc_smc(context.esr) {
+        H::hvc_smc_lower(context)
+    } else {
+        H::sync_lower(context)
+    }
+}
+
 /// Interface to statically register exception handlers
+///
+/// Each handler gets the [`StackedRegisters`] of the interrupted code and may change them, e.g. to
+/// return values in X0 or to [skip](StackedRegisters::skip_instruction) the instruction that
+/// caused the exception.
 pub trait ExceptionHandlers {
+    /// Handles Synchronous exceptions taken at the current EL while using SP_EL0
+    extern "C" fn sync_current_sp0(context: &mut StackedRegisters) {
+        _ = context;
+        panic!("unhandled Synchronous exception at current EL with SP_EL0")
+    }
+
+    /// Handles IRQ exceptions taken at the current EL while using SP_EL0
+    extern "C" fn irq_current_sp0(context: &mut StackedRegisters) {
+        _ = context;
+        panic!("unhandled IRQ exception at current EL with SP_EL0")
+    }
+
+    /// Handles FIQ exceptions taken at the current EL while using SP_EL0
+    extern "C" fn fiq_current_sp0(context: &mut StackedRegisters) {
+        _ = context;
+        panic!("unhandled FIQ exception at current EL with SP_EL0")
+    }
+
+    /// Handles SError exceptions taken at the current EL while using SP_EL0
+    extern "C" fn serror_current_sp0(context: &mut StackedRegisters) {
+        _ = context;
+        panic!("unhandled SError exception at current EL with SP_EL0")
+    }
+
     /// Handles Synchronous exceptions taken at the current EL
-    extern "C" fn sync_current(context: &StackedRegisters) {
+    extern "C" fn sync_current(context: &mut StackedRegisters) {
         _ = context;
         panic!("unhandled Synchronous exception at current EL")
     }
 
     /// Handles IRQ exceptions taken at the current EL
-    extern "C" fn irq_current(context: &StackedRegisters) {
+    extern "C" fn irq_current(context: &mut StackedRegisters) {
         _ = context;
         panic!("unhandled IRQ exception at current EL")
     }
 
     /// Handles IFQ exceptions taken at the current EL
-    extern "C" fn fiq_current(context: &StackedRegisters) {
+    extern "C" fn fiq_current(context: &mut StackedRegisters) {
         _ = context;
         panic!("unhandled FIQ exception at current EL")
     }
 
     /// Handles SError exceptions taken at the current EL
-    extern "C" fn serror_current(context: &StackedRegisters) {
+    extern "C" fn serror_current(context: &mut StackedRegisters) {
         _ = context;
         panic!("unhandled SError exception at current EL")
     }
 
     /// Handles Synchronous exceptions taken from the immediate lower EL
-    extern "C" fn sync_lower(context: &StackedRegisters) {
+    extern "C" fn sync_lower(context: &mut StackedRegisters) {
         _ = context;
         panic!("unhandled Synchronous exception at lower EL")
     }
 
+    /// Handles Software Step exceptions taken from the immediate lower EL
+    ///
+    /// These exceptions are only generated while software step is [enabled](step::enable) and are
+    /// not passed to `sync_lower`. Use [`StackedRegisters::set_software_step`] to step one more
+    /// instruction, or [`step::disable`] to let the lower EL run freely.
+    fn software_step_lower(context: &mut StackedRegisters) {
+        _ = context;
+        panic!("unhandled Software Step exception at lower EL")
+    }
+
+    /// Handles SVC calls taken from the immediate lower EL
+    ///
+    /// These calls are passed to `sync_lower` unless this is overridden, e.g. to handle the system
+    /// calls of EL0 tasks with [`task::dispatch`].
+    fn svc_lower(context: &mut StackedRegisters) {
+        Self::sync_lower(context)
+    }
+
+    /// Handles HVC and SMC calls taken from the immediate lower EL
+    ///
+    /// These calls are passed to `sync_lower` unless this is overridden, e.g. to route them to
+    /// services with a [`smccc::Dispatcher`].
+    fn hvc_smc_lower(context: &mut StackedRegisters) {
+        Self::sync_lower(context)
+    }
+
     /// Handles IRQ exceptions taken from the immediate lower EL
-    extern "C" fn irq_lower(context: &StackedRegisters) {
+    extern "C" fn irq_lower(context: &mut StackedRegisters) {
         _ = context;
         panic!("unhandled IRQ exception at lower EL")
     }
 
     /// Handles FIQ exceptions taken from the immediate lower EL
-    extern "C" fn fiq_lower(context: &StackedRegisters) {
+    extern "C" fn fiq_lower(context: &mut StackedRegisters) {
         _ = context;
         panic!("unhandled FIQ exception at lower EL")
     }
 
     /// Handles SError exceptions taken from the immediate lower EL
-    extern "C" fn serror_lower(context: &StackedRegisters) {
+    extern "C" fn serror_lower(context: &mut StackedRegisters) {
         _ = context;
         panic!("unhandled SError exception at lower EL")
     }
+
+    /// Handles Synchronous exceptions taken from the immediate lower EL using AArch32
+    extern "C" fn sync_lower_aarch32(context: &mut StackedRegisters) {
+        _ = context;
+        panic!("unhandled Synchronous exception at lower EL using AArch32")
+    }
+
+    /// Handles IRQ exceptions taken from the immediate lower EL using AArch32
+    extern "C" fn irq_lower_aarch32(context: &mut StackedRegisters) {
+        _ = context;
+        panic!("unhandled IRQ exception at lower EL using AArch32")
+    }
+
+    /// Handles FIQ exceptions taken from the immediate lower EL using AArch32
+    extern "C" fn fiq_lower_aarch32(context: &mut StackedRegisters) {
+        _ = context;
+        panic!("unhandled FIQ exception at lower EL using AArch32")
+    }
+
+    /// Handles SError exceptions taken from the immediate lower EL using AArch32
+    extern "C" fn serror_lower_aarch32(context: &mut StackedRegisters) {
+        _ = context;
+        panic!("unhandled SError exception at lower EL using AArch32")
+    }
 }
 
 /// Marks the main function of the binary and reserves space for the boot stack
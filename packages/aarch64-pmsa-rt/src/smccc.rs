@@ -0,0 +1,236 @@
+//! Dispatching of SMCCC calls taken from the lower Exception Level
+//!
+//! Code running at EL2 or EL3 receives the HVC and SMC calls of the lower ELs in
+//! [`ExceptionHandlers::hvc_smc_lower`]. A [`Dispatcher`] classifies such a call by the owning
+//! entity of its Function Identifier, passes it to the [`Service`] registered for that entity and
+//! writes the results back into the saved X0-X3, which the exception return restores.
+//!
+//! ```ignore
+//! use aarch64_pmsa_rt::{
+//!     ExceptionHandlers, StackedRegisters,
+//!     smccc::{Dispatcher, psci::PsciServer},
+//! };
+//!
+//! static PSCI: PsciServer = PsciServer { system_off: power_off };
+//! static DISPATCHER: Dispatcher = Dispatcher {
+//!     psci: Some(&PSCI),
+//!     ..Dispatcher::new()
+//! };
+//!
+//! struct Handlers;
+//! impl ExceptionHandlers for Handlers {
+//!     fn hvc_smc_lower(context: &mut StackedRegisters) {
+//!         assert!(DISPATCHER.dispatch(context), "not an SMCCC call");
+//!     }
+//! }
+//! ```
+//!
+//! [`ExceptionHandlers::hvc_smc_lower`]: crate::ExceptionHandlers::hvc_smc_lower
+
+use aarch64_cpu::{
+    ExceptionLevel,
+    smccc::{Conduit, FunctionId, NOT_SUPPORTED, Owner},
+    syndrome::Syndrome,
+};
+
+use crate::StackedRegisters;
+
+pub mod psci;
+
+/// Function Numbers of the Standard Secure Service calls that belong to PSCI
+const PSCI_NUMBERS: core::ops::Range<u16> = 0x0000..0x0020;
+
+/// An SMCCC call made by the lower EL
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Call {
+    /// The instruction used to make the call
+    pub conduit: Conduit,
+    /// The Function Identifier, from W0
+    pub function: FunctionId,
+    /// The arguments, from X1-X17
+    ///
+    /// The upper 32 bits are cleared for SMC32 calls.
+    pub args: [u64; 17],
+    /// The Exception Level the call was made from
+    pub caller_el: ExceptionLevel,
+}
+
+/// A handler for the calls of one or more owning entities
+pub trait Service: Sync {
+    /// Handles `call`, returning the results to write back to X0-X3
+    ///
+    /// The upper 32 bits of the results are cleared for SMC32 calls.
+    fn handle(&self, call: &Call) -> [u64; 4];
+}
+
+/// Routes SMCCC calls to services by owning entity
+///
+/// Calls to an entity without a service return `NOT_SUPPORTED`.
+#[derive(Clone, Copy)]
+pub struct Dispatcher {
+    /// Handles Arm Architecture calls, e.g. `SMCCC_VERSION`
+    pub arch: Option<&'static dyn Service>,
+    /// Handles the PSCI calls, e.g. [`psci::PsciServer`]
+    pub psci: Option<&'static dyn Service>,
+    /// Handles the Standard Secure Service calls that do not belong to PSCI
+    pub standard_secure: Option<&'static dyn Service>,
+    /// Handles SiP, OEM, Vendor Specific Hypervisor and Vendor Specific EL3 Monitor calls
+    pub vendor: Option<&'static dyn Service>,
+}
+
+impl Dispatcher {
+    /// A dispatcher without any service
+    pub const fn new() -> Dispatcher {
+        Dispatcher {
+            arch: None,
+            psci: None,
+            standard_secure: None,
+            vendor: None,
+        }
+    }
+
+    /// Returns the service registered for `function`
+    pub fn service(&self, function: FunctionId) -> Option<&'static dyn Service> {
+        match function.owner() {
+            Owner::Arch => self.arch,
+            Owner::StandardSecure if PSCI_NUMBERS.contains(&function.number()) => self.psci,
+            Owner::StandardSecure => self.standard_secure,
+            Owner::Sip | Owner::Oem | Owner::VendorHypervisor | Owner::VendorEl3Monitor => {
+                self.vendor
+            }
+            _ => None,
+        }
+    }
+
+    /// Handles the HVC or SMC call being handled at the current EL, whose registers are `context`
+    ///
+    /// Returns `false`, leaving `context` untouched, if the exception is not an HVC or SMC with an
+    /// immediate of zero, as used by the SMCCC. A trapped SMC is stepped over, so that the
+    /// exception return resumes after it, as it does for calls taken to their own EL.
+    pub fn dispatch(&self, context: &mut StackedRegisters) -> bool {
+        let conduit = match Syndrome::decode(context.esr) {
+            Syndrome::Hvc(0) => Conduit::Hvc,
+            Syndrome::Smc(0) => Conduit::Smc,
+            _ => return false,
+        };
+
+        // the preferred return address of an SMC trapped to EL2 by `HCR_EL2.TSC` is the SMC itself
+        if conduit == Conduit::Smc && ExceptionLevel::current() == ExceptionLevel::EL2 {
+            context.skip_instruction();
+        }
+
+        let mut call = Call {
+            conduit,
+            function: FunctionId(context.x[0] as u32),
+            args: [0; 17],
+            caller_el: context.exception_level(),
+        };
+        call.args.copy_from_slice(&context.x[1..18]);
+
+        let results = self.call(call);
+        context.x[..4].copy_from_slice(&results);
+        true
+    }
+
+    /// Passes `call` to the service registered for its function, returning the results to write
+    /// back to X0-X3
+    ///
+    /// The upper 32 bits of the arguments and the results are cleared for SMC32 calls.
+    fn call(&self, mut call: Call) -> [u64; 4] {
+        let smc32 = !call.function.is_smc64();
+        let mut results = match self.service(call.function) {
+            Some(service) => {
+                if smc32 {
+                    call.args
+                        .iter_mut()
+                        .for_each(|arg| *arg = *arg as u32 as u64);
+                }
+                service.handle(&call)
+            }
+            None => [NOT_SUPPORTED as u64, 0, 0, 0],
+        };
+        if smc32 {
+            results
+                .iter_mut()
+                .for_each(|result| *result = *result as u32 as u64);
+        }
+        results
+    }
+}
+
+impl Default for Dispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns `true` if the exception described by `esr` is an HVC or SMC call
+///
+/// Neither can be taken to EL1, so this is never the case there.
+pub(crate) fn is_hvc_smc(esr: u64) -> bool {
+    matches!(Syndrome::decode(esr), Syndrome::Hvc(_) | Syndrome::Smc(_))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Returns the arguments it was called with and a result with the upper 32 bits set
+    struct Echo;
+    impl Service for Echo {
+        fn handle(&self, call: &Call) -> [u64; 4] {
+            [call.args[0], call.args[1], call.args[16], u64::MAX]
+        }
+    }
+
+    static ECHO: Echo = Echo;
+    static DISPATCHER: Dispatcher = Dispatcher {
+        vendor: Some(&ECHO),
+        ..Dispatcher::new()
+    };
+
+    fn call(function: FunctionId) -> Call {
+        let mut args = [0; 17];
+        args[0] = 0x1234_5678_9abc_def0;
+        args[1] = 0xffff_ffff_0000_0001;
+        args[16] = 0x8000_0000_8000_0000;
+        Call {
+            conduit: Conduit::Smc,
+            function,
+            args,
+            caller_el: ExceptionLevel::EL1,
+        }
+    }
+
+    #[test]
+    fn smc32_clears_upper_bits() {
+        let results = DISPATCHER.call(call(FunctionId::fast32(Owner::Sip, 1)));
+        assert_eq!(
+            results,
+            [0x9abc_def0, 0x0000_0001, 0x8000_0000, 0xffff_ffff]
+        );
+    }
+
+    #[test]
+    fn smc64_keeps_upper_bits() {
+        let results = DISPATCHER.call(call(FunctionId::fast64(Owner::Sip, 1)));
+        assert_eq!(
+            results,
+            [
+                0x1234_5678_9abc_def0,
+                0xffff_ffff_0000_0001,
+                0x8000_0000_8000_0000,
+                u64::MAX
+            ]
+        );
+    }
+
+    #[test]
+    fn not_supported() {
+        let results = DISPATCHER.call(call(FunctionId::fast32(Owner::Arch, 0)));
+        assert_eq!(results, [NOT_SUPPORTED as u32 as u64, 0, 0, 0]);
+
+        let results = DISPATCHER.call(call(FunctionId::fast64(Owner::Arch, 0)));
+        assert_eq!(results, [NOT_SUPPORTED as u64, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,260 @@
+//! Built-in PSCI server for the lower Exception Level
+//!
+//! [`PsciServer`] lets the lower EL turn the cores on and off with PSCI v1.0 calls, on top of the
+//! mailbox of the [`smp`](crate::smp) module, and shut the system down. It implements
+//! `PSCI_VERSION`, `PSCI_FEATURES`, `CPU_ON`, `CPU_OFF`, `AFFINITY_INFO` and `SYSTEM_OFF`; the
+//! other PSCI functions return `NOT_SUPPORTED`.
+//!
+//! A core can only be turned on once it has been given a stack with [`set_stack`], on which it
+//! handles the exceptions it takes to the current EL. It enters the lower EL at the requested
+//! entry point with the context ID in X0 and all exceptions masked, as the caller of `CPU_ON` was
+//! configured: `HCR_EL2` at EL2 and `SCR_EL3` at EL3 are copied from the calling core. `CPU_OFF`
+//! [parks](crate::smp::park) the calling core, which cannot be the boot core.
+//!
+//! Cores are identified by Aff0 of their `MPIDR_EL1`, which must be their core ID; the other
+//! affinity fields must be zero.
+
+#[cfg(target_arch = "aarch64")]
+use core::sync::atomic::{AtomicBool, AtomicU64};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use aarch64_cpu::smccc::{
+    FunctionId, Version,
+    psci::{self, Error},
+};
+#[cfg(target_arch = "aarch64")]
+use aarch64_cpu::{
+    ExceptionLevel,
+    asm::barrier,
+    registers::{CurrentEL, HCR_EL2, ID_AA64PFR0_EL1, Readable as _, SCR_EL3, Writeable as _},
+};
+
+use super::{Call, Service};
+use crate::{
+    Stack,
+    smp::{self, MAX_CORES},
+};
+
+/// The PSCI version reported by `PSCI_VERSION`
+const VERSION: Version = Version { major: 1, minor: 0 };
+
+/// Ways [`set_stack`] can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StackError {
+    /// The core ID is 0, which cannot be turned off and on, or [`MAX_CORES`] or higher
+    InvalidCore(usize),
+    /// The core has already been given a stack
+    AlreadySet(usize),
+}
+
+/// How a core enters the lower EL, as requested by `CPU_ON`
+#[cfg(target_arch = "aarch64")]
+struct Boot {
+    entry_point: AtomicU64,
+    context_id: AtomicU64,
+    spsr: AtomicU64,
+    scr_el3: AtomicU64,
+    hcr_el2: AtomicU64,
+    /// Whether `hcr_el2` is to be written when turning the core on from EL3
+    write_hcr_el2: AtomicBool,
+}
+
+#[cfg(target_arch = "aarch64")]
+static BOOT: [Boot; MAX_CORES] = [const {
+    Boot {
+        entry_point: AtomicU64::new(0),
+        context_id: AtomicU64::new(0),
+        spsr: AtomicU64::new(0),
+        scr_el3: AtomicU64::new(0),
+        hcr_el2: AtomicU64::new(0),
+        write_hcr_el2: AtomicBool::new(false),
+    }
+}; MAX_CORES];
+
+/// Initial SP of each core; zero for the cores without a stack
+static STACKS: [AtomicUsize; MAX_CORES] = [const { AtomicUsize::new(0) }; MAX_CORES];
+
+/// Gives the core `core_id` the `stack` memory it runs on at the current EL once turned on
+pub fn set_stack(core_id: usize, stack: Stack) -> Result<(), StackError> {
+    if core_id == 0 || core_id >= MAX_CORES {
+        return Err(StackError::InvalidCore(core_id));
+    }
+
+    // the stack grows downwards ("full descending stack") so the initial SP value is the higher
+    // boundary of the memory block
+    STACKS[core_id]
+        .compare_exchange(0, stack.higher(), Ordering::Relaxed, Ordering::Relaxed)
+        .map(drop)
+        .map_err(|_| StackError::AlreadySet(core_id))
+}
+
+/// A PSCI implementation for the lower EL, to be registered as [`Dispatcher::psci`]
+///
+/// [`Dispatcher::psci`]: super::Dispatcher::psci
+#[derive(Clone, Copy, Debug)]
+pub struct PsciServer {
+    /// Shuts the system down, for `SYSTEM_OFF`
+    pub system_off: fn() -> !,
+}
+
+impl Service for PsciServer {
+    fn handle(&self, call: &Call) -> [u64; 4] {
+        let [arg1, arg2, ..] = call.args;
+        let result = match call.function {
+            psci::PSCI_VERSION => Ok(VERSION.bits().into()),
+            psci::PSCI_FEATURES => features(arg1),
+            #[cfg(target_arch = "aarch64")]
+            psci::CPU_ON_32 | psci::CPU_ON_64 => cpu_on(call.caller_el, arg1, arg2, call.args[2]),
+            #[cfg(target_arch = "aarch64")]
+            psci::CPU_OFF => cpu_off(),
+            psci::AFFINITY_INFO_32 | psci::AFFINITY_INFO_64 => affinity_info(arg1, arg2),
+            psci::SYSTEM_OFF => (self.system_off)(),
+            _ => Err(Error::NotSupported),
+        };
+
+        // negative error codes are sign-extended to 64 bits
+        [result.unwrap_or_else(|e| e.code() as u64), 0, 0, 0]
+    }
+}
+
+fn features(function: u64) -> Result<u64, Error> {
+    match FunctionId(function as u32) {
+        psci::PSCI_VERSION
+        | psci::PSCI_FEATURES
+        | psci::CPU_ON_32
+        | psci::CPU_ON_64
+        | psci::CPU_OFF
+        | psci::AFFINITY_INFO_32
+        | psci::AFFINITY_INFO_64
+        | psci::SYSTEM_OFF => Ok(0),
+        _ => Err(Error::NotSupported),
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+fn cpu_on(
+    caller_el: ExceptionLevel,
+    target_cpu: u64,
+    entry_point: u64,
+    context_id: u64,
+) -> Result<u64, Error> {
+    let core_id = core_id(target_cpu).ok_or(Error::InvalidParameters)?;
+    if smp::is_online(core_id) {
+        return Err(Error::AlreadyOn);
+    }
+    if smp::is_pending(core_id) {
+        return Err(Error::OnPending);
+    }
+    if !entry_point.is_multiple_of(4) {
+        return Err(Error::InvalidAddress);
+    }
+    let initial_sp = STACKS[core_id].load(Ordering::Relaxed);
+    if initial_sp == 0 {
+        return Err(Error::InternalFailure);
+    }
+
+    // [ARM-R64/C5.2.15] ELxh with D, A, I and F masked
+    let spsr = 0b1111 << 6 | (caller_el as u64) << 2 | 1;
+    let (scr_el3, hcr_el2) = match CurrentEL.read(CurrentEL::EL) {
+        2 => (0, Some(HCR_EL2.get())),
+        _ => {
+            let el2_implemented =
+                !ID_AA64PFR0_EL1.matches_all(ID_AA64PFR0_EL1::EL2::NotImplemented);
+            // an EL2 caller sets up `HCR_EL2` itself
+            let hcr_el2 =
+                (caller_el == ExceptionLevel::EL1 && el2_implemented).then(|| HCR_EL2.get());
+            (SCR_EL3.get(), hcr_el2)
+        }
+    };
+
+    let boot = &BOOT[core_id];
+    boot.entry_point.store(entry_point, Ordering::Relaxed);
+    boot.context_id.store(context_id, Ordering::Relaxed);
+    boot.spsr.store(spsr, Ordering::Relaxed);
+    boot.scr_el3.store(scr_el3, Ordering::Relaxed);
+    boot.hcr_el2.store(hcr_el2.unwrap_or(0), Ordering::Relaxed);
+    boot.write_hcr_el2
+        .store(hcr_el2.is_some(), Ordering::Relaxed);
+
+    smp::release(core_id, enter_lower_el as *const () as usize, initial_sp)
+        .map(|()| 0)
+        .map_err(|_| Error::AlreadyOn)
+}
+
+#[cfg(target_arch = "aarch64")]
+fn cpu_off() -> Result<u64, Error> {
+    if crate::get_cpuid() == 0 {
+        return Err(Error::Denied);
+    }
+    smp::park()
+}
+
+fn affinity_info(target_affinity: u64, lowest_affinity_level: u64) -> Result<u64, Error> {
+    let core_id = core_id(target_affinity).ok_or(Error::InvalidParameters)?;
+    if lowest_affinity_level != 0 {
+        return Err(Error::InvalidParameters);
+    }
+
+    let state = if smp::is_online(core_id) {
+        psci::AffinityState::On
+    } else if smp::is_pending(core_id) {
+        psci::AffinityState::OnPending
+    } else {
+        psci::AffinityState::Off
+    };
+    Ok(state as u64)
+}
+
+fn core_id(mpidr: u64) -> Option<usize> {
+    let core_id = usize::try_from(mpidr).ok()?;
+    (core_id < MAX_CORES).then_some(core_id)
+}
+
+/// Entry point of a core turned on by `CPU_ON`, running at the current EL on its own stack
+#[cfg(target_arch = "aarch64")]
+extern "C" fn enter_lower_el(core_id: usize) -> ! {
+    let boot = &BOOT[core_id];
+    let entry_point = boot.entry_point.load(Ordering::Relaxed);
+    let context_id = boot.context_id.load(Ordering::Relaxed);
+    let spsr = boot.spsr.load(Ordering::Relaxed);
+    let hcr_el2 = boot.hcr_el2.load(Ordering::Relaxed);
+
+    if CurrentEL.read(CurrentEL::EL) == 3 {
+        SCR_EL3.set(boot.scr_el3.load(Ordering::Relaxed));
+        if boot.write_hcr_el2.load(Ordering::Relaxed) {
+            HCR_EL2.set(hcr_el2);
+        }
+        barrier::isb(barrier::SY);
+
+        // SAFETY: `cpu_on` checked that nothing runs on this core; from here on the lower EL
+        // owns it
+        unsafe {
+            core::arch::asm!(
+                "msr ELR_EL3, {entry_point}",
+                "msr SPSR_EL3, {spsr}",
+                "eret",
+                entry_point = in(reg) entry_point,
+                spsr = in(reg) spsr,
+                in("x0") context_id,
+                options(noreturn),
+            )
+        }
+    } else {
+        HCR_EL2.set(hcr_el2);
+        barrier::isb(barrier::SY);
+
+        // SAFETY: `cpu_on` checked that nothing runs on this core; from here on the lower EL
+        // owns it
+        unsafe {
+            core::arch::asm!(
+                "msr ELR_EL2, {entry_point}",
+                "msr SPSR_EL2, {spsr}",
+                "eret",
+                entry_point = in(reg) entry_point,
+                spsr = in(reg) spsr,
+                in("x0") context_id,
+                options(noreturn),
+            )
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! Bring-up of the secondary cores
+//!
+//! `_default_start` parks every core whose [`get_cpuid`](crate::get_cpuid) is not zero. Parked
+//! cores wait in `wfe` until [`start_core`] releases them through their slot in a mailbox, at
+//! which point they switch to their own stack, install the vector table configured via
+//! `exception_handlers` in their own VBAR and call the given entry point with their core ID.
+//!
+//! Cores whose ID is [`MAX_CORES`] or higher stay asleep forever. A started core can go back to
+//! the mailbox with [`park`], after which it can be started again.
+//!
+//! Parked cores run with their data cache disabled while the core that starts them may have its
+//! own enabled, so every access to a mailbox slot is paired with a clean and invalidate of the slot
+//! to the Point of Coherency.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(target_arch = "aarch64")]
+use aarch64_cpu::asm;
+use aarch64_cpu::asm::cache;
+
+#[cfg(target_arch = "aarch64")]
+use crate::Stack;
+
+/// Number of cores, including the boot core, that can be started
+pub const MAX_CORES: usize = 16;
+
+/// The core is waiting to be started
+const PARKED: usize = 1;
+/// [`release`] is writing the entry point and the stack to the slot
+#[cfg(target_arch = "aarch64")]
+const CLAIMED: usize = 2;
+/// The entry point and the stack have been written to the slot
+pub(crate) const RELEASED: usize = 3;
+/// The core is running its entry point
+const ONLINE: usize = 4;
+
+/// log2 of the size of a mailbox slot, as used by `_default_start`
+pub(crate) const SLOT_SHIFT: usize = 7;
+
+/// Ways [`start_core`] can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The core ID is [`MAX_CORES`] or higher
+    InvalidCore(usize),
+    /// The core is the boot core or has already been started
+    AlreadyStarted(usize),
+}
+
+/// One slot of the mailbox
+///
+/// NOTE the layout is relied upon by `_default_start`. Slots are aligned to 128 bytes, the largest
+/// cache line of the supported cores, so that cleaning one slot never writes a stale copy of
+/// another core's slot back to memory.
+#[repr(C, align(128))]
+pub(crate) struct Slot {
+    state: AtomicUsize,
+    entry: AtomicUsize,
+    initial_sp: AtomicUsize,
+}
+
+impl Slot {
+    /// Cleans and invalidates the slot to the Point of Coherency
+    ///
+    /// Done after writing the slot, so that cores with their data cache disabled see the write,
+    /// and before reading it, so that a core with its data cache enabled does not read a stale
+    /// line. Clean and invalidate, rather than invalidate only, never discards a pending write.
+    fn sync(&self) {
+        cache::clean_invalidate_dcache_range(self as *const Slot as usize, size_of::<Slot>());
+    }
+}
+
+const _: () = assert!(1 << SLOT_SHIFT == size_of::<Slot>());
+
+// NOTE this must not live in .bss: the other cores poll it before the boot core has zeroed .bss,
+// and RAM contents are UNKNOWN at reset
+#[unsafe(link_section = ".data.mailbox")]
+pub(crate) static MAILBOX: [Slot; MAX_CORES] = [const {
+    Slot {
+        state: AtomicUsize::new(PARKED),
+        entry: AtomicUsize::new(0),
+        initial_sp: AtomicUsize::new(0),
+    }
+}; MAX_CORES];
+
+/// Starts the parked core `core_id`, which will run `entry(core_id)` on the given `stack` memory
+///
+/// This returns once the core has been released, not once it is running; use [`is_online`] to
+/// check whether it came online. A core that does not exist is never reported as online.
+///
+/// The started core runs at the Exception Level that the program was booted at. Only the mailbox
+/// is used to synchronize with it, and its slot is cleaned to the Point of Coherency, so this works
+/// whether or not the data cache of either core is enabled.
+#[cfg(target_arch = "aarch64")]
+pub fn start_core(
+    core_id: usize,
+    entry: extern "C" fn(usize) -> !,
+    stack: Stack,
+) -> Result<(), Error> {
+    // the stack grows downwards ("full descending stack") so the initial SP value is the higher
+    // boundary of the memory block
+    release(core_id, entry as usize, stack.higher())
+}
+
+/// Releases the parked core `core_id` to run `entry(core_id)` with the given initial SP
+#[cfg(target_arch = "aarch64")]
+pub(crate) fn release(core_id: usize, entry: usize, initial_sp: usize) -> Result<(), Error> {
+    let slot = MAILBOX.get(core_id).ok_or(Error::InvalidCore(core_id))?;
+    slot.sync();
+    if core_id == 0
+        || slot
+            .state
+            .compare_exchange(PARKED, CLAIMED, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+    {
+        return Err(Error::AlreadyStarted(core_id));
+    }
+
+    slot.entry.store(entry, Ordering::Relaxed);
+    slot.initial_sp.store(initial_sp, Ordering::Relaxed);
+    // the entry point and the stack must reach memory before the state that publishes them
+    slot.sync();
+    slot.state.store(RELEASED, Ordering::Release);
+
+    // the mailbox write must be observable before the parked cores wake up; `sync` completes with
+    // a `DSB SY`
+    slot.sync();
+    asm::sev();
+
+    Ok(())
+}
+
+/// Stops the calling core and parks it until [`start_core`] releases it again
+///
+/// Whatever the core was running is abandoned, including its stack; once released, the core starts
+/// over on the stack given to `start_core`.
+///
+/// # Panics
+///
+/// The boot core, core 0, cannot be parked.
+#[cfg(target_arch = "aarch64")]
+pub fn park() -> ! {
+    let core_id = crate::get_cpuid() as usize;
+    assert_ne!(core_id, 0, "the boot core cannot be parked");
+    let slot = &MAILBOX[core_id];
+
+    slot.state.store(PARKED, Ordering::Release);
+    slot.sync();
+    while slot.state.load(Ordering::Acquire) != RELEASED {
+        asm::wfe();
+        slot.sync();
+    }
+
+    let entry = slot.entry.load(Ordering::Relaxed);
+    let initial_sp = slot.initial_sp.load(Ordering::Relaxed);
+    // SAFETY: the current stack is abandoned; `secondary_start` does not return
+    unsafe {
+        core::arch::asm!(
+            "mov sp, {initial_sp}",
+            "b {secondary_start}",
+            initial_sp = in(reg) initial_sp,
+            secondary_start = sym secondary_start,
+            in("x0") core_id,
+            in("x1") entry,
+            options(noreturn),
+        )
+    }
+}
+
+/// Returns `true` if the core `core_id` has been released by [`start_core`] but is not running yet
+pub(crate) fn is_pending(core_id: usize) -> bool {
+    MAILBOX.get(core_id).is_some_and(|slot| {
+        slot.sync();
+        slot.state.load(Ordering::Acquire) == RELEASED
+    })
+}
+
+/// Returns `true` if the core `core_id` is running
+///
+/// The boot core, core 0, is always online.
+pub fn is_online(core_id: usize) -> bool {
+    core_id == 0
+        || MAILBOX.get(core_id).is_some_and(|slot| {
+            slot.sync();
+            slot.state.load(Ordering::Acquire) == ONLINE
+        })
+}
+
+/// Returns the IDs of the cores that are running
+pub fn online_cores() -> impl Iterator<Item = usize> {
+    (0..MAX_CORES).filter(|&core_id| is_online(core_id))
+}
+
+/// Entry point of a released core, once it runs on its own stack
+///
+/// Called by `_default_start` and [`park`].
+#[cfg(target_arch = "aarch64")]
+pub(crate) extern "C" fn secondary_start(core_id: usize, entry: usize) -> ! {
+    crate::set_vbar();
+
+    MAILBOX[core_id].state.store(ONLINE, Ordering::Release);
+    MAILBOX[core_id].sync();
+
+    // SAFETY: `start_core` wrote a function pointer of this type to the mailbox
+    let entry: extern "C" fn(usize) -> ! = unsafe { core::mem::transmute(entry) };
+    entry(core_id)
+}
@@ -0,0 +1,60 @@
+//! Software single-step of code running at a lower Exception Level
+//!
+//! Once software step is [`enable`]d, the lower EL takes a Software Step exception before it
+//! executes the instruction that the next `eret` returns to. These exceptions are handled by
+//! [`ExceptionHandlers::software_step_lower`], which can call
+//! [`StackedRegisters::set_software_step`] to let the lower EL execute exactly one more instruction
+//! before the next Software Step exception, or [`disable`] to let it run freely again.
+//!
+//! [`ExceptionHandlers::software_step_lower`]: crate::ExceptionHandlers::software_step_lower
+//! [`StackedRegisters::set_software_step`]: crate::StackedRegisters::set_software_step
+
+use aarch64_cpu::{
+    asm::barrier,
+    registers::{
+        CurrentEL, ESR_EL1, ESR_EL2, MDCR_EL2, MDSCR_EL1, OSDLR_EL1, OSLAR_EL1, ReadWriteable as _,
+        Readable as _, Writeable as _,
+    },
+};
+
+/// Enables software step of the immediate lower EL
+///
+/// At EL2 this also sets `MDCR_EL2.TDE` so that debug exceptions from EL1 and EL0 are taken to EL2
+/// instead of EL1. Software step exceptions are never taken to EL3, so this must be called at EL1
+/// or EL2.
+pub fn enable() {
+    match CurrentEL.read(CurrentEL::EL) {
+        1 => {}
+        2 => MDCR_EL2.modify(MDCR_EL2::TDE::RouteToEL2Or1),
+        el => panic!("software step is not available at EL{el}"),
+    }
+
+    // debug exceptions are not generated while the OS Lock or the OS Double Lock is set
+    OSDLR_EL1.write(OSDLR_EL1::DLK::Unlocked);
+    OSLAR_EL1.write(OSLAR_EL1::OSLK::Unlocked);
+    MDSCR_EL1.modify(MDSCR_EL1::SS::SoftwareStepEnabled);
+    barrier::isb(barrier::SY);
+}
+
+/// Disables software step
+///
+/// `MDCR_EL2.TDE` is left as is.
+pub fn disable() {
+    MDSCR_EL1.modify(MDSCR_EL1::SS::SoftwareStepDisabled);
+    barrier::isb(barrier::SY);
+}
+
+/// Returns `true` if software step is enabled
+pub fn is_enabled() -> bool {
+    MDSCR_EL1.matches_all(MDSCR_EL1::SS::SoftwareStepEnabled)
+}
+
+/// Returns `true` if the Synchronous exception being handled is a Software Step exception taken
+/// from the immediate lower EL
+pub(crate) fn is_software_step_lower() -> bool {
+    match CurrentEL.read(CurrentEL::EL) {
+        1 => ESR_EL1.matches_all(ESR_EL1::EC::SoftwareStepLowerEL),
+        2 => ESR_EL2.matches_all(ESR_EL2::EC::SoftwareStepLowerEL),
+        _ => false,
+    }
+}
@@ -0,0 +1,142 @@
+//! User-mode tasks running at EL0 and their system calls
+//!
+//! On Armv8-R, [`run`] starts a function at EL0 on its own stack, with the EL1 MPU restricting it
+//! to the regions it was given. The task requests services from EL1 with `SVC #imm` instructions,
+//! which are taken to [`ExceptionHandlers::svc_lower`]. There, [`dispatch`] decodes the call into a
+//! [`Syscall`] from the SVC immediate and X0-X7, hands it to a handler running at EL1 and writes
+//! the handler's result back to X0, which the exception return restores.
+//!
+//! ```ignore
+//! use aarch64_pmsa_rt::{
+//!     ExceptionHandlers, StackedRegisters,
+//!     task::{self, Syscall},
+//! };
+//!
+//! enum Call {
+//!     Yield,
+//!     Sleep { ticks: u64 },
+//! }
+//!
+//! impl Syscall for Call {
+//!     fn decode(number: u16, args: [u64; 8]) -> Option<Self> {
+//!         match number {
+//!             0 => Some(Call::Yield),
+//!             1 => Some(Call::Sleep { ticks: args[0] }),
+//!             _ => None,
+//!         }
+//!     }
+//! }
+//!
+//! struct Handlers;
+//! impl ExceptionHandlers for Handlers {
+//!     fn svc_lower(context: &mut StackedRegisters) {
+//!         task::dispatch(context, |call: Call| match call {
+//!             Call::Yield => 0,
+//!             Call::Sleep { ticks } => sleep(ticks),
+//!         })
+//!         .expect("unknown system call");
+//!     }
+//! }
+//! ```
+//!
+//! [`ExceptionHandlers::svc_lower`]: crate::ExceptionHandlers::svc_lower
+
+use aarch64_cpu::syndrome::Syndrome;
+
+use crate::StackedRegisters;
+
+#[cfg(all(target_arch = "aarch64", arm_architecture = "v8-r"))]
+use aarch64_cpu::{
+    pmsav8::{self, El1Config, El1Mpu},
+    registers::{CurrentEL, ReadWriteable as _, Readable as _, SCTLR_EL1},
+};
+
+#[cfg(all(target_arch = "aarch64", arm_architecture = "v8-r"))]
+use crate::{Daif, DropConfig, ExceptionLevel, Stack};
+
+/// A system call that a task can make
+pub trait Syscall: Sized {
+    /// Decodes a call from the immediate of the SVC instruction and the task's X0-X7
+    ///
+    /// Returns `None` for calls that are not known.
+    fn decode(number: u16, args: [u64; 8]) -> Option<Self>;
+}
+
+/// Ways [`dispatch`] can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The exception being handled is not an SVC
+    NotSvc,
+    /// [`Syscall::decode`] did not recognize the call with this SVC immediate
+    UnknownSyscall(u16),
+}
+
+/// Handles the SVC being handled at the current EL, whose registers are `context`
+///
+/// The call is decoded as an `S` and passed to `handler`, whose return value is written to X0.
+/// On error, `context` is left untouched; the caller can then report the error to the task through
+/// X0 or stop it.
+///
+/// The preferred return address of an SVC is the instruction after it, so the task resumes there.
+pub fn dispatch<S: Syscall>(
+    context: &mut StackedRegisters,
+    handler: impl FnOnce(S) -> u64,
+) -> Result<(), Error> {
+    let Syndrome::Svc(number) = Syndrome::decode(context.esr) else {
+        return Err(Error::NotSvc);
+    };
+
+    let mut args = [0; 8];
+    args.copy_from_slice(&context.x[..8]);
+    let call = S::decode(number, args).ok_or(Error::UnknownSyscall(number))?;
+
+    context.x[0] = handler(call);
+    Ok(())
+}
+
+/// Returns `true` if the exception described by `esr` is an SVC
+pub(crate) fn is_svc(esr: u64) -> bool {
+    matches!(Syndrome::decode(esr), Syndrome::Svc(_))
+}
+
+/// Executes `f` at EL0 on the given `stack` memory, restricted to the regions of `config`
+///
+/// The EL1 MPU is programmed with `config` and enabled together with the background region
+/// (`SCTLR_EL1.BR`), so EL1 keeps using the default memory map where no region matches while EL0
+/// faults on any access outside of the regions that grant it access. The regions for the task
+/// must therefore cover its code, the read-only data it uses and `stack`; regions only accessible
+/// from EL1 may be added, e.g. to set the memory attributes of the kernel. Regions must not
+/// overlap.
+///
+/// `f` inherits the current Interrupt Mask Bits (DAIF). Its system calls are handled by
+/// [`ExceptionHandlers::svc_lower`](crate::ExceptionHandlers::svc_lower) and its faults by
+/// [`ExceptionHandlers::sync_lower`](crate::ExceptionHandlers::sync_lower).
+///
+/// Must be called at EL1. Only returns if `config` could not be programmed into the MPU.
+#[cfg(all(target_arch = "aarch64", arm_architecture = "v8-r"))]
+pub fn run(
+    mpu: &mut El1Mpu,
+    config: &El1Config,
+    f: extern "C" fn() -> !,
+    stack: Stack,
+) -> pmsav8::Error {
+    let el = CurrentEL.read(CurrentEL::EL);
+    assert_eq!(el, 1, "EL0 tasks must be started from EL1, not EL{el}");
+
+    if let Err(e) = mpu.configure(config) {
+        return e;
+    }
+    // the background region never applies to EL0 accesses
+    SCTLR_EL1.modify(SCTLR_EL1::BR::Enable);
+    mpu.enable();
+
+    crate::drop_exception_level_with(
+        DropConfig {
+            el: ExceptionLevel::EL0,
+            daif: Daif::current(),
+            non_secure: true,
+        },
+        f,
+        stack,
+    )
+}
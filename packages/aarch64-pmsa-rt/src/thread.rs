@@ -0,0 +1,210 @@
+//! Switching between threads of execution at the same Exception Level
+//!
+//! A [`Context`] holds the state that a thread keeps across a call to [`switch`]: the callee-saved
+//! registers X19-X29, SP, LR, the Interrupt Mask Bits, the thread pointer and, optionally, the
+//! Advanced SIMD and floating-point state. The caller-saved registers need no saving, as `switch`
+//! is an ordinary function call for the compiler.
+//!
+//! Threads run on SP_ELx, so the exceptions they take at the current EL push their registers onto
+//! the thread's own stack. An exception handler, e.g. `irq_current` handling a Generic Timer
+//! interrupt, can thus preempt the interrupted thread by calling `switch`: the handler is
+//! suspended together with the thread and returns to it once it is switched back to.
+//!
+//! The thread pointer is kept in `TPIDR_EL1` at EL1 and in `TPIDR_EL2` at EL2, see
+//! [`thread_pointer`].
+
+use aarch64_cpu::fpsimd;
+
+#[cfg(target_arch = "aarch64")]
+use aarch64_cpu::registers::{CurrentEL, Readable as _, TPIDR_EL1, TPIDR_EL2, Writeable as _};
+
+#[cfg(target_arch = "aarch64")]
+use crate::{Daif, Stack};
+
+/// The state of a thread that is not running
+///
+/// NOTE the layout is relied upon by `switch_registers`
+#[derive(Debug)]
+#[repr(C)]
+pub struct Context {
+    /// X19-X28
+    x19_x28: [u64; 10],
+    /// X29 AKA Frame Pointer
+    x29: u64,
+    sp: u64,
+    /// X30 AKA Procedure Link Register; `switch` returns to it
+    lr: u64,
+    daif: u64,
+    /// Only saved and restored by [`switch`]
+    thread_pointer: usize,
+    fpsimd: *mut fpsimd::State,
+}
+
+// NOTE this size must match the offsets used by `switch_registers`
+const _: () = assert!(8 * 16 == core::mem::size_of::<Context>());
+
+// SAFETY: the FP/SIMD state is only accessed by `switch`, whose callers guarantee that it is valid
+unsafe impl Send for Context {}
+
+impl Context {
+    /// A context to save the running thread into, e.g. the boot thread before its first switch
+    pub const fn empty() -> Context {
+        Context {
+            x19_x28: [0; 10],
+            x29: 0,
+            sp: 0,
+            lr: 0,
+            daif: 0,
+            thread_pointer: 0,
+            fpsimd: core::ptr::null_mut(),
+        }
+    }
+
+    /// A context that starts a thread running `entry(arg)` on the given `stack` memory, with
+    /// the Interrupt Mask Bits `daif`
+    ///
+    /// The thread starts with a thread pointer of zero and without Advanced SIMD and
+    /// floating-point state.
+    #[cfg(target_arch = "aarch64")]
+    pub fn new(entry: extern "C" fn(usize) -> !, arg: usize, stack: Stack, daif: Daif) -> Context {
+        let mut context = Context::empty();
+        context.x19_x28[0] = entry as *const () as u64;
+        context.x19_x28[1] = arg as u64;
+        // the stack grows downwards ("full descending stack") so the initial SP value is the
+        // higher boundary of the memory block
+        context.sp = stack.higher() as u64;
+        context.lr = thread_start as *const () as u64;
+        context.daif = daif.bits();
+        context
+    }
+
+    /// Sets the thread pointer that [`switch`] installs when it switches to this context
+    pub fn with_thread_pointer(mut self, thread_pointer: usize) -> Context {
+        self.thread_pointer = thread_pointer;
+        self
+    }
+
+    /// Saves and restores V0-V31, `FPCR` and `FPSR` to and from `state` when switching from and
+    /// to this context
+    ///
+    /// Contexts without such state must not use the Advanced SIMD and floating-point registers,
+    /// e.g. by being built for a soft-float target, as those registers are shared with the other
+    /// threads. A new thread starts with the contents of `state`.
+    pub fn with_fpsimd(mut self, state: &'static mut fpsimd::State) -> Context {
+        self.fpsimd = state;
+        self
+    }
+}
+
+/// Saves the running thread into `from` and resumes the thread saved in `to`
+///
+/// Returns once another thread switches back to `from`. Switching from an exception handler is
+/// allowed; e.g. an IRQ handler must complete the interrupt, e.g. signal its end to the interrupt
+/// controller, before switching, as the next thread may run with IRQs unmasked.
+///
+/// Must be called at EL1 or EL2, with SP_ELx selected.
+///
+/// # Safety
+///
+/// - `from` must be valid for writes and `to` must be valid for reads; they may be the same.
+/// - `to` must have been created with [`Context::new`] or saved by a `switch` at the same EL, and
+///   must not be resumed by more than one `switch`.
+/// - Nothing else may access the contexts, or the FP/SIMD states they refer to, while `switch`
+///   runs.
+#[cfg(target_arch = "aarch64")]
+pub unsafe fn switch(from: *mut Context, to: *const Context) {
+    // SAFETY: the caller guarantees that the contexts are valid
+    unsafe {
+        (*from).thread_pointer = thread_pointer();
+        set_thread_pointer((*to).thread_pointer);
+        switch_registers(from, to)
+    }
+}
+
+/// Returns the thread pointer of the running thread
+///
+/// This is `TPIDR_EL1` at EL1 and `TPIDR_EL2` at EL2.
+#[cfg(target_arch = "aarch64")]
+pub fn thread_pointer() -> usize {
+    match CurrentEL.read(CurrentEL::EL) {
+        1 => TPIDR_EL1.get() as usize,
+        2 => TPIDR_EL2.get() as usize,
+        el => panic!("there is no thread pointer at EL{el}"),
+    }
+}
+
+/// Sets the thread pointer of the running thread
+///
+/// This is `TPIDR_EL1` at EL1 and `TPIDR_EL2` at EL2.
+#[cfg(target_arch = "aarch64")]
+pub fn set_thread_pointer(thread_pointer: usize) {
+    match CurrentEL.read(CurrentEL::EL) {
+        1 => TPIDR_EL1.set(thread_pointer as u64),
+        2 => TPIDR_EL2.set(thread_pointer as u64),
+        el => panic!("there is no thread pointer at EL{el}"),
+    }
+}
+
+/// Saves the registers of the running thread into `from` (X0) and loads those of `to` (X1)
+#[unsafe(naked)]
+#[cfg(target_arch = "aarch64")]
+unsafe extern "C" fn switch_registers(from: *mut Context, to: *const Context) {
+    core::arch::naked_asm!(
+        r#"
+            stp     x19, x20, [x0, #8 * 0]
+            stp     x21, x22, [x0, #8 * 2]
+            stp     x23, x24, [x0, #8 * 4]
+            stp     x25, x26, [x0, #8 * 6]
+            stp     x27, x28, [x0, #8 * 8]
+            mov     x9, sp
+            stp     x29, x9, [x0, #8 * 10]
+            mrs     x10, DAIF
+            stp     x30, x10, [x0, #8 * 12]
+            ldr     x11, [x0, #8 * 15]          // save the FP/SIMD state, if any
+            cbz     x11, 1f
+            .arch_extension fp
+            .irp n, 0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23,24,25,26,27,28,29,30,31
+            str     q\n, [x11, #(16 * \n)]
+            .endr
+            mrs     x12, FPCR
+            mrs     x13, FPSR
+            add     x11, x11, #(16 * 32)        // FPCR and FPSR follow V0-V31
+            stp     x12, x13, [x11]
+        1:
+            ldr     x11, [x1, #8 * 15]          // load the FP/SIMD state, if any
+            cbz     x11, 2f
+            .irp n, 0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23,24,25,26,27,28,29,30,31
+            ldr     q\n, [x11, #(16 * \n)]
+            .endr
+            add     x11, x11, #(16 * 32)
+            ldp     x12, x13, [x11]
+            msr     FPCR, x12
+            msr     FPSR, x13
+        2:
+            ldp     x19, x20, [x1, #8 * 0]
+            ldp     x21, x22, [x1, #8 * 2]
+            ldp     x23, x24, [x1, #8 * 4]
+            ldp     x25, x26, [x1, #8 * 6]
+            ldp     x27, x28, [x1, #8 * 8]
+            ldp     x29, x9, [x1, #8 * 10]
+            mov     sp, x9
+            ldp     x30, x10, [x1, #8 * 12]
+            msr     DAIF, x10
+            ret
+        "#,
+    );
+}
+
+/// First code run by a thread created with [`Context::new`], which stashed the entry point in X19
+/// and its argument in X20
+#[unsafe(naked)]
+#[cfg(target_arch = "aarch64")]
+unsafe extern "C" fn thread_start() -> ! {
+    core::arch::naked_asm!(
+        r#"
+            mov     x0, x20
+            mov     x29, xzr                    // terminate the frame chain
+            br      x19
+        "#,
+    );
+}
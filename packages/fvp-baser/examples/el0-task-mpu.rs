@@ -0,0 +1,182 @@
+//! Checks that an EL0 task can make system calls but cannot access memory outside of its regions
+// runner: FVP_BaseR_AEMv8R -f FVP_BaseR_AEMv8R.cfg
+// ignore: aarch64-unknown-none aarch64-unknown-none-softfloat
+// features: v8-r
+
+#![no_std]
+#![no_main]
+
+use core::{
+    arch::asm,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use aarch64_cpu::{
+    pmsav8::{Cacheable, El1AccessPerms, El1Config, El1Mpu, El1Region, El1Shareability, MemAttr},
+    registers::{self, Readable as _},
+    syndrome::Syndrome,
+};
+use aarch64_pmsa_rt::{
+    ExceptionHandlers, Section, Stack, StackedRegisters, alloc_stack, drop_exception_level, entry,
+    exception_handlers,
+    task::{self, Syscall},
+};
+use semihosting::{println, process};
+
+const SYS_WRITE: u16 = 1;
+const SYS_ADD: u16 = 2;
+
+/// Only accessible from EL1
+static KERNEL_DATA: AtomicU64 = AtomicU64::new(42);
+
+enum Call {
+    Write { ptr: u64, len: u64 },
+    Add { a: u64, b: u64 },
+}
+
+impl Syscall for Call {
+    fn decode(number: u16, args: [u64; 8]) -> Option<Self> {
+        match number {
+            SYS_WRITE => Some(Call::Write {
+                ptr: args[0],
+                len: args[1],
+            }),
+            SYS_ADD => Some(Call::Add {
+                a: args[0],
+                b: args[1],
+            }),
+            _ => None,
+        }
+    }
+}
+
+entry!(main);
+
+fn main() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("running at EL{el}");
+    assert_eq!(2, el, "this example must run at EL2");
+
+    let stack = alloc_stack!(4096).expect("called twice");
+    drop_exception_level(at_el1, stack);
+}
+
+extern "C" fn at_el1() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("dropped to EL{el}");
+    assert_eq!(1, el);
+
+    // the MPU regions must be 64-byte aligned
+    let stack = alloc_stack!(4096, align = 64).expect("called twice");
+    let regions = [
+        // the task's code and its string literals
+        El1Region {
+            range: Section::Text.bottom()..=Section::Rodata.top().wrapping_sub(1),
+            shareability: El1Shareability::NonShareable, // no SMP
+            access: El1AccessPerms::ReadOnlyEl1El0,
+            no_exec: false,
+            mair: 0,
+            enable: true,
+        },
+        stack_region(&stack),
+    ];
+    let config = El1Config {
+        regions: &regions,
+        memory_attributes: &[MemAttr::NormalMemory {
+            outer: Cacheable::NonCacheable,
+            inner: Cacheable::NonCacheable,
+        }],
+    };
+
+    println!("starting task at EL0");
+    // SAFETY: this is the only MPU handle
+    let mut mpu = unsafe { El1Mpu::new() };
+    let e = task::run(&mut mpu, &config, at_el0, stack);
+    panic!("could not configure the MPU: {e:?}")
+}
+
+fn stack_region(stack: &Stack) -> El1Region {
+    El1Region {
+        range: stack.lower() as *const u8..=(stack.higher() - 1) as *const u8,
+        shareability: El1Shareability::NonShareable,
+        access: El1AccessPerms::ReadWriteEl1El0,
+        no_exec: true,
+        mair: 0,
+        enable: true,
+    }
+}
+
+// NOTE semihosting does not work at EL0 by default, so all reporting is done through system calls
+extern "C" fn at_el0() -> ! {
+    write("hello from EL0");
+
+    if syscall::<SYS_ADD>(40, 2) == 42 {
+        write("40 + 2 = 42");
+    }
+
+    write("reading kernel data");
+    let value = KERNEL_DATA.load(Ordering::Relaxed);
+    unreachable!("EL0 read kernel data: {value}")
+}
+
+fn write(s: &str) {
+    syscall::<SYS_WRITE>(s.as_ptr() as u64, s.len() as u64);
+}
+
+fn syscall<const NUMBER: u16>(arg0: u64, arg1: u64) -> u64 {
+    let ret;
+    // SAFETY: the system calls are handled by the EL1 `svc_lower` handler
+    unsafe {
+        asm!(
+            "svc {number}",
+            number = const NUMBER,
+            inlateout("x0") arg0 => ret,
+            in("x1") arg1,
+            options(nostack),
+        );
+    }
+    ret
+}
+
+exception_handlers!(Handlers);
+struct Handlers;
+impl ExceptionHandlers for Handlers {
+    fn svc_lower(context: &mut StackedRegisters) {
+        task::dispatch(context, |call: Call| match call {
+            Call::Write { ptr, len } => {
+                // SAFETY: the task passes a string from its own regions, which EL1 can read too
+                let bytes = unsafe { core::slice::from_raw_parts(ptr as *const u8, len as usize) };
+                let s = core::str::from_utf8(bytes).expect("task wrote invalid UTF-8");
+                println!("EL0: {s}");
+                len
+            }
+            Call::Add { a, b } => a + b,
+        })
+        .expect("unknown system call");
+    }
+
+    extern "C" fn sync_lower(context: &mut StackedRegisters) {
+        let syndrome = Syndrome::decode(context.esr);
+        assert!(
+            matches!(
+                syndrome,
+                Syndrome::DataAbort {
+                    lower_el: true,
+                    wnr: false,
+                    ..
+                }
+            ),
+            "unexpected exception {syndrome:?}"
+        );
+        println!(
+            "task faulted reading kernel data: {}",
+            context.far as usize == KERNEL_DATA.as_ptr() as usize
+        );
+        println!(
+            "kernel data is intact: {}",
+            KERNEL_DATA.load(Ordering::Relaxed) == 42
+        );
+
+        process::exit(0)
+    }
+}
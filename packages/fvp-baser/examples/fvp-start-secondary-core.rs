@@ -0,0 +1,54 @@
+//! Checks that a parked secondary core can be started on its own stack and with its own VBAR
+// runner: FVP_BaseR_AEMv8R -f FVP_BaseR_AEMv8R_smp.cfg
+
+#![no_std]
+#![no_main]
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use aarch64_cpu::{
+    asm,
+    registers::{self, Readable},
+};
+use aarch64_pmsa_rt::{ExceptionHandlers, alloc_stack, entry, exception_handlers, smp};
+use semihosting::{println, process};
+
+static DONE: AtomicBool = AtomicBool::new(false);
+
+entry!(main);
+
+fn main() -> ! {
+    // sanity check; this FVP always starts at EL2
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    assert_eq!(2, el, "this example must run at EL2");
+
+    println!("core 0 starting core 1");
+    let stack = alloc_stack!(4096).expect("called twice");
+    smp::start_core(1, core1_main, stack).expect("could not start core 1");
+
+    while !DONE.load(Ordering::Acquire) {
+        core::hint::spin_loop();
+    }
+
+    for core_id in smp::online_cores() {
+        println!("core {core_id} is online");
+    }
+
+    process::exit(0)
+}
+
+extern "C" fn core1_main(core_id: usize) -> ! {
+    println!("core {core_id} running");
+    println!("MPIDR_EL1: {:#010x}", registers::MPIDR_EL1.get());
+    println!("VBAR_EL2 set: {}", registers::VBAR_EL2.get() != 0);
+
+    DONE.store(true, Ordering::Release);
+
+    loop {
+        asm::wfe();
+    }
+}
+
+exception_handlers!(NoOp);
+struct NoOp;
+impl ExceptionHandlers for NoOp {}
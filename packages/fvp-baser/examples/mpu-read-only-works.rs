@@ -107,7 +107,7 @@ fn enable_mpu() {
 exception_handlers!(Handlers);
 struct Handlers;
 impl ExceptionHandlers for Handlers {
-    extern "C" fn sync_current(_context: &StackedRegisters) {
+    extern "C" fn sync_current(_context: &mut StackedRegisters) {
         // [ARM-R64/D12.2.31] Exception Class for Data Abort, including
         // MPU faults
         const EC_DATA_ABORT_MPU_FAULT: u64 = 0b100101;
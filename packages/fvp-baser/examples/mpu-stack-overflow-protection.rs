@@ -123,7 +123,7 @@ fn enable_mpu() {
 exception_handlers!(Handlers);
 struct Handlers;
 impl ExceptionHandlers for Handlers {
-    extern "C" fn sync_current(_context: &StackedRegisters) {
+    extern "C" fn sync_current(_context: &mut StackedRegisters) {
         // stack is exhausted so we cannot do much here
         process::exit(0)
     }
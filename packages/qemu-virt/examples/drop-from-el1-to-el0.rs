@@ -0,0 +1,68 @@
+//! Checks that it's possible to drop from EL1 into EL0 and make SVC calls to EL1
+// runner: qemu-system-aarch64 -cpu neoverse-v1 -machine virt -nographic -semihosting -kernel
+
+#![no_std]
+#![no_main]
+
+use core::arch::asm;
+
+use aarch64_cpu::registers::{self, ESR_EL1, Readable as _};
+use aarch64_pmsa_rt::{
+    ExceptionHandlers, StackedRegisters, alloc_stack, drop_exception_level, entry,
+    exception_handlers,
+};
+use semihosting::{println, process};
+
+const SVC_HELLO: u64 = 7;
+const SVC_EXIT: u64 = 0;
+const EXPECTED_SVC_X0_ARG: u64 = 42;
+
+entry!(main);
+
+fn main() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("running at EL{el}");
+    assert_eq!(1, el, "this example must run at EL1");
+
+    let stack = alloc_stack!(4096).expect("called twice");
+    drop_exception_level(at_el0, stack);
+}
+
+// NOTE `CurrentEL` cannot be read at EL0, nor does semihosting work there by default, so all
+// reporting is done by the EL1 handler
+extern "C" fn at_el0() -> ! {
+    // SAFETY: VBAR_EL1 has been set
+    unsafe {
+        asm!("svc {imm}", imm = const SVC_HELLO, in("x0") EXPECTED_SVC_X0_ARG);
+        asm!("svc {imm}", imm = const SVC_EXIT, options(noreturn));
+    }
+}
+
+exception_handlers!(Handlers);
+struct Handlers;
+impl ExceptionHandlers for Handlers {
+    extern "C" fn sync_lower(context: &mut StackedRegisters) {
+        // [ARM-R64/C5.2.15] M[3:2] holds the Exception Level
+        let from_el = (context.spsr & 0b1100) >> 2;
+
+        assert!(
+            ESR_EL1.matches_all(ESR_EL1::EC::SVC64),
+            "unexpected exception {:#x}",
+            ESR_EL1.get()
+        );
+
+        // the immediate of the SVC instruction
+        match ESR_EL1.read(ESR_EL1::ISS) & 0xffff {
+            SVC_HELLO => {
+                println!("handling SVC #{SVC_HELLO} from EL{from_el}");
+                assert_eq!(0, from_el);
+                assert_eq!(EXPECTED_SVC_X0_ARG, context.x[0]);
+            }
+            SVC_EXIT => {
+                println!("exiting");
+                process::exit(0)
+            }
+            imm => panic!("unexpected SVC #{imm}"),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Checks that it's possible to drop from EL2 into EL0 and make SVC calls to EL2
+// runner: qemu-system-aarch64 -cpu neoverse-v1 -machine virt,virtualization=on -nographic -semihosting -kernel
+
+#![no_std]
+#![no_main]
+
+use core::arch::asm;
+
+use aarch64_cpu::{
+    registers::{self, HCR_EL2, Readable as _},
+    syndrome::Syndrome,
+};
+use aarch64_pmsa_rt::{
+    DropConfig, ExceptionHandlers, ExceptionLevel, StackedRegisters, alloc_stack,
+    drop_exception_level_with, entry, exception_handlers,
+};
+use semihosting::{println, process};
+
+const SVC_HELLO: u16 = 7;
+const SVC_EXIT: u16 = 0;
+const EXPECTED_SVC_X0_ARG: u64 = 42;
+
+entry!(main);
+
+fn main() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("running at EL{el}");
+    assert_eq!(2, el, "this example must run at EL2");
+
+    let stack = alloc_stack!(4096).expect("called twice");
+    let config = DropConfig {
+        el: ExceptionLevel::EL0,
+        ..DropConfig::next_lower()
+    };
+    drop_exception_level_with(config, at_el0, stack);
+}
+
+// NOTE `CurrentEL` cannot be read at EL0, nor does semihosting work there by default, so all
+// reporting is done by the EL2 handler
+extern "C" fn at_el0() -> ! {
+    // SAFETY: `HCR_EL2.TGE` routes the SVC calls to the EL2 vector table
+    unsafe {
+        asm!("svc {imm}", imm = const SVC_HELLO, in("x0") EXPECTED_SVC_X0_ARG);
+        asm!("svc {imm}", imm = const SVC_EXIT, options(noreturn));
+    }
+}
+
+exception_handlers!(Handlers);
+struct Handlers;
+impl ExceptionHandlers for Handlers {
+    fn svc_lower(context: &mut StackedRegisters) {
+        let from_el = context.exception_level();
+        assert!(HCR_EL2.is_set(HCR_EL2::TGE), "EL0 was not run with TGE set");
+
+        match Syndrome::decode(context.esr) {
+            Syndrome::Svc(SVC_HELLO) => {
+                println!("handling SVC #{SVC_HELLO} from {from_el:?} at EL2");
+                assert_eq!(ExceptionLevel::EL0, from_el);
+                assert_eq!(EXPECTED_SVC_X0_ARG, context.x[0]);
+            }
+            Syndrome::Svc(SVC_EXIT) => {
+                println!("exiting");
+                process::exit(0)
+            }
+            syndrome => panic!("unexpected exception {syndrome:?}"),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! Checks that it's possible to drop from EL3 into EL2 with the requested DAIF
+// runner: qemu-system-aarch64 -cpu neoverse-v1 -machine virt,virtualization=on,secure=on -nographic -semihosting -kernel
+
+#![no_std]
+#![no_main]
+
+use aarch64_cpu::registers::{self, Readable as _};
+use aarch64_pmsa_rt::{
+    Daif, DropConfig, ExceptionHandlers, ExceptionLevel, alloc_stack, drop_exception_level_with,
+    entry, exception_handlers,
+};
+use semihosting::{println, process};
+
+entry!(main);
+
+fn main() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("running at EL{el}");
+    assert_eq!(3, el, "this example must run at EL3");
+
+    let stack = alloc_stack!(4096).expect("called twice");
+    let config = DropConfig {
+        el: ExceptionLevel::EL2,
+        daif: Daif::MASKED,
+        non_secure: true,
+    };
+    drop_exception_level_with(config, at_el2, stack);
+}
+
+extern "C" fn at_el2() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("dropped to EL{el}");
+    assert_eq!(2, el);
+
+    println!("DAIF masked: {}", Daif::current() == Daif::MASKED);
+
+    process::exit(0)
+}
+
+exception_handlers!(NoOp);
+struct NoOp;
+impl ExceptionHandlers for NoOp {}
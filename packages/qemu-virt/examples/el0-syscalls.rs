@@ -0,0 +1,129 @@
+//! Checks that the system calls of an EL0 task are decoded and handled at EL1
+// runner: qemu-system-aarch64 -cpu neoverse-v1 -machine virt -nographic -semihosting -kernel
+
+#![no_std]
+#![no_main]
+
+use core::arch::asm;
+
+use aarch64_cpu::registers::{self, Readable as _};
+use aarch64_pmsa_rt::{
+    ExceptionHandlers, ExceptionLevel, StackedRegisters, alloc_stack, drop_exception_level, entry,
+    exception_handlers,
+    task::{self, Syscall},
+};
+use semihosting::{println, process};
+
+const SYS_EXIT: u16 = 0;
+const SYS_WRITE: u16 = 1;
+const SYS_ADD: u16 = 2;
+const SYS_UNKNOWN: u16 = 99;
+
+/// Returned to the task for system calls that are not known
+const UNKNOWN: u64 = u64::MAX;
+
+enum Call {
+    Exit { code: i32 },
+    Write { ptr: u64, len: u64 },
+    Add { a: u64, b: u64 },
+}
+
+impl Syscall for Call {
+    fn decode(number: u16, args: [u64; 8]) -> Option<Self> {
+        match number {
+            SYS_EXIT => Some(Call::Exit {
+                code: args[0] as i32,
+            }),
+            SYS_WRITE => Some(Call::Write {
+                ptr: args[0],
+                len: args[1],
+            }),
+            SYS_ADD => Some(Call::Add {
+                a: args[0],
+                b: args[1],
+            }),
+            _ => None,
+        }
+    }
+}
+
+entry!(main);
+
+fn main() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("running at EL{el}");
+    assert_eq!(1, el, "this example must run at EL1");
+
+    let stack = alloc_stack!(4096).expect("called twice");
+    drop_exception_level(at_el0, stack);
+}
+
+// NOTE semihosting does not work at EL0 by default, so all reporting is done through system calls
+extern "C" fn at_el0() -> ! {
+    write("hello from EL0");
+
+    let sum = syscall::<SYS_ADD>(40, 2);
+
+    if syscall::<SYS_UNKNOWN>(0, 0) == UNKNOWN {
+        write("unknown system call was rejected");
+    }
+
+    syscall::<SYS_EXIT>(sum, 0);
+    unreachable!()
+}
+
+fn write(s: &str) {
+    syscall::<SYS_WRITE>(s.as_ptr() as u64, s.len() as u64);
+}
+
+fn syscall<const NUMBER: u16>(arg0: u64, arg1: u64) -> u64 {
+    let ret;
+    // SAFETY: the system calls are handled by the EL1 `svc_lower` handler
+    unsafe {
+        asm!(
+            "svc {number}",
+            number = const NUMBER,
+            inlateout("x0") arg0 => ret,
+            in("x1") arg1,
+            options(nostack),
+        );
+    }
+    ret
+}
+
+exception_handlers!(Handlers);
+struct Handlers;
+impl ExceptionHandlers for Handlers {
+    fn svc_lower(context: &mut StackedRegisters) {
+        let from_el = context.exception_level();
+        assert_eq!(
+            ExceptionLevel::EL0,
+            from_el,
+            "unexpected SVC from {from_el:?}"
+        );
+
+        let result = task::dispatch(context, |call: Call| match call {
+            Call::Exit { code } => {
+                println!("task exited with code {code}");
+                process::exit(0)
+            }
+            Call::Write { ptr, len } => {
+                // SAFETY: the task passes a valid string; this example does not restrict EL0's
+                // access to memory, so EL1 can read whatever EL0 can
+                let bytes = unsafe { core::slice::from_raw_parts(ptr as *const u8, len as usize) };
+                let s = core::str::from_utf8(bytes).expect("task wrote invalid UTF-8");
+                println!("EL0: {s}");
+                len
+            }
+            Call::Add { a, b } => {
+                println!("add({a}, {b}) from EL0");
+                a + b
+            }
+        });
+
+        if let Err(e) = result {
+            println!("rejected SVC: {e:?}");
+            context.x[0] = UNKNOWN;
+        }
+    }
+}
@@ -97,7 +97,7 @@ fn main() -> ! {
 exception_handlers!(Handlers);
 struct Handlers;
 impl ExceptionHandlers for Handlers {
-    extern "C" fn sync_current(context: &StackedRegisters) {
+    extern "C" fn sync_current(context: &mut StackedRegisters) {
         const RAM_SIZE: usize = 128 * 1024 * 1024;
         const RAM_START: usize = 0x4000_0000;
         const ARM_INSN_SIZE: usize = 4;
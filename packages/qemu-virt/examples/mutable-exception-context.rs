@@ -0,0 +1,117 @@
+//! Checks that exception handlers can change the registers of the interrupted code, including its
+//! SP, and skip the instruction that caused the exception
+// runner: qemu-system-aarch64 -cpu neoverse-v1 -machine virt -nographic -semihosting -kernel
+
+#![no_std]
+#![no_main]
+
+use core::arch::asm;
+
+use aarch64_cpu::{
+    registers::{self, Readable as _},
+    syndrome::Syndrome,
+};
+use aarch64_pmsa_rt::{
+    ExceptionHandlers, StackedRegisters, alloc_stack, drop_exception_level, entry,
+    exception_handlers,
+};
+use semihosting::{println, process};
+
+const BRK_ADD_41: u16 = 16;
+const SVC_DOUBLE: u16 = 1;
+const SVC_EXIT: u16 = 0;
+
+entry!(main);
+
+fn main() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("running at EL{el}");
+    assert_eq!(1, el, "this example must run at EL1");
+
+    let result: u64;
+    // SAFETY: VBAR_EL1 has been set and the handler skips the BRK instruction
+    unsafe {
+        asm!(
+            "mov x1, sp",
+            "brk #{imm}",
+            imm = const BRK_ADD_41,
+            inout("x0") 1u64 => result,
+            out("x1") _,
+        )
+    }
+    println!("BRK returned {result}");
+
+    let stack = alloc_stack!(4096).expect("called twice");
+    drop_exception_level(at_el0, stack);
+}
+
+// NOTE semihosting does not work at EL0 so the results are reported by the EL1 handler
+extern "C" fn at_el0() -> ! {
+    let result: u64;
+    let sp_seen: u64;
+    let sp_before: u64;
+    let sp_after: u64;
+    // SAFETY: VBAR_EL1 has been set and SP is restored before leaving the block
+    unsafe {
+        asm!(
+            "mov {before}, sp",
+            "svc #{imm}",
+            "mov {after}, sp",
+            "mov sp, {before}",
+            imm = const SVC_DOUBLE,
+            before = out(reg) sp_before,
+            after = out(reg) sp_after,
+            inout("x0") 21u64 => result,
+            out("x1") sp_seen,
+        )
+    }
+
+    // SAFETY: VBAR_EL1 has been set
+    unsafe {
+        asm!(
+            "svc #{imm}",
+            imm = const SVC_EXIT,
+            in("x0") result,
+            in("x1") u64::from(sp_seen == sp_before),
+            in("x2") u64::from(sp_after == sp_before - 16),
+            options(noreturn),
+        )
+    }
+}
+
+exception_handlers!(Handlers);
+struct Handlers;
+impl ExceptionHandlers for Handlers {
+    extern "C" fn sync_current(context: &mut StackedRegisters) {
+        match Syndrome::decode(context.esr) {
+            Syndrome::Brk(BRK_ADD_41) => {
+                println!("handling BRK #{BRK_ADD_41}");
+                // X1 holds SP as it was right before the BRK instruction
+                println!("SP matches: {}", context.sp as u64 == context.x[1]);
+
+                context.x[0] += 41;
+                // the preferred return address is the BRK instruction itself
+                context.skip_instruction();
+            }
+            syndrome => panic!("unexpected exception {syndrome:?}"),
+        }
+    }
+
+    extern "C" fn sync_lower(context: &mut StackedRegisters) {
+        match Syndrome::decode(context.esr) {
+            Syndrome::Svc(SVC_DOUBLE) => {
+                context.x[0] *= 2;
+                context.x[1] = context.sp as u64;
+                // the EL0 code observes and then undoes this change of SP_EL0
+                context.sp -= 16;
+            }
+            Syndrome::Svc(SVC_EXIT) => {
+                println!("SVC #{SVC_DOUBLE} returned {}", context.x[0]);
+                println!("SP_EL0 read by the handler: {}", context.x[1] != 0);
+                println!("SP_EL0 written back: {}", context.x[2] != 0);
+                process::exit(0)
+            }
+            syndrome => panic!("unexpected exception {syndrome:?}"),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Checks that threads can be switched cooperatively and preempted by the Generic Timer interrupt
+// runner: qemu-system-aarch64 -cpu neoverse-v1 -machine virt,gic-version=3 -nographic -semihosting -kernel
+
+#![no_std]
+#![no_main]
+
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use aarch64_cpu::{
+    fpsimd,
+    gic_cpu::{self, Group, IntId},
+    registers::{self, CNTFRQ_EL0, CNTP_CTL_EL0, CNTP_TVAL_EL0, Readable as _, Writeable as _},
+};
+use aarch64_pmsa_rt::{
+    Daif, ExceptionHandlers, StackedRegisters, alloc_stack, entry, exception_handlers,
+    thread::{self, Context},
+};
+use semihosting::{println, process};
+
+/// GICv3 Distributor of the `virt` machine
+const GICD: usize = 0x0800_0000;
+/// GICv3 Redistributor of core 0; its SGI and PPI registers are in the next 64 KiB frame
+const GICR: usize = 0x080a_0000;
+const GICR_SGI: usize = GICR + 0x1_0000;
+
+/// EL1 Physical Timer interrupt
+const TIMER: IntId = IntId::ppi(14);
+
+const MAIN_THREAD_POINTER: usize = 0x1234;
+
+/// The boot thread and threads A and B
+static mut CONTEXTS: [Context; 3] = [const { Context::empty() }; 3];
+static mut FPSIMD: [fpsimd::State; 3] = [const { fpsimd::State::new() }; 3];
+/// Index into `CONTEXTS` of the running thread
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+static PREEMPTIONS: AtomicUsize = AtomicUsize::new(0);
+static COUNTS: [AtomicU64; 2] = [const { AtomicU64::new(0) }; 2];
+static THREAD_POINTERS: [AtomicUsize; 2] = [const { AtomicUsize::new(0) }; 2];
+
+entry!(main);
+
+fn main() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("running at EL{el}");
+    assert_eq!(1, el, "this example must run at EL1");
+
+    let stack_a = alloc_stack!(8192).expect("called twice");
+    let stack_b = alloc_stack!(8192).expect("called twice");
+    let fpsimd: *mut fpsimd::State = (&raw mut FPSIMD).cast();
+    // SAFETY: no other thread is running yet, and each FP/SIMD state is only borrowed once
+    unsafe {
+        CONTEXTS[0] = Context::empty().with_fpsimd(&mut *fpsimd);
+        CONTEXTS[1] = Context::new(spin, 0, stack_a, Daif::UNMASKED)
+            .with_thread_pointer(1)
+            .with_fpsimd(&mut *fpsimd.add(1));
+        CONTEXTS[2] = Context::new(spin, 1, stack_b, Daif::UNMASKED)
+            .with_thread_pointer(2)
+            .with_fpsimd(&mut *fpsimd.add(2));
+    }
+    thread::set_thread_pointer(MAIN_THREAD_POINTER);
+
+    enable_timer_interrupt();
+    CNTP_TVAL_EL0.set(period());
+    CNTP_CTL_EL0.write(CNTP_CTL_EL0::ENABLE::SET);
+
+    // the boot thread keeps IRQs masked, so it only gets back control once thread B is preempted
+    println!("switching to thread A");
+    CURRENT.store(1, Ordering::Relaxed);
+    // SAFETY: IRQs are masked, so the IRQ handler does not access the contexts concurrently
+    unsafe { thread::switch(&raw mut CONTEXTS[0], &raw const CONTEXTS[1]) };
+
+    CNTP_CTL_EL0.write(CNTP_CTL_EL0::ENABLE::CLEAR);
+    println!(
+        "back in main after {} preemptions",
+        PREEMPTIONS.load(Ordering::Relaxed)
+    );
+    for (name, count) in ["A", "B"].iter().zip(&COUNTS) {
+        println!("thread {name} ran: {}", count.load(Ordering::Relaxed) > 0);
+    }
+    println!(
+        "thread pointers: {:?}",
+        THREAD_POINTERS
+            .each_ref()
+            .map(|tp| tp.load(Ordering::Relaxed))
+    );
+    println!(
+        "main thread pointer restored: {}",
+        thread::thread_pointer() == MAIN_THREAD_POINTER
+    );
+
+    process::exit(0)
+}
+
+/// Never yields; only the timer interrupt switches away from it
+extern "C" fn spin(index: usize) -> ! {
+    THREAD_POINTERS[index].store(thread::thread_pointer(), Ordering::Relaxed);
+    loop {
+        COUNTS[index].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// 10 ms
+fn period() -> u64 {
+    CNTFRQ_EL0.get() / 100
+}
+
+/// Routes the timer interrupt to this core as a Group 1 interrupt
+fn enable_timer_interrupt() {
+    let bit = 1 << TIMER.raw();
+    // SAFETY: the GIC registers are at these addresses on the `virt` machine; the GIC does not
+    // implement two Security states, so GICD_CTLR uses the single Security state layout
+    unsafe {
+        // GICD_CTLR: ARE (4) and EnableGrp1 (1)
+        write32(GICD, 1 << 4 | 1 << 1);
+
+        // GICR_WAKER: clear ProcessorSleep (1) and wait for ChildrenAsleep (2) to clear
+        let waker = (GICR + 0x14) as *mut u32;
+        waker.write_volatile(waker.read_volatile() & !(1 << 1));
+        while waker.read_volatile() & (1 << 2) != 0 {}
+
+        // GICR_IGROUPR0, GICR_IPRIORITYR<n> and GICR_ISENABLER0
+        write32(GICR_SGI + 0x80, bit);
+        ((GICR_SGI + 0x400 + TIMER.raw() as usize) as *mut u8).write_volatile(0x80);
+        write32(GICR_SGI + 0x100, bit);
+    }
+
+    gic_cpu::enable_system_registers();
+    gic_cpu::set_priority_mask(0xff);
+    gic_cpu::enable_group(Group::Group1, true);
+}
+
+unsafe fn write32(addr: usize, value: u32) {
+    // SAFETY: the caller passes the address of a GIC register
+    unsafe { (addr as *mut u32).write_volatile(value) }
+}
+
+exception_handlers!(Handlers);
+struct Handlers;
+impl ExceptionHandlers for Handlers {
+    extern "C" fn irq_current(_context: &mut StackedRegisters) {
+        let intid = gic_cpu::acknowledge(Group::Group1).expect("spurious interrupt");
+        assert_eq!(TIMER, intid, "unexpected interrupt");
+        CNTP_TVAL_EL0.set(period());
+        // the next thread runs with IRQs unmasked, so complete the interrupt first
+        gic_cpu::end_of_interrupt(intid, Group::Group1);
+
+        let current = CURRENT.load(Ordering::Relaxed);
+        let next = (current + 1) % 3;
+        CURRENT.store(next, Ordering::Relaxed);
+        PREEMPTIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: IRQs are masked, so nothing else accesses the contexts; the handler resumes
+        // once the interrupted thread is switched back to
+        unsafe { thread::switch(&raw mut CONTEXTS[current], &raw const CONTEXTS[next]) };
+    }
+}
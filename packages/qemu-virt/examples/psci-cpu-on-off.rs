@@ -0,0 +1,102 @@
+//! Checks that a secondary core can be powered on and off through PSCI, and that the system can be
+//! shut down through it
+// runner: qemu-system-aarch64 -cpu neoverse-v1 -machine virt -nographic -semihosting -smp 2 -kernel
+
+#![no_std]
+#![no_main]
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use aarch64_cpu::{
+    registers::{self, Readable},
+    smccc::{
+        Conduit, Version,
+        psci::{AffinityState, Psci},
+    },
+};
+use aarch64_pmsa_rt::{ExceptionHandlers, alloc_stack, entry, exception_handlers};
+use semihosting::println;
+
+// without EL2 and EL3, QEMU implements PSCI itself and expects HVC calls
+static PSCI: Psci = Psci::new(Conduit::Hvc);
+const CORE1_MPIDR: u64 = 1;
+
+static CORE1_RUNNING: AtomicBool = AtomicBool::new(false);
+static CORE1_MAY_STOP: AtomicBool = AtomicBool::new(false);
+
+entry!(main);
+
+fn main() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("running at EL{el}");
+    assert_eq!(1, el, "this example must run at EL1");
+
+    let version = PSCI.version().expect("PSCI v0.1");
+    assert!(version >= Version { major: 0, minor: 2 });
+
+    print_core1_state();
+
+    println!("starting core 1");
+    let stack = alloc_stack!(4096).expect("called twice");
+    // SAFETY: `core1_entry` sets up what the Rust code running on core 1 needs
+    unsafe {
+        PSCI.cpu_on(
+            CORE1_MPIDR,
+            core1_entry as *const () as u64,
+            stack.higher() as u64,
+        )
+    }
+    .expect("CPU_ON failed");
+
+    while !CORE1_RUNNING.load(Ordering::Acquire) {
+        core::hint::spin_loop();
+    }
+    print_core1_state();
+
+    CORE1_MAY_STOP.store(true, Ordering::Release);
+    while PSCI.affinity_info(CORE1_MPIDR, 0) != Ok(AffinityState::Off) {
+        core::hint::spin_loop();
+    }
+    print_core1_state();
+
+    println!("powering off");
+    panic!("SYSTEM_OFF failed: {:?}", PSCI.system_off())
+}
+
+fn print_core1_state() {
+    let state = PSCI
+        .affinity_info(CORE1_MPIDR, 0)
+        .expect("AFFINITY_INFO failed");
+    println!("core 1 is {state:?}");
+}
+
+/// Entered by core 1 with the MMU off, an UNKNOWN SP and the context ID, the top of its stack, in
+/// X0
+#[unsafe(naked)]
+extern "C" fn core1_entry() -> ! {
+    core::arch::naked_asm!(
+        r#"
+            mov     sp, x0
+            mrs     x0, CPACR_EL1       // enable FP/SIMD, which Rust code is free to use
+            orr     x0, x0, #(0b11 << 20)
+            msr     CPACR_EL1, x0
+            isb
+            b       {main}
+        "#,
+        main = sym core1_main,
+    )
+}
+
+extern "C" fn core1_main() -> ! {
+    println!("core 1 running");
+    CORE1_RUNNING.store(true, Ordering::Release);
+
+    while !CORE1_MAY_STOP.load(Ordering::Acquire) {
+        core::hint::spin_loop();
+    }
+    panic!("CPU_OFF failed: {:?}", PSCI.cpu_off())
+}
+
+exception_handlers!(NoOp);
+struct NoOp;
+impl ExceptionHandlers for NoOp {}
@@ -0,0 +1,144 @@
+//! Checks that EL3 can serve the PSCI calls of an EL1 guest through an SMCCC dispatcher
+// runner: qemu-system-aarch64 -cpu neoverse-v1 -machine virt,secure=on -nographic -semihosting -smp 2 -kernel
+
+#![no_std]
+#![no_main]
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use aarch64_cpu::{
+    registers::{self, Readable as _},
+    smccc::{
+        self, Conduit,
+        psci::{AffinityState, Psci},
+    },
+};
+use aarch64_pmsa_rt::{
+    Daif, DropConfig, ExceptionHandlers, ExceptionLevel, StackedRegisters, alloc_stack,
+    drop_exception_level_with, entry, exception_handlers,
+    smccc::{
+        Dispatcher,
+        psci::{self, PsciServer},
+    },
+};
+use semihosting::{println, process};
+
+static PSCI_SERVER: PsciServer = PsciServer {
+    system_off: power_off,
+};
+static DISPATCHER: Dispatcher = Dispatcher {
+    psci: Some(&PSCI_SERVER),
+    ..Dispatcher::new()
+};
+
+static PSCI: Psci = Psci::new(Conduit::Smc);
+const CORE1_MPIDR: u64 = 1;
+
+static CORE1_RUNNING: AtomicBool = AtomicBool::new(false);
+static CORE1_MAY_STOP: AtomicBool = AtomicBool::new(false);
+
+entry!(main);
+
+fn main() -> ! {
+    // observably QEMU keeps all the cores but the first one halted when booting
+    // at EL2 and lower so we need EL3 for this test
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("running at EL{el}");
+    assert_eq!(3, el, "this example must run at EL3");
+
+    let stack = alloc_stack!(4096).expect("called twice");
+    psci::set_stack(1, stack).expect("could not set the stack of core 1");
+
+    let stack = alloc_stack!(4096).expect("called twice");
+    let config = DropConfig {
+        el: ExceptionLevel::EL1,
+        daif: Daif::MASKED,
+        non_secure: true,
+    };
+    drop_exception_level_with(config, at_el1, stack);
+}
+
+fn power_off() -> ! {
+    println!("powering off");
+    process::exit(0)
+}
+
+extern "C" fn at_el1() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("running at EL{el}");
+
+    // no Arm Architecture service is registered
+    println!("SMCCC_VERSION: {:?}", smccc::version(Conduit::Smc));
+    let version = PSCI.version().expect("PSCI_VERSION failed");
+    println!("PSCI_VERSION: {}.{}", version.major, version.minor);
+
+    print_core1_state();
+
+    println!("starting core 1");
+    let stack = alloc_stack!(4096).expect("called twice");
+    // SAFETY: `core1_entry` sets up what the Rust code running on core 1 needs
+    unsafe {
+        PSCI.cpu_on(
+            CORE1_MPIDR,
+            core1_entry as *const () as u64,
+            stack.higher() as u64,
+        )
+    }
+    .expect("CPU_ON failed");
+
+    while !CORE1_RUNNING.load(Ordering::Acquire) {
+        core::hint::spin_loop();
+    }
+    print_core1_state();
+
+    CORE1_MAY_STOP.store(true, Ordering::Release);
+    while PSCI.affinity_info(CORE1_MPIDR, 0) != Ok(AffinityState::Off) {
+        core::hint::spin_loop();
+    }
+    print_core1_state();
+
+    panic!("SYSTEM_OFF failed: {:?}", PSCI.system_off())
+}
+
+fn print_core1_state() {
+    let state = PSCI
+        .affinity_info(CORE1_MPIDR, 0)
+        .expect("AFFINITY_INFO failed");
+    println!("core 1 is {state:?}");
+}
+
+/// Entered by core 1 with the MMU off, an UNKNOWN SP and the context ID, the top of its stack, in
+/// X0
+#[unsafe(naked)]
+extern "C" fn core1_entry() -> ! {
+    core::arch::naked_asm!(
+        r#"
+            mov     sp, x0
+            mrs     x0, CPACR_EL1       // enable FP/SIMD, which Rust code is free to use
+            orr     x0, x0, #(0b11 << 20)
+            msr     CPACR_EL1, x0
+            isb
+            b       {main}
+        "#,
+        main = sym core1_main,
+    )
+}
+
+extern "C" fn core1_main() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("core 1 running at EL{el}");
+    CORE1_RUNNING.store(true, Ordering::Release);
+
+    while !CORE1_MAY_STOP.load(Ordering::Acquire) {
+        core::hint::spin_loop();
+    }
+    panic!("CPU_OFF failed: {:?}", PSCI.cpu_off())
+}
+
+exception_handlers!(Handlers);
+struct Handlers;
+impl ExceptionHandlers for Handlers {
+    fn hvc_smc_lower(context: &mut StackedRegisters) {
+        assert!(DISPATCHER.dispatch(context), "not an SMCCC call");
+    }
+}
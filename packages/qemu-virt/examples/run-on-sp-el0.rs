@@ -0,0 +1,50 @@
+//! Checks that thread code can run on SP_EL0 while its exceptions are handled on SP_EL1
+// runner: qemu-system-aarch64 -cpu neoverse-v1 -machine virt -nographic -semihosting -kernel
+
+#![no_std]
+#![no_main]
+
+use core::arch::asm;
+
+use aarch64_cpu::{
+    registers::{self, Readable as _, SPSel},
+    syndrome::Syndrome,
+};
+use aarch64_pmsa_rt::{
+    ExceptionHandlers, StackedRegisters, alloc_stack, entry, exception_handlers, run_on_sp_el0,
+};
+use semihosting::{println, process};
+
+entry!(main);
+
+fn main() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("running at EL{el}");
+    assert_eq!(1, el, "this example must run at EL1");
+
+    let stack = alloc_stack!(4096).expect("called twice");
+    run_on_sp_el0(thread, stack)
+}
+
+extern "C" fn thread() -> ! {
+    println!("thread uses SP_EL0: {}", SPSel.matches_all(SPSel::SP::EL0));
+
+    // SAFETY: VBAR_EL1 has been set
+    unsafe { asm!("mov x1, sp", "svc #0", out("x1") _) }
+
+    println!("returned from SVC");
+    process::exit(0)
+}
+
+exception_handlers!(Handlers);
+struct Handlers;
+impl ExceptionHandlers for Handlers {
+    extern "C" fn sync_current_sp0(context: &mut StackedRegisters) {
+        assert_eq!(Syndrome::Svc(0), Syndrome::decode(context.esr));
+
+        println!("handling SVC taken with SP_EL0");
+        println!("handler uses SP_EL1: {}", SPSel.matches_all(SPSel::SP::ELx));
+        // X1 holds SP as it was right before the SVC instruction
+        println!("SP_EL0 matches: {}", context.sp as u64 == context.x[1]);
+    }
+}
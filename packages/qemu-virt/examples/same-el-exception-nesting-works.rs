@@ -31,7 +31,7 @@ static COUNT: AtomicUsize = AtomicUsize::new(0);
 exception_handlers!(Handlers);
 struct Handlers;
 impl ExceptionHandlers for Handlers {
-    extern "C" fn sync_current(context: &StackedRegisters) {
+    extern "C" fn sync_current(context: &mut StackedRegisters) {
         let count = COUNT.fetch_add(1, atomic::Ordering::Relaxed);
 
         match count {
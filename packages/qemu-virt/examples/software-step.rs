@@ -0,0 +1,82 @@
+//! Checks that EL2 can single-step EL1 code, one Software Step exception per instruction
+// runner: qemu-system-aarch64 -cpu neoverse-v1 -machine virt,virtualization=on -nographic -semihosting -kernel
+
+#![no_std]
+#![no_main]
+
+use core::{
+    arch::asm,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use aarch64_cpu::registers::{self, Readable as _};
+use aarch64_pmsa_rt::{
+    ExceptionHandlers, StackedRegisters, alloc_stack, drop_exception_level, entry,
+    exception_handlers, step,
+};
+use semihosting::{println, process};
+
+const START_STEPPING: u64 = 1;
+const STOP_STEPPING: u64 = 2;
+
+static STEPS: AtomicUsize = AtomicUsize::new(0);
+
+entry!(main);
+
+fn main() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("running at EL{el}");
+    assert_eq!(2, el, "this example must run at EL2");
+
+    let stack = alloc_stack!(4096).expect("called twice");
+    drop_exception_level(at_el1, stack);
+}
+
+extern "C" fn at_el1() -> ! {
+    let el = registers::CurrentEL.read(registers::CurrentEL::EL);
+    println!("dropped to EL{el}");
+    assert_eq!(1, el);
+
+    // SAFETY: VBAR_EL2 has been set
+    unsafe {
+        asm!(
+            "hvc 0",
+            // stepped: NOP, NOP, MOV and HVC
+            "nop",
+            "nop",
+            "mov x0, #{stop}",
+            "hvc 0",
+            stop = const STOP_STEPPING,
+            inout("x0") START_STEPPING => _,
+        )
+    }
+
+    println!("EL1 was stepped {} times", STEPS.load(Ordering::Relaxed));
+
+    process::exit(0)
+}
+
+exception_handlers!(Handlers);
+struct Handlers;
+impl ExceptionHandlers for Handlers {
+    extern "C" fn sync_lower(context: &mut StackedRegisters) {
+        match context.x[0] {
+            START_STEPPING => {
+                println!("start stepping");
+                step::enable();
+            }
+            STOP_STEPPING => {
+                println!("stop stepping");
+                step::disable();
+            }
+            x0 => panic!("unexpected HVC argument {x0}"),
+        }
+    }
+
+    fn software_step_lower(context: &mut StackedRegisters) {
+        let steps = STEPS.fetch_add(1, Ordering::Relaxed) + 1;
+        println!("software step {steps}");
+
+        context.set_software_step(true);
+    }
+}
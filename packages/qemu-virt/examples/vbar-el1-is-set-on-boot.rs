@@ -32,7 +32,7 @@ fn main() -> ! {
 exception_handlers!(Handlers);
 struct Handlers;
 impl ExceptionHandlers for Handlers {
-    extern "C" fn sync_current(context: &StackedRegisters) {
+    extern "C" fn sync_current(context: &mut StackedRegisters) {
         let el = registers::CurrentEL.read(registers::CurrentEL::EL);
         println!("handling SVC at EL{el}");
 
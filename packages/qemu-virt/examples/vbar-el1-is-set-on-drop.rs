@@ -44,7 +44,7 @@ extern "C" fn at_el1() -> ! {
 exception_handlers!(Handlers);
 struct Handlers;
 impl ExceptionHandlers for Handlers {
-    extern "C" fn sync_current(context: &StackedRegisters) {
+    extern "C" fn sync_current(context: &mut StackedRegisters) {
         let el = registers::CurrentEL.read(registers::CurrentEL::EL);
 
         println!("handling SVC at EL{el}");